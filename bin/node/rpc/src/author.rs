@@ -0,0 +1,178 @@
+///////////////////////////////////////////////////////////////////////////////
+//
+//  Copyright 2018-2021 Robonomics Network <research@robonomics.network>
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+//
+///////////////////////////////////////////////////////////////////////////////
+//! Extrinsic submission and watching RPC methods under robonomics subscriptions limit.
+
+use codec::Decode;
+use futures::{task::Spawn, FutureExt, StreamExt, TryFutureExt, TryStreamExt};
+use jsonrpc_core::futures::{future::Future as Future01, sink::Sink as Sink01};
+use jsonrpc_derive::rpc;
+use jsonrpc_pubsub::{manager::SubscriptionManager, typed::Subscriber, SubscriptionId};
+use robonomics_primitives::{Block, Hash};
+use sc_rpc::SubscriptionTaskExecutor;
+use sc_rpc_api::author::error::Error;
+use sp_blockchain::HeaderBackend;
+use sp_core::Bytes;
+use sp_runtime::{generic::BlockId, traits::Block as BlockT};
+use sp_transaction_pool::{
+    error::IntoPoolError, TransactionPool, TransactionSource, TransactionStatus,
+};
+use std::sync::Arc;
+
+use crate::subscriptions::SubscriptionsLimit;
+
+/// Extrinsic watching methods of `author` RPC that count in subscriptions limit.
+#[rpc]
+pub trait WatchExtrinsicApi {
+    /// RPC Metadata
+    type Metadata;
+
+    /// Submit an extrinsic and subscribe to its status updates.
+    #[pubsub(
+        subscription = "author_extrinsicUpdate",
+        subscribe,
+        name = "author_submitAndWatchExtrinsic"
+    )]
+    fn watch_extrinsic(
+        &self,
+        metadata: Self::Metadata,
+        subscriber: Subscriber<TransactionStatus<Hash, Hash>>,
+        bytes: Bytes,
+    );
+
+    /// Unsubscribe from extrinsic status updates.
+    #[pubsub(
+        subscription = "author_extrinsicUpdate",
+        unsubscribe,
+        name = "author_unwatchExtrinsic"
+    )]
+    fn unwatch_extrinsic(
+        &self,
+        metadata: Option<Self::Metadata>,
+        id: SubscriptionId,
+    ) -> jsonrpc_core::Result<bool>;
+}
+
+/// Implements the [`WatchExtrinsicApi`] RPC trait, overrides default `author` handlers.
+pub struct WatchExtrinsic<C, P> {
+    client: Arc<C>,
+    pool: Arc<P>,
+    executor: Arc<SubscriptionTaskExecutor>,
+    manager: SubscriptionManager,
+    limit: SubscriptionsLimit,
+}
+
+impl<C, P> WatchExtrinsic<C, P> {
+    /// Create new `WatchExtrinsic` with the given client, transaction pool, subscriptions
+    /// executor and limit.
+    pub fn new(
+        client: Arc<C>,
+        pool: Arc<P>,
+        executor: SubscriptionTaskExecutor,
+        limit: SubscriptionsLimit,
+    ) -> Self {
+        let executor = Arc::new(executor);
+        Self {
+            client,
+            pool,
+            manager: SubscriptionManager::new(executor.clone()),
+            executor,
+            limit,
+        }
+    }
+}
+
+impl<C, P> WatchExtrinsicApi for WatchExtrinsic<C, P>
+where
+    C: HeaderBackend<Block> + Send + Sync + 'static,
+    P: TransactionPool<Block = Block, Hash = Hash> + 'static,
+{
+    type Metadata = sc_rpc::Metadata;
+
+    fn watch_extrinsic(
+        &self,
+        metadata: Self::Metadata,
+        subscriber: Subscriber<TransactionStatus<Hash, Hash>>,
+        bytes: Bytes,
+    ) {
+        let slot = match self.limit.acquire(&metadata) {
+            Ok(slot) => slot,
+            Err(e) => {
+                let _ = subscriber.reject(e);
+                return;
+            }
+        };
+
+        let xt = match <Block as BlockT>::Extrinsic::decode(&mut &bytes[..]) {
+            Ok(xt) => xt,
+            Err(e) => {
+                let _ = subscriber.reject(Error::from(e).into());
+                return;
+            }
+        };
+
+        let best_hash = self.client.info().best_hash;
+        let submit = self
+            .pool
+            .submit_and_watch(&BlockId::hash(best_hash), TransactionSource::External, xt)
+            .map_err(|e| {
+                e.into_pool_error()
+                    .map(Error::from)
+                    .unwrap_or_else(|e| Error::Verification(Box::new(e)))
+            });
+
+        let manager = self.manager.clone();
+        let future = async move {
+            let statuses = match submit.await {
+                Ok(statuses) => statuses,
+                Err(e) => {
+                    let _ = subscriber.reject(e.into());
+                    return;
+                }
+            };
+
+            let statuses = statuses.map(|status| Ok::<_, ()>(Ok(status))).compat();
+            manager.add(subscriber, |sink| {
+                sink.sink_map_err(|e| {
+                    log::warn!(
+                        target: "robonomics::rpc",
+                        "Error sending notifications: {:?}",
+                        e
+                    )
+                })
+                .send_all(statuses)
+                .map(move |_| drop(slot))
+            });
+        };
+
+        if let Err(e) = self.executor.spawn_obj(future.boxed().into()) {
+            log::warn!(
+                target: "robonomics::rpc",
+                "Unable to spawn extrinsic watching: {:?}",
+                e
+            );
+        }
+    }
+
+    fn unwatch_extrinsic(
+        &self,
+        _metadata: Option<Self::Metadata>,
+        id: SubscriptionId,
+    ) -> jsonrpc_core::Result<bool> {
+        Ok(self.manager.cancel(id))
+    }
+}
@@ -0,0 +1,215 @@
+///////////////////////////////////////////////////////////////////////////////
+//
+//  Copyright 2018-2021 Robonomics Network <research@robonomics.network>
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+//
+///////////////////////////////////////////////////////////////////////////////
+//! Robonomics datalog RPC methods.
+
+use codec::{Decode, Encode};
+use futures::{FutureExt, TryFutureExt};
+use jsonrpc_core::{futures::future::result, Error as RpcError, ErrorCode};
+use jsonrpc_derive::rpc;
+use local_runtime::{Call, SignedExtra, UncheckedExtrinsic};
+use robonomics_primitives::{AccountId, Block, Hash, Index};
+use sc_rpc_api::DenyUnsafe;
+use sp_api::{Core, ProvideRuntimeApi};
+use sp_blockchain::HeaderBackend;
+use sp_core::{crypto::key_types::ACCOUNT, sr25519, Bytes};
+use sp_keystore::{SyncCryptoStore, SyncCryptoStorePtr};
+use sp_runtime::{
+    generic::{BlockId, Era, SignedPayload},
+    OpaqueExtrinsic,
+};
+use sp_transaction_pool::{TransactionPool, TransactionSource};
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+};
+use substrate_frame_rpc_system::AccountNonceApi;
+
+use crate::FutureResult;
+
+/// Datalog RPC methods.
+#[rpc]
+pub trait DatalogApi {
+    /// Sign datalog record by keystore key of given account and submit it.
+    ///
+    /// Account nonce is managed by node, returns extrinsic hash.
+    #[rpc(name = "robonomicsIo_recordDatalog")]
+    fn record_datalog(&self, account: AccountId, payload: Bytes) -> FutureResult<Hash>;
+}
+
+/// Implements the [`DatalogApi`] RPC trait for submitting datalog records.
+pub struct Datalog<C, P> {
+    client: Arc<C>,
+    pool: Arc<P>,
+    keystore: SyncCryptoStorePtr,
+    deny_unsafe: DenyUnsafe,
+    /// Last nonce used by this node for each account.
+    nonces: Arc<Mutex<HashMap<AccountId, Index>>>,
+}
+
+impl<C, P> Datalog<C, P> {
+    /// Create new `Datalog` with the given client, pool and keystore.
+    pub fn new(
+        client: Arc<C>,
+        pool: Arc<P>,
+        keystore: SyncCryptoStorePtr,
+        deny_unsafe: DenyUnsafe,
+    ) -> Self {
+        Self {
+            client,
+            pool,
+            keystore,
+            deny_unsafe,
+            nonces: Default::default(),
+        }
+    }
+}
+
+/// Forget last nonce of the account when it's the given one, later nonces are kept.
+fn reset_nonce(nonces: &Mutex<HashMap<AccountId, Index>>, account: &AccountId, nonce: Index) {
+    let mut nonces = nonces.lock().expect("nonces lock poisoned; qed");
+    if nonces.get(account) == Some(&nonce) {
+        nonces.remove(account);
+    }
+}
+
+fn rpc_error(code: i64, message: &str, e: impl std::fmt::Debug) -> RpcError {
+    RpcError {
+        code: ErrorCode::ServerError(code),
+        message: message.into(),
+        data: Some(format!("{:?}", e).into()),
+    }
+}
+
+impl<C, P> Datalog<C, P>
+where
+    C: ProvideRuntimeApi<Block> + HeaderBackend<Block>,
+    C::Api: AccountNonceApi<Block, AccountId, Index>,
+    C::Api: Core<Block>,
+{
+    /// Next account nonce that takes into account transactions sent by this node.
+    fn next_nonce(&self, at: &BlockId<Block>, account: &AccountId) -> Result<Index, RpcError> {
+        let onchain = self
+            .client
+            .runtime_api()
+            .account_nonce(at, account.clone())
+            .map_err(|e| rpc_error(1, "Unable to query nonce.", e))?;
+
+        let mut nonces = self.nonces.lock().expect("nonces lock poisoned; qed");
+        let nonce = match nonces.get(account) {
+            Some(last) if *last >= onchain => last + 1,
+            _ => onchain,
+        };
+        nonces.insert(account.clone(), nonce);
+        Ok(nonce)
+    }
+
+    /// Forget nonce of failed extrinsic, so next one is resynced with the chain.
+    fn reset_nonce(&self, account: &AccountId, nonce: Index) {
+        reset_nonce(&self.nonces, account, nonce)
+    }
+
+    /// Create signed datalog record extrinsic.
+    fn create_extrinsic(
+        &self,
+        account: AccountId,
+        payload: Vec<u8>,
+    ) -> Result<(BlockId<Block>, OpaqueExtrinsic, Index), RpcError> {
+        let info = self.client.info();
+        let at = BlockId::hash(info.best_hash);
+        let version = self
+            .client
+            .runtime_api()
+            .version(&at)
+            .map_err(|e| rpc_error(1, "Unable to query runtime version.", e))?;
+        let nonce = self.next_nonce(&at, &account)?;
+
+        let call = Call::Datalog(pallet_robonomics_datalog::Call::record(payload));
+        let extra: SignedExtra = (
+            frame_system::CheckSpecVersion::new(),
+            frame_system::CheckTxVersion::new(),
+            frame_system::CheckGenesis::new(),
+            frame_system::CheckEra::from(Era::Immortal),
+            frame_system::CheckNonce::from(nonce),
+            frame_system::CheckWeight::new(),
+            pallet_transaction_payment::ChargeTransactionPayment::from(0),
+        );
+        let raw_payload = SignedPayload::from_raw(
+            call,
+            extra,
+            (
+                version.spec_version,
+                version.transaction_version,
+                info.genesis_hash,
+                info.genesis_hash,
+                (),
+                (),
+                (),
+            ),
+        );
+
+        let public = sr25519::Public::from_raw(account.clone().into());
+        let signature = raw_payload
+            .using_encoded(|e| {
+                SyncCryptoStore::sign_with(&*self.keystore, ACCOUNT, &public.into(), e)
+            })
+            .map_err(|e| {
+                self.reset_nonce(&account, nonce);
+                rpc_error(2, "Unable to sign extrinsic.", e)
+            })?;
+        let signature = sr25519::Signature::from_slice(&signature);
+
+        let (call, extra, _) = raw_payload.deconstruct();
+        let xt =
+            UncheckedExtrinsic::new_signed(call, account.clone().into(), signature.into(), extra);
+        let xt = OpaqueExtrinsic::decode(&mut &xt.encode()[..]).map_err(|e| {
+            self.reset_nonce(&account, nonce);
+            rpc_error(3, "Unable to encode extrinsic.", e)
+        })?;
+        Ok((at, xt, nonce))
+    }
+}
+
+impl<C, P> DatalogApi for Datalog<C, P>
+where
+    C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + Send + Sync + 'static,
+    C::Api: AccountNonceApi<Block, AccountId, Index>,
+    C::Api: Core<Block>,
+    P: TransactionPool<Block = Block, Hash = Hash> + 'static,
+{
+    fn record_datalog(&self, account: AccountId, payload: Bytes) -> FutureResult<Hash> {
+        if let Err(err) = self.deny_unsafe.check_if_safe() {
+            return Box::new(result(Err(err.into())));
+        }
+
+        let (at, xt, nonce) = match self.create_extrinsic(account.clone(), payload.to_vec()) {
+            Ok(x) => x,
+            Err(e) => return Box::new(result(Err(e))),
+        };
+
+        let nonces = self.nonces.clone();
+        let submit = self
+            .pool
+            .submit_one(&at, TransactionSource::Local, xt)
+            .map_err(move |e| {
+                // Rejected extrinsic doesn't use the nonce, it's requeried on next record.
+                reset_nonce(&nonces, &account, nonce);
+                rpc_error(4, "Unable to submit extrinsic.", e)
+            });
+        Box::new(submit.boxed().compat())
+    }
+}
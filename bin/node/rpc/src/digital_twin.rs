@@ -0,0 +1,96 @@
+///////////////////////////////////////////////////////////////////////////////
+//
+//  Copyright 2018-2021 Robonomics Network <research@robonomics.network>
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+//
+///////////////////////////////////////////////////////////////////////////////
+//! Robonomics digital twin pallet RPC methods.
+
+use codec::{Decode, Encode};
+use jsonrpc_core::{Error as RpcError, ErrorCode, Result};
+use jsonrpc_derive::rpc;
+use robonomics_primitives::{AccountId, Block, Hash};
+use sc_client_api::StorageProvider;
+use sp_blockchain::HeaderBackend;
+use sp_core::{
+    hashing::{twox_128, twox_64},
+    storage::StorageKey,
+    H256,
+};
+use sp_runtime::generic::BlockId;
+use std::{collections::BTreeMap, marker::PhantomData, sync::Arc};
+
+/// Digital twin RPC methods.
+#[rpc]
+pub trait DigitalTwinApi<BlockHash> {
+    /// Returns topic to source account map of digital twin with given id.
+    #[rpc(name = "robonomics_digitalTwin")]
+    fn digital_twin(
+        &self,
+        id: u32,
+        at: Option<BlockHash>,
+    ) -> Result<Option<BTreeMap<H256, AccountId>>>;
+}
+
+/// Implements the [`DigitalTwinApi`] RPC trait for interacting with digital twin pallet.
+pub struct DigitalTwin<C, B> {
+    client: Arc<C>,
+    _marker: PhantomData<B>,
+}
+
+impl<C, B> DigitalTwin<C, B> {
+    /// Create new `DigitalTwin` with the given reference to the client.
+    pub fn new(client: Arc<C>) -> Self {
+        Self {
+            client,
+            _marker: Default::default(),
+        }
+    }
+}
+
+/// Storage key of `DigitalTwin::DigitalTwin` map entry.
+fn digital_twin_key(id: u32) -> StorageKey {
+    let encoded = id.encode();
+    let mut key = twox_128(b"DigitalTwin").to_vec();
+    key.extend_from_slice(&twox_128(b"DigitalTwin"));
+    key.extend_from_slice(&twox_64(&encoded));
+    key.extend_from_slice(&encoded);
+    StorageKey(key)
+}
+
+impl<C, B> DigitalTwinApi<Hash> for DigitalTwin<C, B>
+where
+    C: StorageProvider<Block, B> + HeaderBackend<Block> + Send + Sync + 'static,
+    B: sc_client_api::Backend<Block> + Send + Sync + 'static,
+{
+    fn digital_twin(&self, id: u32, at: Option<Hash>) -> Result<Option<BTreeMap<H256, AccountId>>> {
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+        let twin = self
+            .client
+            .storage(&at, &digital_twin_key(id))
+            .map_err(|e| RpcError {
+                code: ErrorCode::ServerError(1),
+                message: "Unable to read digital twin.".into(),
+                data: Some(format!("{:?}", e).into()),
+            })?;
+
+        twin.map(|data| BTreeMap::decode(&mut &data.0[..]))
+            .transpose()
+            .map_err(|e| RpcError {
+                code: ErrorCode::ServerError(2),
+                message: "Unable to decode digital twin.".into(),
+                data: Some(format!("{:?}", e).into()),
+            })
+    }
+}
@@ -0,0 +1,102 @@
+///////////////////////////////////////////////////////////////////////////////
+//
+//  Copyright 2018-2021 Robonomics Network <research@robonomics.network>
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+//
+///////////////////////////////////////////////////////////////////////////////
+//! Robonomics extrinsic dry run RPC methods.
+
+use codec::{Decode, Encode};
+use jsonrpc_core::{Error as RpcError, ErrorCode, Result};
+use jsonrpc_derive::rpc;
+use pallet_transaction_payment_rpc::TransactionPaymentRuntimeApi;
+use robonomics_primitives::{Balance, Block, Hash};
+use serde::{Deserialize, Serialize};
+use sp_api::ProvideRuntimeApi;
+use sp_block_builder::BlockBuilder;
+use sp_blockchain::HeaderBackend;
+use sp_core::Bytes;
+use sp_runtime::{generic::BlockId, traits::Block as BlockT};
+use std::sync::Arc;
+
+/// Result of extrinsic dry run.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DryRunResult {
+    /// Is extrinsic valid and dispatched successfully.
+    pub success: bool,
+    /// SCALE encoded `ApplyExtrinsicResult` that contains `DispatchOutcome`.
+    pub outcome: Bytes,
+    /// Estimated weight of extrinsic.
+    pub weight: u64,
+}
+
+/// Extrinsic dry run RPC methods.
+#[rpc]
+pub trait DryRunApi<BlockHash> {
+    /// Execute extrinsic against state at given block without broadcasting it.
+    #[rpc(name = "robonomics_dryRun")]
+    fn dry_run(&self, extrinsic: Bytes, at: Option<BlockHash>) -> Result<DryRunResult>;
+}
+
+/// Implements the [`DryRunApi`] RPC trait for checking extrinsics before submit.
+pub struct DryRun<C> {
+    client: Arc<C>,
+}
+
+impl<C> DryRun<C> {
+    /// Create new `DryRun` with the given reference to the client.
+    pub fn new(client: Arc<C>) -> Self {
+        Self { client }
+    }
+}
+
+impl<C> DryRunApi<Hash> for DryRun<C>
+where
+    C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + Send + Sync + 'static,
+    C::Api: BlockBuilder<Block>,
+    C::Api: TransactionPaymentRuntimeApi<Block, Balance>,
+{
+    fn dry_run(&self, extrinsic: Bytes, at: Option<Hash>) -> Result<DryRunResult> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+        let encoded_len = extrinsic.len() as u32;
+
+        let uxt = <Block as BlockT>::Extrinsic::decode(&mut &*extrinsic).map_err(|e| RpcError {
+            code: ErrorCode::InvalidParams,
+            message: "Unable to decode extrinsic.".into(),
+            data: Some(format!("{:?}", e).into()),
+        })?;
+
+        let info = api
+            .query_info(&at, uxt.clone(), encoded_len)
+            .map_err(|e| RpcError {
+                code: ErrorCode::ServerError(1),
+                message: "Unable to estimate extrinsic weight.".into(),
+                data: Some(format!("{:?}", e).into()),
+            })?;
+
+        let result = api.apply_extrinsic(&at, uxt).map_err(|e| RpcError {
+            code: ErrorCode::ServerError(2),
+            message: "Unable to dry run extrinsic.".into(),
+            data: Some(format!("{:?}", e).into()),
+        })?;
+
+        Ok(DryRunResult {
+            success: matches!(result, Ok(Ok(()))),
+            outcome: result.encode().into(),
+            weight: info.weight,
+        })
+    }
+}
@@ -0,0 +1,288 @@
+///////////////////////////////////////////////////////////////////////////////
+//
+//  Copyright 2018-2021 Robonomics Network <research@robonomics.network>
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+//
+///////////////////////////////////////////////////////////////////////////////
+//! Robonomics account extrinsics watching RPC methods.
+
+use codec::{Compact, Decode, Encode};
+use frame_support::dispatch::GetCallMetadata;
+use futures::{stream, StreamExt, TryStreamExt};
+use jsonrpc_core::futures::{future::Future as Future01, sink::Sink as Sink01};
+use jsonrpc_derive::rpc;
+use jsonrpc_pubsub::{manager::SubscriptionManager, typed::Subscriber, SubscriptionId};
+use local_runtime::UncheckedExtrinsic;
+use robonomics_primitives::{AccountId, Balance, Block, Hash};
+use sc_client_api::{BlockBackend, BlockchainEvents};
+use sc_rpc::SubscriptionTaskExecutor;
+use serde::{Deserialize, Serialize};
+use sp_blockchain::HeaderBackend;
+use sp_runtime::{
+    generic::BlockId,
+    traits::{BlakeTwo256, Hash as HashT},
+    MultiAddress, OpaqueExtrinsic,
+};
+use sp_transaction_pool::{InPoolTransaction, TransactionPool};
+use std::sync::Arc;
+
+use crate::{finalized::finalized_blocks, subscriptions::SubscriptionsLimit};
+
+/// Status of extrinsic signed by watched account.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", tag = "status")]
+pub enum ExtrinsicStatus {
+    /// Extrinsic is ready in the transaction pool.
+    Ready {
+        /// Extrinsic hash.
+        hash: Hash,
+    },
+    /// Extrinsic is included into imported block.
+    InBlock {
+        /// Extrinsic hash.
+        hash: Hash,
+        /// Block hash.
+        block: Hash,
+    },
+    /// Extrinsic is included into finalized block.
+    Finalized {
+        /// Extrinsic hash.
+        hash: Hash,
+        /// Block hash.
+        block: Hash,
+    },
+}
+
+/// Decoded extrinsic from transaction pool ready queue.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PendingExtrinsic {
+    /// Extrinsic hash.
+    pub hash: Hash,
+    /// Signer account, `None` for unsigned extrinsic.
+    pub sender: Option<AccountId>,
+    /// Called pallet name.
+    pub pallet: String,
+    /// Called function name.
+    pub call: String,
+    /// Tip paid to block author.
+    pub tip: Balance,
+}
+
+/// Account extrinsics watching RPC methods.
+#[rpc]
+pub trait ExtrinsicsApi {
+    /// RPC Metadata
+    type Metadata;
+
+    /// Subscribe to status updates of extrinsics signed by given account.
+    #[pubsub(
+        subscription = "robonomics_extrinsicsFrom",
+        subscribe,
+        name = "robonomics_watchExtrinsicsFrom"
+    )]
+    fn watch_extrinsics_from(
+        &self,
+        metadata: Self::Metadata,
+        subscriber: Subscriber<ExtrinsicStatus>,
+        account: AccountId,
+    );
+
+    /// Returns decoded extrinsics that are ready in the transaction pool.
+    #[rpc(name = "robonomics_pendingExtrinsics")]
+    fn pending_extrinsics(&self) -> jsonrpc_core::Result<Vec<PendingExtrinsic>>;
+
+    /// Unsubscribe from account extrinsics status updates.
+    #[pubsub(
+        subscription = "robonomics_extrinsicsFrom",
+        unsubscribe,
+        name = "robonomics_unwatchExtrinsicsFrom"
+    )]
+    fn unwatch_extrinsics_from(
+        &self,
+        metadata: Option<Self::Metadata>,
+        id: SubscriptionId,
+    ) -> jsonrpc_core::Result<bool>;
+}
+
+/// Implements the [`ExtrinsicsApi`] RPC trait for watching account extrinsics.
+pub struct Extrinsics<C, P> {
+    client: Arc<C>,
+    pool: Arc<P>,
+    manager: SubscriptionManager,
+    limit: SubscriptionsLimit,
+}
+
+impl<C, P> Extrinsics<C, P> {
+    /// Create new `Extrinsics` with the given client, transaction pool, subscriptions executor
+    /// and limit.
+    pub fn new(
+        client: Arc<C>,
+        pool: Arc<P>,
+        executor: SubscriptionTaskExecutor,
+        limit: SubscriptionsLimit,
+    ) -> Self {
+        Self {
+            client,
+            pool,
+            manager: SubscriptionManager::new(Arc::new(executor)),
+            limit,
+        }
+    }
+}
+
+/// Check that extrinsic is signed by given account.
+fn is_signed_by(xt: &OpaqueExtrinsic, account: &AccountId) -> bool {
+    match UncheckedExtrinsic::decode(&mut &xt.encode()[..]) {
+        Ok(xt) => {
+            matches!(xt.signature, Some((MultiAddress::Id(signer), _, _)) if &signer == account)
+        }
+        Err(_) => false,
+    }
+}
+
+/// Decode sender, call and tip of pool extrinsic, `None` for unknown extrinsic format.
+fn decode_pending(hash: Hash, xt: &OpaqueExtrinsic) -> Option<PendingExtrinsic> {
+    let xt = UncheckedExtrinsic::decode(&mut &xt.encode()[..]).ok()?;
+    let metadata = xt.function.get_call_metadata();
+    let (sender, tip) = match xt.signature {
+        Some((address, _, extra)) => {
+            let sender = match address {
+                MultiAddress::Id(account) => Some(account),
+                _ => None,
+            };
+            // Transaction payment extension keeps just compact encoded tip.
+            let tip = Compact::<Balance>::decode(&mut &extra.6.encode()[..]).ok()?;
+            (sender, tip.0)
+        }
+        None => (None, 0),
+    };
+
+    Some(PendingExtrinsic {
+        hash,
+        sender,
+        pallet: metadata.pallet_name.into(),
+        call: metadata.function_name.into(),
+        tip,
+    })
+}
+
+/// Hashes of extrinsics signed by given account in the block.
+fn block_extrinsics<C>(client: &C, block: Hash, account: &AccountId) -> Vec<Hash>
+where
+    C: BlockBackend<Block>,
+{
+    match client.block_body(&BlockId::Hash(block)) {
+        Ok(Some(body)) => body
+            .iter()
+            .filter(|xt| is_signed_by(xt, account))
+            .map(|xt| BlakeTwo256::hash_of(xt))
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+impl<C, P> ExtrinsicsApi for Extrinsics<C, P>
+where
+    C: BlockchainEvents<Block> + BlockBackend<Block> + HeaderBackend<Block> + Send + Sync + 'static,
+    P: TransactionPool<Block = Block, Hash = Hash> + 'static,
+{
+    type Metadata = sc_rpc::Metadata;
+
+    fn watch_extrinsics_from(
+        &self,
+        metadata: Self::Metadata,
+        subscriber: Subscriber<ExtrinsicStatus>,
+        account: AccountId,
+    ) {
+        let slot = match self.limit.acquire(&metadata) {
+            Ok(slot) => slot,
+            Err(e) => {
+                let _ = subscriber.reject(e);
+                return;
+            }
+        };
+
+        let pool = self.pool.clone();
+        let ready_account = account.clone();
+        let ready = self
+            .pool
+            .import_notification_stream()
+            .filter_map(move |hash| {
+                let status = pool
+                    .ready_transaction(&hash)
+                    .filter(|tx| is_signed_by(tx.data(), &ready_account))
+                    .map(|_| ExtrinsicStatus::Ready { hash });
+                futures::future::ready(status)
+            });
+
+        let client = self.client.clone();
+        let block_account = account.clone();
+        let in_block = self
+            .client
+            .import_notification_stream()
+            .map(move |notification| {
+                let block = notification.hash;
+                let statuses: Vec<_> = block_extrinsics(&*client, block, &block_account)
+                    .into_iter()
+                    .map(|hash| ExtrinsicStatus::InBlock { hash, block })
+                    .collect();
+                stream::iter(statuses)
+            })
+            .flatten();
+
+        let client = self.client.clone();
+        let finalized_blocks = finalized_blocks(self.client.clone())
+            .map(move |(_, block)| {
+                let statuses: Vec<_> = block_extrinsics(&*client, block, &account)
+                    .into_iter()
+                    .map(|hash| ExtrinsicStatus::Finalized { hash, block })
+                    .collect();
+                stream::iter(statuses)
+            })
+            .flatten();
+
+        let statuses = stream::select(stream::select(ready, in_block), finalized_blocks)
+            .map(|status| Ok::<_, ()>(Ok(status)))
+            .compat();
+
+        self.manager.add(subscriber, |sink| {
+            sink.sink_map_err(|e| {
+                log::warn!(
+                    target: "robonomics::rpc",
+                    "Error sending notifications: {:?}",
+                    e
+                )
+            })
+            .send_all(statuses)
+            .map(move |_| drop(slot))
+        });
+    }
+
+    fn pending_extrinsics(&self) -> jsonrpc_core::Result<Vec<PendingExtrinsic>> {
+        Ok(self
+            .pool
+            .ready()
+            .filter_map(|tx| decode_pending(*tx.hash(), tx.data()))
+            .collect())
+    }
+
+    fn unwatch_extrinsics_from(
+        &self,
+        _metadata: Option<Self::Metadata>,
+        id: SubscriptionId,
+    ) -> jsonrpc_core::Result<bool> {
+        Ok(self.manager.cancel(id))
+    }
+}
@@ -0,0 +1,196 @@
+///////////////////////////////////////////////////////////////////////////////
+//
+//  Copyright 2018-2021 Robonomics Network <research@robonomics.network>
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+//
+///////////////////////////////////////////////////////////////////////////////
+//! Robonomics transaction fee estimation RPC methods.
+
+use codec::{Decode, Encode};
+use futures::{FutureExt, TryFutureExt};
+use jsonrpc_core::{futures::future::result, Error as RpcError, ErrorCode};
+use jsonrpc_derive::rpc;
+use local_runtime::{Call, UncheckedExtrinsic};
+use pallet_transaction_payment::RuntimeDispatchInfo;
+use pallet_transaction_payment_rpc::TransactionPaymentRuntimeApi;
+use robonomics_primitives::{AccountId, Balance, Block};
+use sc_client_api::light::{future_header, Fetcher, RemoteBlockchain, RemoteCallRequest};
+use serde::{Deserialize, Serialize};
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_core::Bytes;
+use sp_rpc::number::NumberOrHex;
+use sp_runtime::{generic::BlockId, OpaqueExtrinsic};
+use std::sync::Arc;
+
+use crate::FutureResult;
+
+/// Robonomics call to estimate fee for.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum RobonomicsCall {
+    /// Datalog record with given payload.
+    Datalog(Bytes),
+    /// Robot launch with given parameter.
+    Launch {
+        /// Robot account.
+        robot: AccountId,
+        /// Launch parameter.
+        param: bool,
+    },
+}
+
+impl RobonomicsCall {
+    /// Unsigned extrinsic of the call.
+    fn into_extrinsic(self) -> OpaqueExtrinsic {
+        let call = match self {
+            RobonomicsCall::Datalog(payload) => {
+                Call::Datalog(pallet_robonomics_datalog::Call::record(payload.to_vec()))
+            }
+            RobonomicsCall::Launch { robot, param } => {
+                Call::Launch(pallet_robonomics_launch::Call::launch(robot, param))
+            }
+        };
+        let uxt = UncheckedExtrinsic::new_unsigned(call);
+        OpaqueExtrinsic::decode(&mut &uxt.encode()[..]).expect("extrinsic is encoded above; qed")
+    }
+}
+
+/// Fee estimation RPC methods.
+#[rpc]
+pub trait FeeApi {
+    /// Returns fee of given robonomics call in XRT base units.
+    ///
+    /// Signed extrinsic is longer than the unsigned one, so length could be set by `len_hint`.
+    #[rpc(name = "robonomics_estimateFee")]
+    fn estimate_fee(
+        &self,
+        call: RobonomicsCall,
+        len_hint: Option<u32>,
+    ) -> FutureResult<NumberOrHex>;
+}
+
+/// Extrinsic and its length used for fee estimation.
+fn fee_query(call: RobonomicsCall, len_hint: Option<u32>) -> (OpaqueExtrinsic, u32) {
+    let uxt = call.into_extrinsic();
+    let len = len_hint.unwrap_or_else(|| uxt.encoded_size() as u32);
+    (uxt, len)
+}
+
+fn fee_error<E: std::fmt::Debug>(e: E) -> RpcError {
+    RpcError {
+        code: ErrorCode::ServerError(1),
+        message: "Unable to query fee info.".into(),
+        data: Some(format!("{:?}", e).into()),
+    }
+}
+
+/// Implements the [`FeeApi`] RPC trait using full client runtime API.
+pub struct Fee<C> {
+    client: Arc<C>,
+}
+
+impl<C> Fee<C> {
+    /// Create new `Fee` with the given reference to the client.
+    pub fn new(client: Arc<C>) -> Self {
+        Self { client }
+    }
+}
+
+impl<C> FeeApi for Fee<C>
+where
+    C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + Send + Sync + 'static,
+    C::Api: TransactionPaymentRuntimeApi<Block, Balance>,
+{
+    fn estimate_fee(
+        &self,
+        call: RobonomicsCall,
+        len_hint: Option<u32>,
+    ) -> FutureResult<NumberOrHex> {
+        let at = BlockId::hash(self.client.info().best_hash);
+        let (uxt, len) = fee_query(call, len_hint);
+        let fee = self
+            .client
+            .runtime_api()
+            .query_info(&at, uxt, len)
+            .map(|info| info.partial_fee.into())
+            .map_err(fee_error);
+
+        Box::new(result(fee))
+    }
+}
+
+/// Implements the [`FeeApi`] RPC trait using light client remote calls.
+pub struct LightFee<C, F> {
+    client: Arc<C>,
+    remote_blockchain: Arc<dyn RemoteBlockchain<Block>>,
+    fetcher: Arc<F>,
+}
+
+impl<C, F> LightFee<C, F> {
+    /// Create new `LightFee` with the given light client dependencies.
+    pub fn new(
+        client: Arc<C>,
+        remote_blockchain: Arc<dyn RemoteBlockchain<Block>>,
+        fetcher: Arc<F>,
+    ) -> Self {
+        Self {
+            client,
+            remote_blockchain,
+            fetcher,
+        }
+    }
+}
+
+impl<C, F> FeeApi for LightFee<C, F>
+where
+    C: HeaderBackend<Block> + Send + Sync + 'static,
+    F: Fetcher<Block> + 'static,
+{
+    fn estimate_fee(
+        &self,
+        call: RobonomicsCall,
+        len_hint: Option<u32>,
+    ) -> FutureResult<NumberOrHex> {
+        let best_hash = self.client.info().best_hash;
+        let best_header = future_header(
+            &*self.remote_blockchain,
+            &*self.fetcher,
+            BlockId::hash(best_hash),
+        );
+        let fetcher = self.fetcher.clone();
+        let call_data = fee_query(call, len_hint).encode();
+
+        let fee = async move {
+            let header = best_header
+                .await
+                .map_err(fee_error)?
+                .ok_or_else(|| fee_error(format!("Unknown block {}", best_hash)))?;
+            let info = fetcher
+                .remote_call(RemoteCallRequest {
+                    block: best_hash,
+                    header,
+                    method: "TransactionPaymentApi_query_info".into(),
+                    call_data,
+                    retry_count: None,
+                })
+                .await
+                .map_err(fee_error)?;
+            let info = RuntimeDispatchInfo::<Balance>::decode(&mut &info[..]).map_err(fee_error)?;
+            Ok(info.partial_fee.into())
+        };
+
+        Box::new(fee.boxed().compat())
+    }
+}
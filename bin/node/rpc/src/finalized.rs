@@ -0,0 +1,47 @@
+///////////////////////////////////////////////////////////////////////////////
+//
+//  Copyright 2018-2021 Robonomics Network <research@robonomics.network>
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+//
+///////////////////////////////////////////////////////////////////////////////
+//! Stream of finalized blocks.
+
+use futures::{stream, Stream, StreamExt};
+use robonomics_primitives::{Block, BlockNumber, Hash};
+use sc_client_api::BlockchainEvents;
+use sp_blockchain::HeaderBackend;
+use sp_runtime::traits::Header;
+use std::sync::Arc;
+
+/// Number and hash of every block finalized since the stream is created.
+///
+/// Notice: Notification could be received for the last one of several finalized blocks,
+/// so all blocks since the previous notification are yielded in ascending order.
+pub fn finalized_blocks<C>(client: Arc<C>) -> impl Stream<Item = (BlockNumber, Hash)>
+where
+    C: BlockchainEvents<Block> + HeaderBackend<Block>,
+{
+    let mut finalized = client.info().finalized_number;
+    client
+        .finality_notification_stream()
+        .map(move |notification| {
+            let number = *notification.header.number();
+            let blocks: Vec<_> = (finalized + 1..=number)
+                .filter_map(|n| client.hash(n).ok().flatten().map(|hash| (n, hash)))
+                .collect();
+            finalized = finalized.max(number);
+            stream::iter(blocks)
+        })
+        .flatten()
+}
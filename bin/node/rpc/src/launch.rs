@@ -0,0 +1,350 @@
+///////////////////////////////////////////////////////////////////////////////
+//
+//  Copyright 2018-2021 Robonomics Network <research@robonomics.network>
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+//
+///////////////////////////////////////////////////////////////////////////////
+//! Robonomics launch notifications RPC methods.
+
+use codec::Decode;
+use futures::{future::Either, StreamExt, TryStreamExt};
+use jsonrpc_core::futures::{future::Future as Future01, sink::Sink as Sink01};
+use jsonrpc_derive::rpc;
+use jsonrpc_pubsub::{manager::SubscriptionManager, typed::Subscriber, SubscriptionId};
+use local_runtime::Event;
+use robonomics_primitives::{AccountId, Block, BlockNumber, Hash};
+use sc_client_api::{BlockchainEvents, StorageProvider};
+use sc_rpc::SubscriptionTaskExecutor;
+use serde::{Deserialize, Serialize};
+use sp_blockchain::HeaderBackend;
+use sp_core::{hashing::twox_128, storage::StorageKey};
+use sp_runtime::{generic::BlockId, traits::Header};
+use std::{marker::PhantomData, sync::Arc};
+
+use crate::{finalized::finalized_blocks, subscriptions::SubscriptionsLimit};
+
+type EventRecord = frame_system::EventRecord<Event, Hash>;
+
+/// Finalized robot launch.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LaunchNotification {
+    /// Finalized block that contains launch.
+    pub block_hash: Hash,
+    /// Finalized block number.
+    pub block_number: BlockNumber,
+    /// Launch sender account.
+    pub sender: AccountId,
+    /// Launch parameter.
+    pub param: bool,
+}
+
+/// Robot launch status on the best chain.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "status", content = "launch", rename_all = "camelCase")]
+pub enum LaunchStatus {
+    /// Launch is included into the best chain block.
+    Included(LaunchNotification),
+    /// Block that contains launch is finalized.
+    Finalized(LaunchNotification),
+    /// Block that contains launch is reorged out before finalization.
+    Retracted(LaunchNotification),
+}
+
+/// Launch notifications RPC methods.
+#[rpc]
+pub trait LaunchApi {
+    /// RPC Metadata
+    type Metadata;
+
+    /// Subscribe to finalized launches of given robot account.
+    #[pubsub(
+        subscription = "robonomics_finalizedLaunch",
+        subscribe,
+        name = "robonomics_subscribeFinalizedLaunch"
+    )]
+    fn subscribe_finalized_launch(
+        &self,
+        metadata: Self::Metadata,
+        subscriber: Subscriber<LaunchNotification>,
+        account: AccountId,
+    );
+
+    /// Unsubscribe from finalized launches.
+    #[pubsub(
+        subscription = "robonomics_finalizedLaunch",
+        unsubscribe,
+        name = "robonomics_unsubscribeFinalizedLaunch"
+    )]
+    fn unsubscribe_finalized_launch(
+        &self,
+        metadata: Option<Self::Metadata>,
+        id: SubscriptionId,
+    ) -> jsonrpc_core::Result<bool>;
+
+    /// Subscribe to launches of given robot account on the best chain until finalization.
+    #[pubsub(
+        subscription = "robonomics_launchStatus",
+        subscribe,
+        name = "robonomics_subscribeLaunchStatus"
+    )]
+    fn subscribe_launch_status(
+        &self,
+        metadata: Self::Metadata,
+        subscriber: Subscriber<LaunchStatus>,
+        account: AccountId,
+    );
+
+    /// Unsubscribe from launch statuses.
+    #[pubsub(
+        subscription = "robonomics_launchStatus",
+        unsubscribe,
+        name = "robonomics_unsubscribeLaunchStatus"
+    )]
+    fn unsubscribe_launch_status(
+        &self,
+        metadata: Option<Self::Metadata>,
+        id: SubscriptionId,
+    ) -> jsonrpc_core::Result<bool>;
+}
+
+/// Implements the [`LaunchApi`] RPC trait for robots waiting for launch finality.
+pub struct Launch<C, B> {
+    client: Arc<C>,
+    manager: SubscriptionManager,
+    limit: SubscriptionsLimit,
+    _marker: PhantomData<B>,
+}
+
+impl<C, B> Launch<C, B> {
+    /// Create new `Launch` with the given client, subscriptions executor and limit.
+    pub fn new(
+        client: Arc<C>,
+        executor: SubscriptionTaskExecutor,
+        limit: SubscriptionsLimit,
+    ) -> Self {
+        Self {
+            client,
+            manager: SubscriptionManager::new(Arc::new(executor)),
+            limit,
+            _marker: Default::default(),
+        }
+    }
+}
+
+/// Launches of given robot in the block with given number.
+fn block_launches<C, B>(
+    client: &C,
+    number: BlockNumber,
+    robot: &AccountId,
+) -> Vec<LaunchNotification>
+where
+    C: StorageProvider<Block, B> + HeaderBackend<Block>,
+    B: sc_client_api::Backend<Block>,
+{
+    match client.hash(number) {
+        Ok(Some(hash)) => block_launches_at(client, hash, number, robot),
+        _ => Vec::new(),
+    }
+}
+
+/// Launches of given robot in the block with given hash.
+fn block_launches_at<C, B>(
+    client: &C,
+    block_hash: Hash,
+    number: BlockNumber,
+    robot: &AccountId,
+) -> Vec<LaunchNotification>
+where
+    C: StorageProvider<Block, B>,
+    B: sc_client_api::Backend<Block>,
+{
+    let key = StorageKey([twox_128(b"System"), twox_128(b"Events")].concat());
+    let events = match client.storage(&BlockId::Hash(block_hash), &key) {
+        Ok(Some(data)) => Vec::<EventRecord>::decode(&mut &data.0[..]).unwrap_or_default(),
+        _ => return Vec::new(),
+    };
+
+    events
+        .into_iter()
+        .filter_map(|record| match record.event {
+            Event::pallet_robonomics_launch(pallet_robonomics_launch::Event::NewLaunch(
+                sender,
+                launch_robot,
+                param,
+            )) if &launch_robot == robot => Some(LaunchNotification {
+                block_hash,
+                block_number: number,
+                sender,
+                param,
+            }),
+            _ => None,
+        })
+        .collect()
+}
+
+impl<C, B> LaunchApi for Launch<C, B>
+where
+    C: BlockchainEvents<Block>
+        + StorageProvider<Block, B>
+        + HeaderBackend<Block>
+        + Send
+        + Sync
+        + 'static,
+    B: sc_client_api::Backend<Block> + Send + Sync + 'static,
+{
+    type Metadata = sc_rpc::Metadata;
+
+    fn subscribe_finalized_launch(
+        &self,
+        metadata: Self::Metadata,
+        subscriber: Subscriber<LaunchNotification>,
+        account: AccountId,
+    ) {
+        let slot = match self.limit.acquire(&metadata) {
+            Ok(slot) => slot,
+            Err(e) => {
+                let _ = subscriber.reject(e);
+                return;
+            }
+        };
+
+        let client = self.client.clone();
+        let stream = finalized_blocks(self.client.clone())
+            .map(move |(number, _)| {
+                futures::stream::iter(block_launches(&*client, number, &account))
+            })
+            .flatten()
+            .map(|launch| Ok::<_, ()>(Ok(launch)))
+            .compat();
+
+        self.manager.add(subscriber, |sink| {
+            sink.sink_map_err(|e| {
+                log::warn!(
+                    target: "robonomics::rpc",
+                    "Error sending notifications: {:?}",
+                    e
+                )
+            })
+            .send_all(stream)
+            .map(move |_| drop(slot))
+        });
+    }
+
+    fn unsubscribe_finalized_launch(
+        &self,
+        _metadata: Option<Self::Metadata>,
+        id: SubscriptionId,
+    ) -> jsonrpc_core::Result<bool> {
+        Ok(self.manager.cancel(id))
+    }
+
+    fn subscribe_launch_status(
+        &self,
+        metadata: Self::Metadata,
+        subscriber: Subscriber<LaunchStatus>,
+        account: AccountId,
+    ) {
+        let slot = match self.limit.acquire(&metadata) {
+            Ok(slot) => slot,
+            Err(e) => {
+                let _ = subscriber.reject(e);
+                return;
+            }
+        };
+
+        let client = self.client.clone();
+        // Launches included into the best chain and waiting for finalization.
+        let mut pending: Vec<LaunchNotification> = Vec::new();
+        let imports = self.client.import_notification_stream().map(Either::Left);
+        let finality = self
+            .client
+            .finality_notification_stream()
+            .map(Either::Right);
+        let stream = futures::stream::select(imports, finality)
+            .map(move |notification| {
+                let mut statuses = Vec::new();
+                match notification {
+                    Either::Left(import) if import.is_new_best => {
+                        let mut enacted = Vec::new();
+                        if let Some(route) = &import.tree_route {
+                            let retracted: Vec<_> =
+                                route.retracted().iter().map(|b| b.hash).collect();
+                            pending.retain(|launch| {
+                                if retracted.contains(&launch.block_hash) {
+                                    statuses.push(LaunchStatus::Retracted(launch.clone()));
+                                    false
+                                } else {
+                                    true
+                                }
+                            });
+                            enacted.extend(route.enacted().iter().map(|b| (b.hash, b.number)));
+                        }
+                        enacted.push((import.hash, *import.header.number()));
+
+                        for (hash, number) in enacted {
+                            for launch in block_launches_at(&*client, hash, number, &account) {
+                                if !pending.contains(&launch) {
+                                    pending.push(launch.clone());
+                                    statuses.push(LaunchStatus::Included(launch));
+                                }
+                            }
+                        }
+                    }
+                    Either::Left(_) => (),
+                    Either::Right(finalized) => {
+                        let number = *finalized.header.number();
+                        pending.retain(|launch| {
+                            if launch.block_number > number {
+                                return true;
+                            }
+                            // Launch block is either finalized or pruned as a stale fork.
+                            if client.hash(launch.block_number).ok().flatten()
+                                == Some(launch.block_hash)
+                            {
+                                statuses.push(LaunchStatus::Finalized(launch.clone()));
+                            } else {
+                                statuses.push(LaunchStatus::Retracted(launch.clone()));
+                            }
+                            false
+                        });
+                    }
+                }
+                futures::stream::iter(statuses)
+            })
+            .flatten()
+            .map(|status| Ok::<_, ()>(Ok(status)))
+            .compat();
+
+        self.manager.add(subscriber, |sink| {
+            sink.sink_map_err(|e| {
+                log::warn!(
+                    target: "robonomics::rpc",
+                    "Error sending notifications: {:?}",
+                    e
+                )
+            })
+            .send_all(stream)
+            .map(move |_| drop(slot))
+        });
+    }
+
+    fn unsubscribe_launch_status(
+        &self,
+        _metadata: Option<Self::Metadata>,
+        id: SubscriptionId,
+    ) -> jsonrpc_core::Result<bool> {
+        Ok(self.manager.cancel(id))
+    }
+}
@@ -32,11 +32,28 @@
 
 use std::sync::Arc;
 
+pub mod author;
+pub mod datalog;
+pub mod digital_twin;
+pub mod dry_run;
+pub mod extrinsics;
+pub mod fee;
+pub mod finalized;
+pub mod launch;
+pub mod nonce;
+pub mod parachain;
+pub mod randomness;
+pub mod session_keys;
+pub mod staking;
+pub mod status;
+pub mod subscriptions;
+
 use robonomics_primitives::{AccountId, Balance, Block, BlockNumber, Hash, Index};
 use sc_client_api::AuxStore;
 use sc_consensus_babe::{Config, Epoch};
 use sc_consensus_babe_rpc::BabeRpcHandler;
 use sc_consensus_epochs::SharedEpochChanges;
+use sc_consensus_manual_seal::rpc::{EngineCommand, ManualSeal, ManualSealApi};
 use sc_finality_grandpa::{
     FinalityProofProvider, GrandpaJustificationStream, SharedAuthoritySet, SharedVoterState,
 };
@@ -50,10 +67,16 @@ use sp_consensus::SelectChain;
 use sp_consensus_babe::BabeApi;
 use sp_keystore::SyncCryptoStorePtr;
 use sp_transaction_pool::TransactionPool;
+use status::{Status, StatusApi, StatusDeps};
+use subscriptions::SubscriptionsLimit;
 
 /// A IO handler that uses all Full RPC extensions.
 pub type IoHandler = jsonrpc_core::IoHandler<sc_rpc_api::Metadata>;
 
+/// Future that resolves to RPC result.
+pub type FutureResult<T> =
+    Box<dyn jsonrpc_core::futures::Future<Item = T, Error = jsonrpc_core::Error> + Send>;
+
 /// Light client extra dependencies.
 pub struct LightDeps<C, F, P> {
     /// The client instance to use.
@@ -64,6 +87,8 @@ pub struct LightDeps<C, F, P> {
     pub remote_blockchain: Arc<dyn sc_client_api::light::RemoteBlockchain<Block>>,
     /// Fetcher instance.
     pub fetcher: Arc<F>,
+    /// Node status dependencies.
+    pub status: StatusDeps,
 }
 
 /// Extra dependencies for BABE.
@@ -106,15 +131,39 @@ pub struct FullDeps<C, P, SC, B> {
     pub babe: BabeDeps,
     /// GRANDPA specific dependencies.
     pub grandpa: GrandpaDeps<B>,
+    /// Manual seal command sink, available when blocks are sealed on demand.
+    pub command_sink: Option<futures::channel::mpsc::Sender<EngineCommand<Hash>>>,
+    /// Node status dependencies, available when network is started.
+    pub status: Option<StatusDeps>,
+    /// Robonomics subscriptions per connection limit, shared by all RPC servers.
+    pub subscriptions_limit: SubscriptionsLimit,
+}
+
+/// Is runtime of the chain the local one, i.e. its extrinsics and events are `local_runtime` types.
+fn is_local_runtime<C>(client: &C) -> bool
+where
+    C: ProvideRuntimeApi<Block> + HeaderBackend<Block>,
+    C::Api: sp_api::Core<Block>,
+{
+    let at = sp_runtime::generic::BlockId::hash(client.info().best_hash);
+    client.runtime_api().version(&at).map_or(false, |version| {
+        version.spec_name == local_runtime::VERSION.spec_name
+    })
 }
 
 /// Instantiate all Full RPC extensions.
+///
+/// Notice: RPCs decoding extrinsics or events of `local_runtime` are available on chains
+/// running it only, e.g. `robonomics_launchFinalized` or `robonomics_pendingExtrinsics`.
 pub fn create_full<C, P, SC, B>(deps: FullDeps<C, P, SC, B>) -> IoHandler
 where
     C: ProvideRuntimeApi<Block>
         + HeaderBackend<Block>
         + AuxStore
         + HeaderMetadata<Block, Error = BlockChainError>
+        + sc_client_api::StorageProvider<Block, B>
+        + sc_client_api::BlockchainEvents<Block>
+        + sc_client_api::BlockBackend<Block>
         + Sync
         + Send
         + 'static,
@@ -122,12 +171,24 @@ where
     C::Api: pallet_transaction_payment_rpc::TransactionPaymentRuntimeApi<Block, Balance>,
     C::Api: BabeApi<Block>,
     C::Api: BlockBuilder<Block>,
-    P: TransactionPool + 'static,
+    C::Api: sp_api::Core<Block>,
+    P: TransactionPool<Block = Block, Hash = Hash> + 'static,
     SC: SelectChain<Block> + 'static,
     B: sc_client_api::Backend<Block> + Send + Sync + 'static,
     B::State: sc_client_api::backend::StateBackend<sp_runtime::traits::HashFor<Block>>,
 {
+    use author::{WatchExtrinsic, WatchExtrinsicApi};
+    use datalog::{Datalog, DatalogApi};
+    use digital_twin::{DigitalTwin, DigitalTwinApi};
+    use dry_run::{DryRun, DryRunApi};
+    use extrinsics::{Extrinsics, ExtrinsicsApi};
+    use fee::{Fee, FeeApi};
+    use launch::{Launch, LaunchApi};
+    use nonce::{Nonce, NonceApi};
     use pallet_transaction_payment_rpc::{TransactionPayment, TransactionPaymentApi};
+    use randomness::{Randomness, RandomnessApi};
+    use session_keys::{SessionKeysApi, SessionKeysHandler};
+    use staking::{Staking, StakingApi};
     use substrate_frame_rpc_system::{FullSystem, SystemApi};
 
     let mut io = jsonrpc_core::IoHandler::default();
@@ -139,6 +200,9 @@ where
         deny_unsafe,
         babe,
         grandpa,
+        command_sink,
+        status,
+        subscriptions_limit,
     } = deps;
 
     let BabeDeps {
@@ -154,14 +218,61 @@ where
         finality_provider,
     } = grandpa;
 
+    let is_local = is_local_runtime(&*client);
+
     io.extend_with(SystemApi::to_delegate(FullSystem::new(
         client.clone(),
-        pool,
+        pool.clone(),
+        deny_unsafe,
+    )));
+    if is_local {
+        io.extend_with(ExtrinsicsApi::to_delegate(Extrinsics::new(
+            client.clone(),
+            pool.clone(),
+            subscription_executor.clone(),
+            subscriptions_limit.clone(),
+        )));
+        io.extend_with(NonceApi::to_delegate(Nonce::new(
+            client.clone(),
+            pool.clone(),
+        )));
+    }
+    if subscriptions_limit.is_limited() {
+        // Overrides default `author` extrinsic watching to count it in the limit.
+        io.extend_with(WatchExtrinsicApi::to_delegate(WatchExtrinsic::new(
+            client.clone(),
+            pool.clone(),
+            subscription_executor.clone(),
+            subscriptions_limit.clone(),
+        )));
+    }
+    if is_local {
+        io.extend_with(DatalogApi::to_delegate(Datalog::new(
+            client.clone(),
+            pool,
+            keystore.clone(),
+            deny_unsafe,
+        )));
+    }
+    io.extend_with(SessionKeysApi::to_delegate(SessionKeysHandler::new(
+        keystore.clone(),
         deny_unsafe,
     )));
     io.extend_with(TransactionPaymentApi::to_delegate(TransactionPayment::new(
         client.clone(),
     )));
+    io.extend_with(StakingApi::to_delegate(Staking::new(client.clone())));
+    io.extend_with(DigitalTwinApi::to_delegate(DigitalTwin::new(
+        client.clone(),
+    )));
+    io.extend_with(DryRunApi::to_delegate(DryRun::new(client.clone())));
+    io.extend_with(RandomnessApi::to_delegate(Randomness::new(client.clone())));
+    if is_local {
+        io.extend_with(FeeApi::to_delegate(Fee::new(client.clone())));
+    }
+    if let Some(status) = status {
+        io.extend_with(StatusApi::to_delegate(Status::new(client.clone(), status)));
+    }
     io.extend_with(sc_consensus_babe_rpc::BabeApi::to_delegate(
         BabeRpcHandler::new(
             client.clone(),
@@ -172,6 +283,13 @@ where
             deny_unsafe,
         ),
     ));
+    if is_local {
+        io.extend_with(LaunchApi::to_delegate(Launch::new(
+            client.clone(),
+            subscription_executor.clone(),
+            subscriptions_limit,
+        )));
+    }
     io.extend_with(sc_finality_grandpa_rpc::GrandpaApi::to_delegate(
         GrandpaRpcHandler::new(
             shared_authority_set.clone(),
@@ -192,6 +310,11 @@ where
         ),
     ));
 
+    if let Some(command_sink) = command_sink {
+        // `engine_createBlock` and `engine_finalizeBlock` methods.
+        io.extend_with(ManualSealApi::to_delegate(ManualSeal::new(command_sink)));
+    }
+
     io
 }
 
@@ -204,6 +327,7 @@ where
     P: TransactionPool + 'static,
     M: jsonrpc_core::Metadata + Default,
 {
+    use fee::{FeeApi, LightFee};
     use substrate_frame_rpc_system::{LightSystem, SystemApi};
 
     let LightDeps {
@@ -211,8 +335,15 @@ where
         pool,
         remote_blockchain,
         fetcher,
+        status,
     } = deps;
     let mut io = jsonrpc_core::IoHandler::default();
+    io.extend_with(StatusApi::to_delegate(Status::new(client.clone(), status)));
+    io.extend_with(FeeApi::to_delegate(LightFee::new(
+        client.clone(),
+        remote_blockchain.clone(),
+        fetcher.clone(),
+    )));
     io.extend_with(SystemApi::<Hash, AccountId, Index>::to_delegate(
         LightSystem::new(client, remote_blockchain, fetcher, pool),
     ));
@@ -0,0 +1,97 @@
+///////////////////////////////////////////////////////////////////////////////
+//
+//  Copyright 2018-2021 Robonomics Network <research@robonomics.network>
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+//
+///////////////////////////////////////////////////////////////////////////////
+//! Robonomics account nonce RPC methods.
+
+use codec::{Compact, Decode, Encode};
+use jsonrpc_core::{Error as RpcError, ErrorCode, Result};
+use jsonrpc_derive::rpc;
+use local_runtime::UncheckedExtrinsic;
+use robonomics_primitives::{AccountId, Block, Index};
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_runtime::{generic::BlockId, MultiAddress, OpaqueExtrinsic};
+use sp_transaction_pool::{InPoolTransaction, TransactionPool};
+use std::{collections::BTreeSet, sync::Arc};
+use substrate_frame_rpc_system::AccountNonceApi;
+
+/// Account nonce RPC methods.
+#[rpc]
+pub trait NonceApi {
+    /// Returns next account index, ready pool transactions of the account are taken into account.
+    #[rpc(name = "robonomics_accountNextIndex")]
+    fn account_next_index(&self, account: AccountId) -> Result<Index>;
+}
+
+/// Implements the [`NonceApi`] RPC trait for senders of many transactions in a row.
+pub struct Nonce<C, P> {
+    client: Arc<C>,
+    pool: Arc<P>,
+}
+
+impl<C, P> Nonce<C, P> {
+    /// Create new `Nonce` with the given client and transaction pool.
+    pub fn new(client: Arc<C>, pool: Arc<P>) -> Self {
+        Self { client, pool }
+    }
+}
+
+/// Nonce of the extrinsic signed by given account.
+fn signed_nonce(xt: &OpaqueExtrinsic, account: &AccountId) -> Option<Index> {
+    let xt = UncheckedExtrinsic::decode(&mut &xt.encode()[..]).ok()?;
+    match xt.signature {
+        Some((MultiAddress::Id(signer), _, extra)) if &signer == account => {
+            // Nonce check extension keeps just compact encoded nonce.
+            Compact::<Index>::decode(&mut &extra.4.encode()[..])
+                .ok()
+                .map(|nonce| nonce.0)
+        }
+        _ => None,
+    }
+}
+
+impl<C, P> NonceApi for Nonce<C, P>
+where
+    C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + Send + Sync + 'static,
+    C::Api: AccountNonceApi<Block, AccountId, Index>,
+    P: TransactionPool<Block = Block> + 'static,
+{
+    fn account_next_index(&self, account: AccountId) -> Result<Index> {
+        let at = BlockId::hash(self.client.info().best_hash);
+        let nonce = self
+            .client
+            .runtime_api()
+            .account_nonce(&at, account.clone())
+            .map_err(|e| RpcError {
+                code: ErrorCode::ServerError(1),
+                message: "Unable to query account nonce.".into(),
+                data: Some(format!("{:?}", e).into()),
+            })?;
+
+        // Skip pool transactions that go one after another from the on-chain nonce.
+        let pending: BTreeSet<Index> = self
+            .pool
+            .ready()
+            .filter_map(|tx| signed_nonce(tx.data(), &account))
+            .collect();
+        let mut next = nonce;
+        while pending.contains(&next) {
+            next += 1;
+        }
+        Ok(next)
+    }
+}
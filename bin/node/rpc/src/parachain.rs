@@ -0,0 +1,101 @@
+///////////////////////////////////////////////////////////////////////////////
+//
+//  Copyright 2018-2021 Robonomics Network <research@robonomics.network>
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+//
+///////////////////////////////////////////////////////////////////////////////
+//! Robonomics parachain collation status RPC methods.
+
+use jsonrpc_core::Result;
+use jsonrpc_derive::rpc;
+use robonomics_primitives::{BlockNumber, Hash};
+use serde::{Deserialize, Serialize};
+use sp_core::Bytes;
+use std::sync::{Arc, RwLock};
+
+/// Collation context seen by the parachain node on the last relay parent.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ParachainStatus {
+    /// Relay chain block hash the last collation was built on.
+    pub relay_parent: Hash,
+    /// Relay chain block number the last collation was built on.
+    pub relay_parent_number: BlockNumber,
+    /// Relay chain state root of the relay parent.
+    pub relay_parent_storage_root: Hash,
+    /// Parachain head data the last collation was built on.
+    pub parent_head: Bytes,
+    /// Maximum legal size of a PoV block, in bytes.
+    pub max_pov_size: u32,
+    /// Count of pending downward (relay to parachain) messages.
+    pub pending_dmp: usize,
+    /// Count of pending horizontal (parachain to parachain) messages.
+    pub pending_hrmp: usize,
+}
+
+/// Parachain which chain spec is included into the node binary.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct KnownParachain {
+    /// Parachain id on the relay chain.
+    pub para_id: u32,
+    /// Chain spec name.
+    pub name: String,
+    /// Relay chain id given by the chain spec.
+    pub relay_chain: String,
+}
+
+/// Last parachain status shared between collator and RPC.
+pub type SharedParachainStatus = Arc<RwLock<Option<ParachainStatus>>>;
+
+/// Parachain status RPC methods.
+#[rpc]
+pub trait ParachainApi {
+    /// Returns last seen relay parent, validation data and pending messages counts.
+    ///
+    /// Status is empty until node collates the first block.
+    #[rpc(name = "robonomics_parachainStatus")]
+    fn parachain_status(&self) -> Result<Option<ParachainStatus>>;
+
+    /// Returns parachains which chain specs are included into the node binary.
+    #[rpc(name = "robonomics_knownParachains")]
+    fn known_parachains(&self) -> Result<Vec<KnownParachain>>;
+}
+
+/// Implements the [`ParachainApi`] RPC trait for collation monitoring.
+pub struct Parachain {
+    status: SharedParachainStatus,
+    known: Vec<KnownParachain>,
+}
+
+impl Parachain {
+    /// Create new `Parachain` with the given shared collation status and known parachains.
+    pub fn new(status: SharedParachainStatus, known: Vec<KnownParachain>) -> Self {
+        Self { status, known }
+    }
+}
+
+impl ParachainApi for Parachain {
+    fn parachain_status(&self) -> Result<Option<ParachainStatus>> {
+        Ok(self
+            .status
+            .read()
+            .expect("parachain status lock is not poisoned; qed")
+            .clone())
+    }
+
+    fn known_parachains(&self) -> Result<Vec<KnownParachain>> {
+        Ok(self.known.clone())
+    }
+}
@@ -0,0 +1,149 @@
+///////////////////////////////////////////////////////////////////////////////
+//
+//  Copyright 2018-2021 Robonomics Network <research@robonomics.network>
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+//
+///////////////////////////////////////////////////////////////////////////////
+//! Robonomics BABE randomness RPC methods.
+
+use jsonrpc_core::{Error as RpcError, ErrorCode, Result};
+use jsonrpc_derive::rpc;
+use robonomics_primitives::{Block, BlockNumber};
+use serde::{Deserialize, Serialize};
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_consensus_babe::{BabeApi, Epoch};
+use sp_core::H256;
+use sp_runtime::generic::BlockId;
+use std::sync::Arc;
+
+/// Randomness of BABE epoch.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EpochRandomness {
+    /// Epoch index.
+    pub epoch_index: u64,
+    /// First slot of the epoch.
+    pub start_slot: u64,
+    /// VRF outputs based randomness of the epoch.
+    pub randomness: H256,
+}
+
+impl From<Epoch> for EpochRandomness {
+    fn from(epoch: Epoch) -> Self {
+        Self {
+            epoch_index: epoch.epoch_index,
+            start_slot: *epoch.start_slot,
+            randomness: epoch.randomness.into(),
+        }
+    }
+}
+
+/// BABE randomness RPC methods.
+#[rpc]
+pub trait RandomnessApi {
+    /// Returns randomness of epoch with given index, current epoch by default.
+    ///
+    /// Randomness of the next epoch is known in advance, past epochs require
+    /// state of their blocks to be kept by database pruning.
+    #[rpc(name = "robonomics_babeRandomness")]
+    fn babe_randomness(&self, epoch: Option<u64>) -> Result<EpochRandomness>;
+}
+
+/// Implements the [`RandomnessApi`] RPC trait for verification of randomness based assignments.
+pub struct Randomness<C> {
+    client: Arc<C>,
+}
+
+impl<C> Randomness<C> {
+    /// Create new `Randomness` with the given reference to the client.
+    pub fn new(client: Arc<C>) -> Self {
+        Self { client }
+    }
+}
+
+impl<C> Randomness<C>
+where
+    C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + Send + Sync + 'static,
+    C::Api: BabeApi<Block>,
+{
+    /// Current epoch by the state of given block.
+    fn current_epoch(&self, number: BlockNumber) -> Result<Epoch> {
+        self.client
+            .runtime_api()
+            .current_epoch(&BlockId::Number(number))
+            .map_err(|e| RpcError {
+                code: ErrorCode::ServerError(1),
+                message: "Unable to query BABE epoch.".into(),
+                data: Some(format!("{:?}", e).into()),
+            })
+    }
+
+    /// Find past epoch by the first block which state has it as current one.
+    fn past_epoch(&self, index: u64, best: BlockNumber) -> Result<Epoch> {
+        let (mut low, mut high) = (0, best);
+        while low < high {
+            let middle = low + (high - low) / 2;
+            if self.current_epoch(middle)?.epoch_index < index {
+                low = middle + 1;
+            } else {
+                high = middle;
+            }
+        }
+
+        let epoch = self.current_epoch(low)?;
+        if epoch.epoch_index != index {
+            return Err(RpcError {
+                code: ErrorCode::ServerError(2),
+                message: "Epoch not found.".into(),
+                data: Some(format!("{:?}", index).into()),
+            });
+        }
+        Ok(epoch)
+    }
+}
+
+impl<C> RandomnessApi for Randomness<C>
+where
+    C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + Send + Sync + 'static,
+    C::Api: BabeApi<Block>,
+{
+    fn babe_randomness(&self, epoch: Option<u64>) -> Result<EpochRandomness> {
+        let best = self.client.info().best_number;
+        let current = self.current_epoch(best)?;
+        let index = epoch.unwrap_or(current.epoch_index);
+
+        let epoch = if index == current.epoch_index {
+            current
+        } else if index == current.epoch_index + 1 {
+            self.client
+                .runtime_api()
+                .next_epoch(&BlockId::Number(best))
+                .map_err(|e| RpcError {
+                    code: ErrorCode::ServerError(1),
+                    message: "Unable to query BABE epoch.".into(),
+                    data: Some(format!("{:?}", e).into()),
+                })?
+        } else if index < current.epoch_index {
+            self.past_epoch(index, best)?
+        } else {
+            return Err(RpcError {
+                code: ErrorCode::ServerError(2),
+                message: "Epoch randomness isn't known yet.".into(),
+                data: Some(format!("{:?}", index).into()),
+            });
+        };
+        Ok(epoch.into())
+    }
+}
@@ -0,0 +1,75 @@
+///////////////////////////////////////////////////////////////////////////////
+//
+//  Copyright 2018-2021 Robonomics Network <research@robonomics.network>
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+//
+///////////////////////////////////////////////////////////////////////////////
+//! Robonomics session keys RPC methods.
+
+use jsonrpc_core::Result;
+use jsonrpc_derive::rpc;
+use sc_rpc_api::DenyUnsafe;
+use serde::{Deserialize, Serialize};
+use sp_core::{
+    crypto::key_types::{BABE, GRANDPA},
+    ed25519, sr25519,
+};
+use sp_keystore::{SyncCryptoStore, SyncCryptoStorePtr};
+
+/// Public session keys present in the keystore.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SessionKeys {
+    /// BABE block authoring keys.
+    pub babe: Vec<sr25519::Public>,
+    /// GRANDPA finality keys.
+    pub grandpa: Vec<ed25519::Public>,
+}
+
+/// Session keys RPC methods.
+#[rpc]
+pub trait SessionKeysApi {
+    /// Returns public session keys loaded into the node keystore.
+    ///
+    /// Unsafe method: key presence is sensitive validator information.
+    #[rpc(name = "robonomics_sessionKeys")]
+    fn session_keys(&self) -> Result<SessionKeys>;
+}
+
+/// Implements the [`SessionKeysApi`] RPC trait for validator monitoring.
+pub struct SessionKeysHandler {
+    keystore: SyncCryptoStorePtr,
+    deny_unsafe: DenyUnsafe,
+}
+
+impl SessionKeysHandler {
+    /// Create new `SessionKeysHandler` with the given keystore.
+    pub fn new(keystore: SyncCryptoStorePtr, deny_unsafe: DenyUnsafe) -> Self {
+        Self {
+            keystore,
+            deny_unsafe,
+        }
+    }
+}
+
+impl SessionKeysApi for SessionKeysHandler {
+    fn session_keys(&self) -> Result<SessionKeys> {
+        self.deny_unsafe.check_if_safe()?;
+
+        Ok(SessionKeys {
+            babe: SyncCryptoStore::sr25519_public_keys(&*self.keystore, BABE),
+            grandpa: SyncCryptoStore::ed25519_public_keys(&*self.keystore, GRANDPA),
+        })
+    }
+}
@@ -0,0 +1,93 @@
+///////////////////////////////////////////////////////////////////////////////
+//
+//  Copyright 2018-2021 Robonomics Network <research@robonomics.network>
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+//
+///////////////////////////////////////////////////////////////////////////////
+//! Robonomics staking pallet RPC methods.
+
+use codec::{Decode, Encode};
+use jsonrpc_core::{Error as RpcError, ErrorCode, Result};
+use jsonrpc_derive::rpc;
+use robonomics_primitives::{AccountId, Balance, Block, Hash};
+use sc_client_api::StorageProvider;
+use sp_blockchain::HeaderBackend;
+use sp_core::{
+    hashing::{twox_128, twox_64},
+    storage::StorageKey,
+};
+use sp_rpc::number::NumberOrHex;
+use sp_runtime::generic::BlockId;
+use std::{marker::PhantomData, sync::Arc};
+
+/// Staking RPC methods.
+#[rpc]
+pub trait StakingApi<BlockHash> {
+    /// Returns staking bonus of given account.
+    #[rpc(name = "robonomicsStaking_bonusOf")]
+    fn bonus_of(&self, account: AccountId, at: Option<BlockHash>) -> Result<Option<NumberOrHex>>;
+}
+
+/// Implements the [`StakingApi`] RPC trait for interacting with staking pallet.
+pub struct Staking<C, B> {
+    client: Arc<C>,
+    _marker: PhantomData<B>,
+}
+
+impl<C, B> Staking<C, B> {
+    /// Create new `Staking` with the given reference to the client.
+    pub fn new(client: Arc<C>) -> Self {
+        Self {
+            client,
+            _marker: Default::default(),
+        }
+    }
+}
+
+/// Storage key of `Staking::Bonus` map entry.
+fn bonus_key(account: &AccountId) -> StorageKey {
+    let encoded = account.encode();
+    let mut key = twox_128(b"Staking").to_vec();
+    key.extend_from_slice(&twox_128(b"Bonus"));
+    key.extend_from_slice(&twox_64(&encoded));
+    key.extend_from_slice(&encoded);
+    StorageKey(key)
+}
+
+impl<C, B> StakingApi<Hash> for Staking<C, B>
+where
+    C: StorageProvider<Block, B> + HeaderBackend<Block> + Send + Sync + 'static,
+    B: sc_client_api::Backend<Block> + Send + Sync + 'static,
+{
+    fn bonus_of(&self, account: AccountId, at: Option<Hash>) -> Result<Option<NumberOrHex>> {
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+        let bonus = self
+            .client
+            .storage(&at, &bonus_key(&account))
+            .map_err(|e| RpcError {
+                code: ErrorCode::ServerError(1),
+                message: "Unable to read staking bonus.".into(),
+                data: Some(format!("{:?}", e).into()),
+            })?;
+
+        bonus
+            .map(|data| Balance::decode(&mut &data.0[..]).map(Into::into))
+            .transpose()
+            .map_err(|e| RpcError {
+                code: ErrorCode::ServerError(2),
+                message: "Unable to decode staking bonus.".into(),
+                data: Some(format!("{:?}", e).into()),
+            })
+    }
+}
@@ -0,0 +1,106 @@
+///////////////////////////////////////////////////////////////////////////////
+//
+//  Copyright 2018-2021 Robonomics Network <research@robonomics.network>
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+//
+///////////////////////////////////////////////////////////////////////////////
+//! Robonomics node status RPC methods.
+
+use futures::{channel::oneshot, FutureExt, TryFutureExt};
+use jsonrpc_core::{Error as RpcError, ErrorCode};
+use jsonrpc_derive::rpc;
+use robonomics_primitives::{Block, BlockNumber};
+use sc_rpc::system::Request;
+use serde::{Deserialize, Serialize};
+use sp_blockchain::HeaderBackend;
+use sp_utils::mpsc::TracingUnboundedSender;
+use std::sync::Arc;
+
+use crate::FutureResult;
+
+/// Node status in a single structure.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NodeStatus {
+    /// Is the node syncing.
+    pub is_syncing: bool,
+    /// Number of connected peers.
+    pub peers: usize,
+    /// Best block number.
+    pub best_number: BlockNumber,
+    /// Finalized block number.
+    pub finalized_number: BlockNumber,
+    /// Robonomics chain family.
+    pub family: String,
+}
+
+/// Node status RPC methods.
+#[rpc]
+pub trait StatusApi {
+    /// Returns sync, peers and chain status of the node.
+    #[rpc(name = "robonomics_nodeStatus")]
+    fn node_status(&self) -> FutureResult<NodeStatus>;
+}
+
+/// Dependencies of node status RPC.
+#[derive(Clone)]
+pub struct StatusDeps {
+    /// Channel to request network status of the node.
+    pub system_rpc_tx: TracingUnboundedSender<Request<Block>>,
+    /// Robonomics chain family name.
+    pub family: String,
+}
+
+/// Implements the [`StatusApi`] RPC trait for node health checks.
+pub struct Status<C> {
+    client: Arc<C>,
+    deps: StatusDeps,
+}
+
+impl<C> Status<C> {
+    /// Create new `Status` with the given client and network status channel.
+    pub fn new(client: Arc<C>, deps: StatusDeps) -> Self {
+        Self { client, deps }
+    }
+}
+
+impl<C> StatusApi for Status<C>
+where
+    C: HeaderBackend<Block> + Send + Sync + 'static,
+{
+    fn node_status(&self) -> FutureResult<NodeStatus> {
+        let info = self.client.info();
+        let family = self.deps.family.clone();
+        let (tx, rx) = oneshot::channel();
+        let _ = self.deps.system_rpc_tx.unbounded_send(Request::Health(tx));
+
+        let status = async move {
+            let health = rx.await.map_err(|e| RpcError {
+                code: ErrorCode::InternalError,
+                message: "Network service is unavailable.".into(),
+                data: Some(format!("{:?}", e).into()),
+            })?;
+
+            Ok(NodeStatus {
+                is_syncing: health.is_syncing,
+                peers: health.peers,
+                best_number: info.best_number,
+                finalized_number: info.finalized_number,
+                family,
+            })
+        };
+
+        Box::new(status.boxed().compat())
+    }
+}
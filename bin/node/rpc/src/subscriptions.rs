@@ -0,0 +1,107 @@
+///////////////////////////////////////////////////////////////////////////////
+//
+//  Copyright 2018-2021 Robonomics Network <research@robonomics.network>
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+//
+///////////////////////////////////////////////////////////////////////////////
+//! Per connection limit of robonomics RPC subscriptions.
+
+use jsonrpc_core::{Error as RpcError, ErrorCode};
+use jsonrpc_pubsub::{PubSubMetadata, Session};
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex, Weak},
+};
+
+/// Active subscriptions count of connection session keyed by its address.
+///
+/// Notice: Weak reference keeps session allocation, so its address can't be reused
+/// by another session while the entry exists, session itself is dropped as usual.
+type ActiveSubscriptions = HashMap<usize, (Weak<Session>, usize)>;
+
+/// Counter of active subscriptions of every connection.
+#[derive(Clone, Default)]
+pub struct SubscriptionsLimit {
+    max: Option<usize>,
+    active: Arc<Mutex<ActiveSubscriptions>>,
+}
+
+/// Active subscription slot, released on drop.
+pub struct SubscriptionSlot {
+    session: usize,
+    active: Arc<Mutex<ActiveSubscriptions>>,
+}
+
+impl SubscriptionsLimit {
+    /// Create new limit of subscriptions per connection, `None` means unlimited.
+    pub fn new(max: Option<usize>) -> Self {
+        Self {
+            max,
+            active: Default::default(),
+        }
+    }
+
+    /// Whether number of subscriptions per connection is limited.
+    pub fn is_limited(&self) -> bool {
+        self.max.is_some()
+    }
+
+    /// Take subscription slot of the connection, fails when limit is reached.
+    pub fn acquire<M: PubSubMetadata>(
+        &self,
+        metadata: &M,
+    ) -> Result<Option<SubscriptionSlot>, RpcError> {
+        let (max, session) = match (self.max, metadata.session()) {
+            (Some(max), Some(session)) => (max, session),
+            _ => return Ok(None),
+        };
+
+        let mut active = self
+            .active
+            .lock()
+            .expect("subscriptions lock poisoned; qed");
+        let key = Arc::as_ptr(&session) as usize;
+        if active.get(&key).map_or(0, |(_, count)| *count) >= max {
+            return Err(RpcError {
+                code: ErrorCode::ServerError(1),
+                message: format!("Subscriptions limit of {} per connection reached", max),
+                data: None,
+            });
+        }
+
+        active
+            .entry(key)
+            .or_insert_with(|| (Arc::downgrade(&session), 0))
+            .1 += 1;
+        Ok(Some(SubscriptionSlot {
+            session: key,
+            active: self.active.clone(),
+        }))
+    }
+}
+
+impl Drop for SubscriptionSlot {
+    fn drop(&mut self) {
+        let mut active = self
+            .active
+            .lock()
+            .expect("subscriptions lock poisoned; qed");
+        if let Some((_, count)) = active.get_mut(&self.session) {
+            *count -= 1;
+            if *count == 0 {
+                active.remove(&self.session);
+            }
+        }
+    }
+}
@@ -0,0 +1,97 @@
+///////////////////////////////////////////////////////////////////////////////
+//
+//  Copyright 2018-2021 Robonomics Network <research@robonomics.network>
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+//
+///////////////////////////////////////////////////////////////////////////////
+//! GRANDPA authority set rotation monitoring.
+
+use futures::StreamExt;
+use robonomics_primitives::{Block, BlockNumber, Hash};
+use sc_client_api::BlockchainEvents;
+use sc_finality_grandpa::SharedAuthoritySet;
+use sp_finality_grandpa::AuthorityId;
+use sp_runtime::traits::Header;
+use std::{collections::HashSet, sync::Arc};
+use substrate_prometheus_endpoint::{register, Gauge, PrometheusError, Registry, U64};
+
+/// Current authorities of GRANDPA authority set.
+fn authorities(authority_set: &SharedAuthoritySet<Hash, BlockNumber>) -> HashSet<AuthorityId> {
+    authority_set
+        .current_authorities()
+        .iter()
+        .map(|(id, _)| id.clone())
+        .collect()
+}
+
+/// Register authority set id gauge in given registry.
+fn register_set_id(registry: &Registry) -> Result<Gauge<U64>, PrometheusError> {
+    register(
+        Gauge::new(
+            "robonomics_grandpa_authority_set_id",
+            "Id of the current GRANDPA authority set",
+        )?,
+        registry,
+    )
+}
+
+/// Report GRANDPA authority set changes observed on block finalization.
+///
+/// Each change is logged with the numbers of incoming and outgoing authorities.
+pub async fn monitor<C>(
+    client: Arc<C>,
+    authority_set: SharedAuthoritySet<Hash, BlockNumber>,
+    registry: Option<Registry>,
+) where
+    C: BlockchainEvents<Block>,
+{
+    let gauge = match registry.as_ref().map(register_set_id).transpose() {
+        Ok(gauge) => gauge,
+        Err(e) => {
+            log::warn!(target: "robonomics::service", "Unable to register metrics: {}", e);
+            None
+        }
+    };
+
+    let mut set_id = authority_set.set_id();
+    let mut current = authorities(&authority_set);
+    if let Some(gauge) = &gauge {
+        gauge.set(set_id);
+    }
+
+    let mut finality = client.finality_notification_stream();
+    while let Some(notification) = finality.next().await {
+        if authority_set.set_id() == set_id {
+            continue;
+        }
+
+        let next = authorities(&authority_set);
+        log::warn!(
+            target: "robonomics::service",
+            "GRANDPA authority set changed: set_id={} previous_set_id={} block={} authorities={} incoming={} outgoing={}",
+            authority_set.set_id(),
+            set_id,
+            notification.header.number(),
+            next.len(),
+            next.difference(&current).count(),
+            current.difference(&next).count(),
+        );
+
+        set_id = authority_set.set_id();
+        current = next;
+        if let Some(gauge) = &gauge {
+            gauge.set(set_id);
+        }
+    }
+}
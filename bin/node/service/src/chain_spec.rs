@@ -17,6 +17,7 @@
 ///////////////////////////////////////////////////////////////////////////////
 //! Chain specification and utils.
 
+use codec::Encode;
 use local_runtime::{
     wasm_binary_unwrap, BabeConfig, BalancesConfig, GenesisConfig, GrandpaConfig, StakingConfig,
     SudoConfig, SystemConfig,
@@ -39,6 +40,67 @@ pub enum RobonomicsFamily {
     Parachain,
 }
 
+impl RobonomicsFamily {
+    /// Default p2p, RPC, WebSocket and Prometheus ports of the chain family.
+    ///
+    /// Parachain ports are shifted to leave a room for embedded relay chain node.
+    pub fn default_ports(&self) -> (u16, u16, u16, u16) {
+        match self {
+            RobonomicsFamily::Development => (30333, 9933, 9944, 9615),
+            #[cfg(feature = "parachain")]
+            RobonomicsFamily::Parachain => (30335, 9935, 9946, 9617),
+        }
+    }
+
+    /// Default transaction pool limits of the chain family: ready transactions count
+    /// and total size in kilobytes, future queue is limited by tenth part of it.
+    pub fn default_pool_limits(&self) -> (usize, usize) {
+        match self {
+            RobonomicsFamily::Development => (8192, 20480),
+            #[cfg(feature = "parachain")]
+            RobonomicsFamily::Parachain => (8192, 20480),
+        }
+    }
+
+    /// Default state pruning in blocks (`None` for archive) and state cache size in bytes.
+    pub fn default_storage(&self) -> (Option<u32>, usize) {
+        match self {
+            RobonomicsFamily::Development => (Some(256), 64 * 1024 * 1024),
+            #[cfg(feature = "parachain")]
+            RobonomicsFamily::Parachain => (Some(256), 64 * 1024 * 1024),
+        }
+    }
+
+    /// Default inbound and outbound full node peers of the chain family, lower than substrate
+    /// ones to keep collators on small instances responsive.
+    pub fn default_peers(&self) -> (u32, u32) {
+        match self {
+            RobonomicsFamily::Development => (16, 8),
+            #[cfg(feature = "parachain")]
+            RobonomicsFamily::Parachain => (12, 6),
+        }
+    }
+
+    /// Runtime `spec_name` expected by the chain family.
+    pub fn spec_name(&self) -> &'static str {
+        match self {
+            RobonomicsFamily::Development => "robonomics-local",
+            #[cfg(feature = "parachain")]
+            RobonomicsFamily::Parachain => "robonomics-alpha",
+        }
+    }
+}
+
+impl std::fmt::Display for RobonomicsFamily {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            RobonomicsFamily::Development => write!(f, "development"),
+            #[cfg(feature = "parachain")]
+            RobonomicsFamily::Parachain => write!(f, "parachain"),
+        }
+    }
+}
+
 /// Robonomics family chains idetify.
 pub trait RobonomicsChain {
     fn family(&self) -> RobonomicsFamily;
@@ -64,6 +126,29 @@ impl RobonomicsChain for Box<dyn sc_chain_spec::ChainSpec> {
 
 type AccountPublic = <Signature as Verify>::Signer;
 
+/// XRT token decimals, the same as `XRT` runtime currency constant.
+pub const XRT_DECIMALS: u32 = 9;
+
+/// XRT token symbol.
+pub const XRT_SYMBOL: &str = "XRT";
+
+/// Robonomics address format, the same as `SS58Prefix` runtime parameter.
+pub const ROBONOMICS_SS58_FORMAT: u16 = 32;
+
+/// Chain spec properties used by wallets to display balances and addresses.
+pub fn token_properties(decimals: u32, symbol: &str, ss58_format: u16) -> sc_service::Properties {
+    let mut properties = sc_service::Properties::new();
+    properties.insert("tokenDecimals".into(), decimals.into());
+    properties.insert("tokenSymbol".into(), symbol.into());
+    properties.insert("ss58Format".into(), ss58_format.into());
+    properties
+}
+
+/// XRT token chain spec properties.
+pub fn xrt_properties() -> sc_service::Properties {
+    token_properties(XRT_DECIMALS, XRT_SYMBOL, ROBONOMICS_SS58_FORMAT)
+}
+
 /// Node `ChainSpec` extensions.
 ///
 /// Additional parameters for some Substrate core modules,
@@ -75,6 +160,85 @@ pub struct Extensions {
     pub fork_blocks: sc_client_api::ForkBlocks<Block>,
     /// Known bad block hashes.
     pub bad_blocks: sc_client_api::BadBlocks<Block>,
+    /// GRANDPA gossip duration in milliseconds.
+    pub grandpa_gossip_duration_ms: Option<u64>,
+    /// GRANDPA justification generation period in blocks.
+    pub grandpa_justification_period: Option<u32>,
+    /// BABE epoch duration in slots, overrides runtime default.
+    pub babe_epoch_duration: Option<u64>,
+}
+
+impl Extensions {
+    /// Try to get the extension from the given `ChainSpec`.
+    pub fn try_get(chain_spec: &Box<dyn sc_service::ChainSpec>) -> Option<&Self> {
+        sc_chain_spec::get_extension(chain_spec.extensions())
+    }
+}
+
+/// Pin BABE epoch duration into genesis storage when it's set by chain spec extensions.
+/// Notice: Storage is pinned once by `build-spec`, loaded specs are used as is.
+pub fn pin_babe_epoch_duration(
+    chain_spec: &mut Box<dyn sc_service::ChainSpec>,
+) -> Result<(), String> {
+    let duration = match Extensions::try_get(chain_spec).and_then(|e| e.babe_epoch_duration) {
+        Some(duration) => duration,
+        None => return Ok(()),
+    };
+
+    let mut storage = chain_spec.build_storage()?;
+    storage.top.insert(
+        local_runtime::EpochDuration::key().to_vec(),
+        duration.encode(),
+    );
+    chain_spec.set_storage(storage);
+    Ok(())
+}
+
+/// Replace network protocol id of the chain spec, it isolates network from the chain spec peers.
+pub fn override_protocol_id(
+    chain_spec: &mut Box<dyn sc_service::ChainSpec>,
+    protocol_id: &str,
+) -> Result<(), String> {
+    let mut json: serde_json::Value =
+        serde_json::from_str(&chain_spec.as_json(false)?).map_err(|e| e.to_string())?;
+    json["protocolId"] = protocol_id.into();
+    let json = serde_json::to_vec(&json).map_err(|e| e.to_string())?;
+
+    *chain_spec = match chain_spec.family() {
+        RobonomicsFamily::Development => Box::new(ChainSpec::from_json_bytes(json)?),
+        #[cfg(feature = "parachain")]
+        RobonomicsFamily::Parachain => Box::new(
+            crate::parachain::chain_spec::ChainSpec::from_json_bytes(json)?,
+        ),
+    };
+    Ok(())
+}
+
+/// Build genesis block of the chain spec, the same as node imports on the first start.
+pub fn genesis_block(chain_spec: &Box<dyn sc_service::ChainSpec>) -> Result<Block, String> {
+    use sp_runtime::traits::{Block as BlockT, Hash as HashT, Header as HeaderT, Zero};
+    type Hashing = <<Block as BlockT>::Header as HeaderT>::Hashing;
+
+    let storage = chain_spec.build_storage()?;
+    let child_roots = storage.children_default.values().map(|child| {
+        let root = Hashing::trie_root(child.data.clone().into_iter().collect());
+        (
+            child.child_info.prefixed_storage_key().into_inner(),
+            root.encode(),
+        )
+    });
+    let state_root =
+        Hashing::trie_root(storage.top.clone().into_iter().chain(child_roots).collect());
+    let extrinsics_root = Hashing::trie_root(Vec::new());
+
+    let header = <Block as BlockT>::Header::new(
+        Zero::zero(),
+        extrinsics_root,
+        state_root,
+        Default::default(),
+        Default::default(),
+    );
+    Ok(Block::new(header, Vec::new()))
 }
 
 /// Specialized `ChainSpec`.
@@ -95,6 +259,16 @@ where
     AccountPublic::from(get_from_seed::<TPublic>(seed)).into_account()
 }
 
+/// Helper function to generate an account ID from secret URI, e.g. `//Alice`
+pub fn get_account_id_from_suri<TPublic: Public>(suri: &str) -> Result<AccountId, String>
+where
+    AccountPublic: From<<TPublic::Pair as Pair>::Public>,
+{
+    let pair = TPublic::Pair::from_string(suri, None)
+        .map_err(|e| format!("Invalid secret URI {}: {:?}", suri, e))?;
+    Ok(AccountPublic::from(pair.public()).into_account())
+}
+
 /// Helper function to generate stash, controller and session key from seed
 fn get_authority_keys_from_seed(seed: &str) -> (AccountId, BabeId, GrandpaId) {
     (
@@ -190,7 +364,7 @@ pub fn development_config() -> ChainSpec {
         vec![],
         None,
         None,
-        None,
+        Some(xrt_properties()),
         Default::default(),
     )
 }
@@ -36,6 +36,17 @@ pub struct Cli {
     #[cfg(feature = "full")]
     pub run: RunCmd,
 
+    /// Use `--base-path` as is, without chain family subdirectory.
+    /// Notice: Chains of different families given the same base path share the directory.
+    #[structopt(long, global = true)]
+    #[cfg(feature = "full")]
+    pub no_family_subdir: bool,
+
+    /// Enable debug logs of all robonomics targets, the same as
+    /// `-l robonomics::service=debug,robonomics::collator=debug,...`.
+    #[structopt(long, global = true)]
+    pub robonomics_debug: bool,
+
     /// Polkadot relaychain arguments.
     #[structopt(raw = true)]
     #[cfg(feature = "parachain")]
@@ -47,7 +58,181 @@ pub struct RunCmd {
     #[structopt(flatten)]
     pub base: sc_cli::RunCmd,
 
-    /// Id of the parachain this collator collates for.
+    /// Development chain block sealing mode: `manual` or `instant`. [default: BABE]
+    /// Notice: `manual` mode enables `engine_createBlock` and `engine_finalizeBlock` RPC.
+    #[structopt(long)]
+    #[cfg(feature = "full")]
+    pub sealing: Option<crate::service::Sealing>,
+
+    /// Proportion of the BABE slot dedicated to block proposing, must be in (0.0, 1.0) range.
+    #[structopt(long, default_value = "0.5", parse(try_from_str = parse_slot_proportion))]
+    #[cfg(feature = "full")]
+    pub babe_slot_proportion: f32,
+
+    /// Run GRANDPA observer instead of the full voter, not allowed for validators.
+    #[structopt(long, conflicts_with = "validator")]
+    #[cfg(feature = "full")]
+    pub grandpa_observer: bool,
+
+    /// GRANDPA votes only on blocks that are at least given number of blocks behind the best.
+    /// Notice: Bigger value protects from finalizing of shallow blocks, but delays finality.
+    #[structopt(long, value_name = "BLOCKS", default_value = "2")]
+    #[cfg(feature = "full")]
+    pub grandpa_before_best_by: robonomics_primitives::BlockNumber,
+
+    /// Disable GRANDPA voting rule that limits votes by three quarters of unfinalized chain.
+    #[structopt(long)]
+    #[cfg(feature = "full")]
+    pub no_grandpa_three_quarters_rule: bool,
+
+    /// Serve RPC only: don't run block authoring and GRANDPA voter regardless of node role.
+    /// Notice: Finality is followed by justifications received from the network.
+    #[structopt(long)]
+    #[cfg(feature = "full")]
+    pub rpc_only: bool,
+
+    /// Maximum size of robonomics pubsub notification in bytes.
+    #[structopt(long, value_name = "BYTES", default_value = "1048576")]
+    #[cfg(feature = "full")]
+    pub pubsub_max_notification_size: u64,
+
+    /// Reserved peers of robonomics pubsub protocol, e.g.
+    /// `/ip4/198.51.100.19/tcp/30333/p2p/QmSk5HQbn6LhUwDiNMseVUjuRYhEtYj4aUZ6WfWoGURpdV`.
+    #[structopt(long, value_name = "ADDR")]
+    #[cfg(feature = "full")]
+    pub pubsub_reserved_nodes: Vec<sc_network::config::MultiaddrWithPeerId>,
+
+    /// Use `keystore-<INDEX>` keystore directory, e.g. to keep keys of several validators
+    /// sharing the base path apart.
+    /// Notice: Validators still need distinct database, network key and ports.
+    #[structopt(long, value_name = "INDEX")]
+    #[cfg(feature = "full")]
+    pub validator_index: Option<u32>,
+
+    /// Disable slowing down of block authoring when finality lags behind.
+    /// Notice: Validators keep growing unfinalized chain in case of finality stall.
+    #[structopt(long)]
+    #[cfg(feature = "full")]
+    pub no_backoff_authoring: bool,
+
+    /// Maximum interval in blocks between authored blocks when finality lags behind.
+    /// Notice: Bigger value holds the chain on finality stall, but slows down its recovery.
+    #[structopt(long, value_name = "BLOCKS", conflicts_with = "no-backoff-authoring")]
+    #[cfg(feature = "full")]
+    pub backoff_max_lag: Option<robonomics_primitives::BlockNumber>,
+
+    /// Stop block authoring while finality lags behind the best block more than given blocks.
+    /// Notice: Authoring is resumed once finality catches up or half of BABE epoch is empty.
+    #[structopt(long, value_name = "BLOCKS")]
+    #[cfg(feature = "full")]
+    pub pause_authoring_on_lag: Option<robonomics_primitives::BlockNumber>,
+
+    /// NTP server to correct block timestamps by, e.g. `pool.ntp.org`.
+    /// Notice: Block authoring is skipped while clock drift exceeds `--max-clock-drift`.
+    #[structopt(long, value_name = "HOST")]
+    #[cfg(feature = "full")]
+    pub ntp_server: Option<String>,
+
+    /// Maximum local clock drift from NTP time in milliseconds that blocks are authored with.
+    #[structopt(long, value_name = "MS", default_value = "500")]
+    #[cfg(feature = "full")]
+    pub max_clock_drift: u64,
+
+    /// Maximum size of authored blocks in bytes, soft-caps blocks below the runtime limit.
+    /// Notice: Applies to block proposal, imported blocks are limited by runtime as usual.
+    #[structopt(long, value_name = "BYTES")]
+    #[cfg(feature = "full")]
+    pub max_block_bytes: Option<usize>,
+
+    /// Author BABE blocks only when transaction pool has ready transactions.
+    /// Notice: Empty slots are skipped by authoring backoff, `--max-empty-interval` is required.
+    #[structopt(long, requires = "max-empty-interval")]
+    #[cfg(feature = "full")]
+    pub author_on_demand: bool,
+
+    /// Maximum interval in seconds between heartbeat blocks when authoring on demand.
+    /// Notice: Should be less than half of BABE epoch duration.
+    #[structopt(long, value_name = "SECONDS", requires = "author-on-demand")]
+    #[cfg(feature = "full")]
+    pub max_empty_interval: Option<u64>,
+
+    /// Override network protocol id of the chain spec, e.g. to isolate private test network.
+    /// Notice: Nodes with different protocol ids never peer with each other.
+    #[structopt(long, value_name = "ID", parse(try_from_str = parse_protocol_id))]
+    #[cfg(feature = "full")]
+    pub protocol_id: Option<String>,
+
+    /// Remote signer for BABE and GRANDPA keys, e.g. `unix:///run/hsm-signer.sock`.
+    /// Notice: Keys are managed on the signer side, keystore RPC methods fail for them.
+    #[structopt(long, value_name = "URL")]
+    #[cfg(feature = "full")]
+    pub remote_signer_url: Option<String>,
+
+    /// Execute blocks by given wasm runtime instead of on-chain one, e.g. to replay incidents.
+    /// Notice: Pinned runtime replaces on-chain code of the same spec version only, so node
+    /// refuses to start when chain has blocks of other spec versions and stops once chain is
+    /// upgraded. Results are NOT canonical, never use it on validators or public RPC nodes.
+    #[structopt(long, value_name = "WASM_FILE", parse(from_os_str))]
+    #[cfg(feature = "full")]
+    pub pin_runtime: Option<std::path::PathBuf>,
+
+    /// Save ready transactions into given file on shutdown and restore them on start.
+    #[structopt(long, value_name = "PATH", parse(from_os_str))]
+    #[cfg(feature = "full")]
+    pub tx_pool_dump: Option<std::path::PathBuf>,
+
+    /// Unsafe RPC method allowed on external RPC interfaces, could be repeated.
+    /// Notice: Other unsafe methods are denied as usual.
+    #[structopt(long = "rpc-allow", value_name = "METHOD")]
+    #[cfg(feature = "full")]
+    pub rpc_allow: Vec<String>,
+
+    /// Maximum number of robonomics RPC subscriptions per connection. [default: unlimited]
+    /// Notice: Excess subscription requests are rejected.
+    #[structopt(long, value_name = "COUNT")]
+    #[cfg(feature = "full")]
+    pub rpc_max_subscriptions_per_connection: Option<usize>,
+
+    /// Additional telemetry endpoint with verbosity level, e.g. `'wss://foo/bar 9'`.
+    /// Notice: Endpoints are added to chain spec or `--telemetry-url` ones, could be repeated.
+    #[structopt(
+        long,
+        value_name = "URL VERBOSITY",
+        parse(try_from_str = parse_telemetry_endpoint)
+    )]
+    #[cfg(feature = "full")]
+    pub telemetry_endpoint: Vec<(String, u8)>,
+
+    /// IPFS node API URL to pin content of finalized datalog records.
+    /// Notice: Offchain workers should be enabled.
+    #[structopt(long, value_name = "URL")]
+    #[cfg(feature = "full")]
+    pub ipfs_pin_url: Option<String>,
+
+    /// NATS URL to publish finalized blocks as JSON into, e.g. `nats://127.0.0.1:4222`.
+    /// Notice: Blocks are buffered while broker is unreachable, the oldest are dropped.
+    #[structopt(long, value_name = "NATS_URL")]
+    #[cfg(feature = "full")]
+    pub publish_finalized: Option<String>,
+
+    /// NATS subject of published finalized blocks. [default: robonomics.finalized]
+    #[structopt(long, value_name = "SUBJECT", requires = "publish-finalized")]
+    #[cfg(feature = "full")]
+    pub subject: Option<String>,
+
+    /// Unix socket path to stream imported block headers into as newline delimited JSON.
+    /// Notice: Several readers could be connected, slow readers are disconnected.
+    #[structopt(long, value_name = "PATH", parse(from_os_str))]
+    #[cfg(feature = "full")]
+    pub import_notify_socket: Option<std::path::PathBuf>,
+
+    /// Run offchain workers and IPFS pinning only when keystore holds the account key.
+    /// Notice: Account key should be inserted with `acco` key type.
+    #[structopt(long, value_name = "SS58")]
+    #[cfg(feature = "full")]
+    pub offchain_worker_account: Option<robonomics_primitives::AccountId>,
+
+    /// Id of the parachain this collator collates for. [default: chain spec value]
     #[structopt(long)]
     #[cfg(feature = "parachain")]
     pub parachain_id: Option<u32>,
@@ -57,6 +242,138 @@ pub struct RunCmd {
     #[structopt(long)]
     #[cfg(feature = "parachain")]
     pub collator_eth_account: Option<sp_core::H160>,
+
+    /// Read relay chain arguments from TOML or YAML file, e.g. `chain = "kusama"`.
+    /// Notice: Arguments given after `--` are appended to file ones.
+    #[structopt(long, value_name = "PATH", parse(from_os_str))]
+    #[cfg(feature = "parachain")]
+    pub relaychain_config: Option<std::path::PathBuf>,
+
+    /// Parsed arguments to tell explicitly given ones from defaults, see `capture_matches`.
+    #[structopt(skip)]
+    #[cfg(feature = "full")]
+    matches: structopt::clap::ArgMatches<'static>,
+}
+
+#[cfg(feature = "full")]
+fn parse_slot_proportion(s: &str) -> Result<f32, String> {
+    let proportion = s.parse::<f32>().map_err(|e| e.to_string())?;
+    if proportion > 0.0 && proportion < 1.0 {
+        Ok(proportion)
+    } else {
+        Err("Slot proportion should be in (0.0, 1.0) range".into())
+    }
+}
+
+#[cfg(feature = "full")]
+fn parse_telemetry_endpoint(s: &str) -> Result<(String, u8), String> {
+    let pos = s
+        .rfind(' ')
+        .ok_or("Telemetry endpoint should be given as `URL VERBOSITY`")?;
+    let (url, verbosity) = (&s[..pos], &s[pos + 1..]);
+    let verbosity = verbosity
+        .parse()
+        .map_err(|e| format!("Invalid telemetry verbosity: {}", e))?;
+    Ok((url.into(), verbosity))
+}
+
+#[cfg(feature = "full")]
+fn parse_protocol_id(s: &str) -> Result<String, String> {
+    if s.is_empty() || s.contains(|c: char| c.is_whitespace() || c == '/') {
+        return Err("Protocol id should be non-empty and contain no spaces or slashes".into());
+    }
+    Ok(s.into())
+}
+
+#[cfg(feature = "full")]
+impl RunCmd {
+    /// Robonomics full node options from command line arguments.
+    pub fn full_node_options(&self) -> crate::service::FullNodeOptions {
+        crate::service::FullNodeOptions {
+            sealing: self.sealing,
+            babe_slot_proportion: self.babe_slot_proportion,
+            grandpa_observer: self.grandpa_observer,
+            grandpa_before_best_by: self.grandpa_before_best_by,
+            grandpa_three_quarters_rule: !self.no_grandpa_three_quarters_rule,
+            tx_pool_dump: self.tx_pool_dump.clone(),
+            rpc_allow: self.rpc_allow.clone(),
+            rpc_max_subscriptions_per_connection: self.rpc_max_subscriptions_per_connection,
+            pool_limit: self.pool_limit(),
+            pool_kbytes: self.pool_kbytes(),
+            ipfs_pin_url: self.ipfs_pin_url.clone(),
+            offchain_worker_account: self.offchain_worker_account.clone(),
+            backoff_authoring: !self.no_backoff_authoring,
+            backoff_max_lag: self.backoff_max_lag,
+            pause_authoring_on_lag: self.pause_authoring_on_lag,
+            ntp_server: self.ntp_server.clone(),
+            max_clock_drift: std::time::Duration::from_millis(self.max_clock_drift),
+            max_block_bytes: self.max_block_bytes,
+            author_on_demand: self.author_on_demand,
+            max_empty_interval: self.max_empty_interval.map(std::time::Duration::from_secs),
+            rpc_only: self.rpc_only,
+            pubsub_max_notification_size: self.pubsub_max_notification_size,
+            pubsub_reserved_nodes: self.pubsub_reserved_nodes.clone(),
+            keystore_suffix: self.validator_index.map(|index| index.to_string()),
+            pinned_runtime: self.pin_runtime.clone(),
+            publish_finalized: self.publish_finalized.clone(),
+            import_notify_socket: self.import_notify_socket.clone(),
+            publish_subject: self
+                .subject
+                .clone()
+                .unwrap_or_else(|| crate::service::FullNodeOptions::default().publish_subject),
+            ..Default::default()
+        }
+    }
+
+    /// Keep parsed arguments of the node to tell explicitly given substrate options.
+    ///
+    /// Notice: Without captured arguments all substrate options are treated as defaults.
+    pub fn capture_matches(&mut self, matches: structopt::clap::ArgMatches<'static>) {
+        self.matches = matches;
+    }
+
+    /// Is argument of given name passed in any form, relay chain arguments aren't counted.
+    fn is_given(&self, name: &str) -> bool {
+        self.matches.occurrences_of(name) > 0
+    }
+
+    /// Transaction pool ready transactions limit when `--pool-limit` is given.
+    pub fn pool_limit(&self) -> Option<usize> {
+        if self.is_given("pool-limit") {
+            Some(self.base.pool_config.pool_limit)
+        } else {
+            None
+        }
+    }
+
+    /// Is state pruning given by `--pruning`.
+    pub fn is_pruning_given(&self) -> bool {
+        self.base.import_params.pruning_params.pruning.is_some()
+    }
+
+    /// Is state cache size given by `--state-cache-size`.
+    pub fn is_state_cache_size_given(&self) -> bool {
+        self.is_given("state-cache-size")
+    }
+
+    /// Is inbound peers count given by `--in-peers`.
+    pub fn is_in_peers_given(&self) -> bool {
+        self.is_given("in-peers")
+    }
+
+    /// Is outbound peers count given by `--out-peers`.
+    pub fn is_out_peers_given(&self) -> bool {
+        self.is_given("out-peers")
+    }
+
+    /// Transaction pool size limit in kilobytes when `--pool-kbytes` is given.
+    pub fn pool_kbytes(&self) -> Option<usize> {
+        if self.is_given("pool-kbytes") {
+            Some(self.base.pool_config.pool_kbytes)
+        } else {
+            None
+        }
+    }
 }
 
 impl std::ops::Deref for RunCmd {
@@ -90,14 +407,91 @@ pub enum Subcommand {
     #[cfg(feature = "full")]
     PurgeChain(sc_cli::PurgeChainCmd),
 
+    /// Validate blocks.
+    ///
+    /// Use `--execution-import-block` (or `--execution` for all operations)
+    /// with `Native`, `Wasm`, `Both` or `NativeElseWasm` value to choose runtime
+    /// execution strategy, e.g. `Both` reports native and wasm results divergence.
+    #[cfg(feature = "full")]
+    CheckBlock(sc_cli::CheckBlockCmd),
+
+    /// Export blocks.
+    #[cfg(feature = "full")]
+    ExportBlocks(sc_cli::ExportBlocksCmd),
+
+    /// Export the state of a given block into a chain spec.
+    #[cfg(feature = "full")]
+    ExportState(sc_cli::ExportStateCmd),
+
+    /// Import blocks.
+    ///
+    /// Block execution strategy is selected by `--execution-import-block`,
+    /// other runtime calls are driven by `--execution-other`.
+    #[cfg(feature = "full")]
+    ImportBlocks(sc_cli::ImportBlocksCmd),
+
+    /// Revert the chain to a previous state.
+    #[cfg(feature = "full")]
+    Revert(sc_cli::RevertCmd),
+
+    /// Export staking bonus of all accounts as CSV.
+    #[cfg(feature = "full")]
+    ExportBonus(ExportBonusCmd),
+
+    /// Re-execute imported blocks and check their state roots.
+    #[cfg(feature = "full")]
+    VerifyBlocks(VerifyBlocksCmd),
+
+    /// Remove blocks of forks abandoned by finality, keeping the canonical chain.
+    #[cfg(feature = "full")]
+    PurgeForks(PurgeForksCmd),
+
+    /// Insert BABE and GRANDPA keys derived from seed into the keystore.
+    #[cfg(feature = "full")]
+    GenerateSessionKeys(GenerateSessionKeysCmd),
+
+    /// Generate new ed25519 network key in place of the node one and print new PeerId.
+    #[cfg(feature = "full")]
+    RotateNodeKey(RotateNodeKeyCmd),
+
+    /// Decode hex encoded extrinsic of development runtime and print it as JSON.
+    #[cfg(feature = "full")]
+    DecodeExtrinsic(DecodeExtrinsicCmd),
+
+    /// Print SS58 account ID derived from seed, the same way as genesis accounts are.
+    #[cfg(feature = "full")]
+    DeriveAccount(DeriveAccountCmd),
+
+    /// Print genesis state root and block hash of the chain.
+    #[cfg(feature = "full")]
+    GenesisHash(GenesisHashCmd),
+
+    /// Print metrics of running node as JSON.
+    MetricsSnapshot(MetricsSnapshotCmd),
+
+    /// Wait until running node is synced and finalizes blocks, fail on timeout.
+    WaitSynced(WaitSyncedCmd),
+
     /// Robonomics Framework I/O operations.
     #[cfg(feature = "robonomics-cli")]
     Io(robonomics_cli::IoCmd),
 
+    /// Flood the chain with datalog records and report inclusion rate and latency.
+    #[cfg(feature = "robonomics-cli")]
+    Soak(robonomics_cli::SoakCmd),
+
+    /// Submit runtime call with root origin signed by the sudo key holder.
+    #[cfg(feature = "robonomics-cli")]
+    Sudo(robonomics_cli::SudoCmd),
+
     /// Benchmarking runtime pallets.
     #[cfg(feature = "frame-benchmarking-cli")]
     Benchmark(frame_benchmarking_cli::BenchmarkCmd),
 
+    /// Benchmarking robonomics pallet with default parameters.
+    #[cfg(feature = "frame-benchmarking-cli")]
+    BenchmarkPallet(BenchmarkPalletCmd),
+
     /// Export the genesis state of the parachain.
     #[structopt(name = "export-genesis-state")]
     #[cfg(feature = "parachain")]
@@ -107,4 +501,969 @@ pub enum Subcommand {
     #[structopt(name = "export-genesis-wasm")]
     #[cfg(feature = "parachain")]
     ExportGenesisWasm(super::parachain::cli::ExportGenesisWasmCommand),
+
+    /// Build a raw chain specification of a new parachain.
+    #[structopt(name = "build-parachain-spec")]
+    #[cfg(feature = "parachain")]
+    BuildParachainSpec(super::parachain::cli::BuildParachainSpecCommand),
+
+    /// Check consistency of the parachain chain specification file.
+    #[structopt(name = "check-spec")]
+    #[cfg(feature = "parachain")]
+    CheckSpec(super::parachain::cli::CheckSpecCommand),
+
+    /// Compare genesis balances, sudo key, staking bonus and id of two parachain specs.
+    #[structopt(name = "diff-spec")]
+    #[cfg(feature = "parachain")]
+    DiffSpec(super::parachain::cli::DiffSpecCommand),
+}
+
+/// Export staking bonus of all accounts as `account,bonus` CSV into stdout.
+#[cfg(feature = "full")]
+#[derive(Debug, StructOpt)]
+pub struct ExportBonusCmd {
+    /// Block hash or number to read bonus at. [default: best block]
+    #[structopt(long, value_name = "HASH or NUMBER")]
+    pub at: Option<sc_cli::BlockNumberOrHash>,
+
+    #[allow(missing_docs)]
+    #[structopt(flatten)]
+    pub shared_params: sc_cli::SharedParams,
+
+    #[allow(missing_docs)]
+    #[structopt(flatten)]
+    pub pruning_params: sc_cli::PruningParams,
+
+    #[allow(missing_docs)]
+    #[structopt(flatten)]
+    pub database_params: sc_cli::DatabaseParams,
+}
+
+#[cfg(feature = "full")]
+impl ExportBonusCmd {
+    /// Iterate `Staking::Bonus` storage map and write it into stdout.
+    pub async fn run<BA, C>(&self, client: std::sync::Arc<C>) -> sc_cli::Result<()>
+    where
+        C: sc_client_api::StorageProvider<robonomics_primitives::Block, BA>
+            + sp_blockchain::HeaderBackend<robonomics_primitives::Block>,
+        BA: sc_client_api::Backend<robonomics_primitives::Block>,
+    {
+        use codec::Decode;
+        use robonomics_primitives::{AccountId, Balance};
+        use sp_core::{crypto::Ss58Codec, hashing::twox_128, storage::StorageKey};
+        use std::io::Write;
+
+        let at = match &self.at {
+            Some(at) => at.parse()?,
+            None => sp_runtime::generic::BlockId::Hash(client.info().best_hash),
+        };
+        let prefix = [twox_128(b"Staking"), twox_128(b"Bonus")].concat();
+        let pairs = client.storage_pairs(&at, &StorageKey(prefix.clone()))?;
+
+        let mut stdout = std::io::stdout();
+        writeln!(stdout, "account,bonus")?;
+        for (key, value) in pairs {
+            // Twox64Concat key: 8 bytes of hash followed by encoded account.
+            let account = AccountId::decode(&mut &key.0[prefix.len() + 8..])
+                .map_err(|e| format!("Invalid bonus key: {}", e))?;
+            let bonus = Balance::decode(&mut &value.0[..])
+                .map_err(|e| format!("Invalid bonus value: {}", e))?;
+            writeln!(stdout, "{},{}", account.to_ss58check(), bonus)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(feature = "full")]
+impl sc_cli::CliConfiguration for ExportBonusCmd {
+    fn shared_params(&self) -> &sc_cli::SharedParams {
+        &self.shared_params
+    }
+
+    fn pruning_params(&self) -> Option<&sc_cli::PruningParams> {
+        Some(&self.pruning_params)
+    }
+
+    fn database_params(&self) -> Option<&sc_cli::DatabaseParams> {
+        Some(&self.database_params)
+    }
+}
+
+/// Re-execute range of imported blocks on top of their parent state.
+#[cfg(feature = "full")]
+#[derive(Debug, StructOpt)]
+pub struct VerifyBlocksCmd {
+    /// First block number to verify.
+    #[structopt(long, value_name = "NUMBER")]
+    pub from: robonomics_primitives::BlockNumber,
+
+    /// Last block number to verify. [default: best block]
+    #[structopt(long, value_name = "NUMBER")]
+    pub to: Option<robonomics_primitives::BlockNumber>,
+
+    #[allow(missing_docs)]
+    #[structopt(flatten)]
+    pub shared_params: sc_cli::SharedParams,
+
+    #[allow(missing_docs)]
+    #[structopt(flatten)]
+    pub import_params: sc_cli::ImportParams,
+}
+
+#[cfg(feature = "full")]
+impl VerifyBlocksCmd {
+    /// Execute blocks one by one, runtime checks that state root matches the stored header.
+    /// Notice: Parent state of the first block should be kept by database pruning.
+    pub async fn run<C>(&self, client: std::sync::Arc<C>) -> sc_cli::Result<()>
+    where
+        C: sp_api::ProvideRuntimeApi<robonomics_primitives::Block>
+            + sc_client_api::BlockBackend<robonomics_primitives::Block>
+            + sp_blockchain::HeaderBackend<robonomics_primitives::Block>,
+        C::Api: sp_api::Core<robonomics_primitives::Block>,
+    {
+        use sp_api::Core;
+        use sp_runtime::{
+            generic::BlockId,
+            traits::{Block as BlockT, Header as HeaderT},
+            DigestItem,
+        };
+
+        let to = self.to.unwrap_or(client.info().best_number);
+        if self.from == 0 || self.from > to {
+            return Err(format!("Invalid block range {}..{}", self.from, to).into());
+        }
+
+        for number in self.from..=to {
+            let block = client
+                .block(&BlockId::Number(number))?
+                .ok_or(format!("Block #{} not found", number))?
+                .block;
+            let (mut header, extrinsics) = block.deconstruct();
+            let hash = header.hash();
+            // Seal is verified and removed by consensus before block execution.
+            if let Some(DigestItem::Seal(_, _)) = header.digest().logs().last() {
+                header.digest_mut().pop();
+            }
+
+            let parent = BlockId::Hash(*header.parent_hash());
+            let block = robonomics_primitives::Block::new(header, extrinsics);
+            if let Err(e) = client.runtime_api().execute_block(&parent, block) {
+                return Err(format!(
+                    "Block #{} ({}) diverges from stored header: {}",
+                    number, hash, e
+                )
+                .into());
+            }
+            log::info!(target: "robonomics::service", "Block #{} ({}) verified", number, hash);
+        }
+
+        println!("Blocks #{}..#{}: OK", self.from, to);
+        Ok(())
+    }
+}
+
+#[cfg(feature = "full")]
+impl sc_cli::CliConfiguration for VerifyBlocksCmd {
+    fn shared_params(&self) -> &sc_cli::SharedParams {
+        &self.shared_params
+    }
+
+    fn import_params(&self) -> Option<&sc_cli::ImportParams> {
+        Some(&self.import_params)
+    }
+}
+
+/// Remove blocks which forks branched off the finalized chain.
+#[cfg(feature = "full")]
+#[derive(Debug, StructOpt)]
+pub struct PurgeForksCmd {
+    #[allow(missing_docs)]
+    #[structopt(flatten)]
+    pub shared_params: sc_cli::SharedParams,
+
+    #[allow(missing_docs)]
+    #[structopt(flatten)]
+    pub import_params: sc_cli::ImportParams,
+}
+
+#[cfg(feature = "full")]
+impl PurgeForksCmd {
+    /// Remove dead fork leaves one by one down to the finalized chain and report database size.
+    /// Notice: Forks of unfinalized blocks are kept, they could still become canonical.
+    pub async fn run<B>(
+        &self,
+        backend: std::sync::Arc<B>,
+        database: Option<std::path::PathBuf>,
+    ) -> sc_cli::Result<()>
+    where
+        B: sc_client_api::Backend<robonomics_primitives::Block>,
+    {
+        let size_before = database.as_deref().map(dir_size);
+        let (removed, skipped) = purge_forks(&*backend)?;
+        let size_after = database.as_deref().map(dir_size);
+        println!("Removed {} fork blocks", removed);
+        if skipped > 0 {
+            println!("Skipped {} fork leaves with discarded state", skipped);
+        }
+        if let (Some(before), Some(after)) = (size_before, size_after) {
+            println!(
+                "Database size {} -> {} bytes, reclaimed {} bytes",
+                before,
+                after,
+                before.saturating_sub(after)
+            );
+        }
+        Ok(())
+    }
+}
+
+/// Remove dead forks of the finalized chain, returns numbers of removed blocks and skipped leaves.
+///
+/// Notice: Leaf which can't be removed, e.g. because its state is already pruned,
+/// is skipped together with its fork.
+#[cfg(feature = "full")]
+fn purge_forks<B>(backend: &B) -> sc_cli::Result<(usize, usize)>
+where
+    B: sc_client_api::Backend<robonomics_primitives::Block>,
+{
+    use sp_blockchain::{Backend as _, HeaderBackend};
+    use sp_runtime::{generic::BlockId, traits::Header as HeaderT};
+
+    let blockchain = backend.blockchain();
+    let info = blockchain.info();
+    let header = |hash: robonomics_primitives::Hash| -> sc_cli::Result<_> {
+        Ok(blockchain
+            .header(BlockId::Hash(hash))?
+            .ok_or_else(|| format!("Block {} not found", hash))?)
+    };
+
+    // Leaf is dead when its ancestor at finalized height isn't the finalized block.
+    let mut dead = Vec::new();
+    for leaf in blockchain.leaves()? {
+        let mut ancestor = header(leaf)?;
+        while *ancestor.number() > info.finalized_number {
+            ancestor = header(*ancestor.parent_hash())?;
+        }
+        if ancestor.hash() != info.finalized_hash {
+            dead.push(leaf);
+        }
+    }
+
+    let mut removed = 0;
+    let mut skipped = 0;
+    while let Some(hash) = dead.pop() {
+        let parent = *header(hash)?.parent_hash();
+        if let Err(e) = backend.remove_leaf_block(&hash) {
+            log::warn!(
+                target: "robonomics::service",
+                "Unable to remove fork block {}: {}",
+                hash,
+                e
+            );
+            skipped += 1;
+            continue;
+        }
+        removed += 1;
+        log::debug!(target: "robonomics::service", "Fork block {} removed", hash);
+
+        // Parent of dead block is dead too, unless it's on canonical chain.
+        let parent_number = *header(parent)?.number();
+        let is_canonical = blockchain.hash(parent_number)? == Some(parent);
+        if !is_canonical && blockchain.children(parent)?.is_empty() {
+            dead.push(parent);
+        }
+    }
+    Ok((removed, skipped))
+}
+
+/// Total size of files in directory.
+#[cfg(feature = "full")]
+fn dir_size(path: &std::path::Path) -> u64 {
+    std::fs::read_dir(path)
+        .map(|entries| {
+            entries
+                .filter_map(|entry| entry.ok())
+                .map(|entry| match entry.metadata() {
+                    Ok(meta) if meta.is_dir() => dir_size(&entry.path()),
+                    Ok(meta) => meta.len(),
+                    Err(_) => 0,
+                })
+                .sum()
+        })
+        .unwrap_or(0)
+}
+
+#[cfg(feature = "full")]
+impl sc_cli::CliConfiguration for PurgeForksCmd {
+    fn shared_params(&self) -> &sc_cli::SharedParams {
+        &self.shared_params
+    }
+
+    fn import_params(&self) -> Option<&sc_cli::ImportParams> {
+        Some(&self.import_params)
+    }
+}
+
+/// Derive validator session keys from seed, insert them into the keystore
+/// and print SCALE encoded session keys for `session.setKeys` call.
+#[cfg(feature = "full")]
+#[derive(Debug, StructOpt)]
+pub struct GenerateSessionKeysCmd {
+    /// The secret key URI: seed, mnemonic or derivation path.
+    /// Notice: It will be prompted when not given.
+    #[structopt(long)]
+    pub suri: Option<String>,
+
+    #[allow(missing_docs)]
+    #[structopt(flatten)]
+    pub shared_params: sc_cli::SharedParams,
+
+    #[allow(missing_docs)]
+    #[structopt(flatten)]
+    pub keystore_params: sc_cli::KeystoreParams,
+}
+
+#[cfg(feature = "full")]
+impl GenerateSessionKeysCmd {
+    /// Insert sr25519 BABE and ed25519 GRANDPA keys into keystore.
+    pub fn run(&self, keystore: sc_service::config::KeystoreConfig) -> sc_cli::Result<()> {
+        use sp_core::{crypto::Pair, ed25519, hexdisplay::HexDisplay, sr25519};
+        use sp_keystore::SyncCryptoStore;
+
+        let (path, password) = match keystore {
+            sc_service::config::KeystoreConfig::Path { path, password } => (path, password),
+            _ => return Err("Keystore path should be configured".into()),
+        };
+        let suri = sc_cli::utils::read_uri(self.suri.as_ref())?;
+        let babe = sr25519::Pair::from_string(&suri, None)
+            .map_err(|e| format!("Invalid secret key URI: {:?}", e))?
+            .public();
+        let grandpa = ed25519::Pair::from_string(&suri, None)
+            .map_err(|e| format!("Invalid secret key URI: {:?}", e))?
+            .public();
+
+        let keystore = sc_keystore::LocalKeystore::open(path, password)
+            .map_err(|e| format!("Unable to open keystore: {}", e))?;
+        SyncCryptoStore::insert_unknown(&keystore, sp_consensus_babe::KEY_TYPE, &suri, &babe.0)
+            .map_err(|_| "Unable to insert BABE key")?;
+        SyncCryptoStore::insert_unknown(
+            &keystore,
+            sp_finality_grandpa::KEY_TYPE,
+            &suri,
+            &grandpa.0,
+        )
+        .map_err(|_| "Unable to insert GRANDPA key")?;
+
+        // Session keys are encoded in runtime order: BABE then GRANDPA.
+        let keys = [babe.0, grandpa.0].concat();
+        println!("0x{}", HexDisplay::from(&keys));
+        Ok(())
+    }
+}
+
+#[cfg(feature = "full")]
+impl sc_cli::CliConfiguration for GenerateSessionKeysCmd {
+    fn shared_params(&self) -> &sc_cli::SharedParams {
+        &self.shared_params
+    }
+
+    fn keystore_params(&self) -> Option<&sc_cli::KeystoreParams> {
+        Some(&self.keystore_params)
+    }
+}
+
+/// Replace node network key file by fresh generated one.
+#[cfg(feature = "full")]
+#[derive(Debug, StructOpt)]
+pub struct RotateNodeKeyCmd {
+    #[allow(missing_docs)]
+    #[structopt(flatten)]
+    pub shared_params: sc_cli::SharedParams,
+}
+
+#[cfg(feature = "full")]
+impl RotateNodeKeyCmd {
+    /// Write new ed25519 secret into `secret_ed25519` file of network config directory.
+    /// Notice: Node should be restarted to use new key.
+    pub fn run(&self, net_config_path: Option<std::path::PathBuf>) -> sc_cli::Result<()> {
+        use sc_network::config::identity::{ed25519, PublicKey};
+        use std::io::Write;
+
+        let dir = net_config_path.ok_or("Network config path should be configured")?;
+        std::fs::create_dir_all(&dir)?;
+        // The same file name as default `--node-key-file` of ed25519 node key type.
+        let path = dir.join("secret_ed25519");
+
+        let keypair = ed25519::Keypair::generate();
+        let mut options = std::fs::OpenOptions::new();
+        options.write(true).create(true).truncate(true);
+        #[cfg(unix)]
+        std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+        options.open(&path)?.write_all(keypair.secret().as_ref())?;
+
+        eprintln!("Node key written into {}", path.display());
+        println!("{}", PublicKey::Ed25519(keypair.public()).into_peer_id());
+        Ok(())
+    }
+}
+
+#[cfg(feature = "full")]
+impl sc_cli::CliConfiguration for RotateNodeKeyCmd {
+    fn shared_params(&self) -> &sc_cli::SharedParams {
+        &self.shared_params
+    }
+}
+
+/// Decode extrinsic and print its signer, call and arguments as JSON.
+#[cfg(feature = "full")]
+#[derive(Debug, StructOpt)]
+pub struct DecodeExtrinsicCmd {
+    /// Hex encoded extrinsic.
+    #[structopt(value_name = "HEX")]
+    pub extrinsic: String,
+}
+
+#[cfg(feature = "full")]
+impl DecodeExtrinsicCmd {
+    /// Decode extrinsic, robonomics pallets calls arguments are printed field by field.
+    pub fn run(&self) -> sc_cli::Result<()> {
+        use codec::Decode;
+        use local_runtime::{Call, UncheckedExtrinsic};
+        use serde_json::json;
+        use sp_core::{bytes::from_hex, crypto::Ss58Codec, hexdisplay::HexDisplay};
+
+        let encoded = from_hex(&self.extrinsic).map_err(|e| format!("Invalid hex: {}", e))?;
+        let xt = UncheckedExtrinsic::decode(&mut &encoded[..])
+            .map_err(|e| format!("Unable to decode extrinsic: {}", e))?;
+
+        let signer = xt.signature.as_ref().map(|(address, _, _)| match address {
+            sp_runtime::MultiAddress::Id(account) => account.to_ss58check(),
+            other => format!("{:?}", other),
+        });
+        let call = match xt.function {
+            Call::Datalog(pallet_robonomics_datalog::Call::record(record)) => json!({
+                "pallet": "datalog",
+                "call": "record",
+                "args": { "record": format!("0x{}", HexDisplay::from(&record)) },
+            }),
+            Call::Datalog(pallet_robonomics_datalog::Call::erase()) => json!({
+                "pallet": "datalog",
+                "call": "erase",
+                "args": {},
+            }),
+            Call::Launch(pallet_robonomics_launch::Call::launch(robot, param)) => json!({
+                "pallet": "launch",
+                "call": "launch",
+                "args": { "robot": robot.to_ss58check(), "param": param },
+            }),
+            other => json!({ "call": format!("{:?}", other) }),
+        };
+
+        let decoded = json!({ "signer": signer, "call": call });
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&decoded).map_err(|e| e.to_string())?
+        );
+        Ok(())
+    }
+}
+
+/// Derive account ID from seed and print it in SS58 format.
+#[cfg(feature = "full")]
+#[derive(Debug, StructOpt)]
+pub struct DeriveAccountCmd {
+    /// Development account name (e.g. `Alice`), secret URI (e.g. `//Alice`) or phrase.
+    #[structopt(long, value_name = "SEED")]
+    pub seed: String,
+
+    /// Cryptography scheme of the account key.
+    #[structopt(
+        long,
+        value_name = "SCHEME",
+        possible_values = &sc_cli::CryptoScheme::variants(),
+        case_insensitive = true,
+        default_value = "Sr25519"
+    )]
+    pub scheme: sc_cli::CryptoScheme,
+}
+
+#[cfg(feature = "full")]
+impl DeriveAccountCmd {
+    /// Derive account ID, plain account name is derived as `//<name>` like genesis accounts.
+    pub fn run(&self) -> sc_cli::Result<()> {
+        use crate::chain_spec::get_account_id_from_suri;
+        use sc_cli::CryptoScheme;
+        use sp_core::{crypto::Ss58Codec, ecdsa, ed25519, sr25519};
+
+        let seed = self.seed.trim();
+        let is_name = seed.chars().all(|c| c.is_ascii_alphanumeric()) && !seed.starts_with("0x");
+        let suri = if is_name {
+            format!("//{}", seed)
+        } else {
+            seed.to_string()
+        };
+
+        let account = match self.scheme {
+            CryptoScheme::Sr25519 => get_account_id_from_suri::<sr25519::Public>(&suri)?,
+            CryptoScheme::Ed25519 => get_account_id_from_suri::<ed25519::Public>(&suri)?,
+            CryptoScheme::Ecdsa => get_account_id_from_suri::<ecdsa::Public>(&suri)?,
+        };
+        println!("{}", account.to_ss58check());
+        Ok(())
+    }
+}
+
+/// Build genesis storage of the chain and print its state root and block hash.
+#[cfg(feature = "full")]
+#[derive(Debug, StructOpt)]
+pub struct GenesisHashCmd {
+    /// Chain id or path to chain spec file.
+    #[structopt(value_name = "CHAIN")]
+    pub chain: String,
+}
+
+#[cfg(feature = "full")]
+impl GenesisHashCmd {
+    /// Build genesis block of given chain spec without starting the node.
+    pub fn run(&self, chain_spec: Box<dyn sc_service::ChainSpec>) -> sc_cli::Result<()> {
+        use sp_runtime::traits::{Block as BlockT, Header as HeaderT};
+
+        let block = crate::chain_spec::genesis_block(&chain_spec)?;
+        println!("State root: {}", block.header().state_root());
+        println!("Block hash: {}", block.hash());
+        Ok(())
+    }
+}
+
+/// Fetch metrics of running node from its Prometheus exporter and print them as JSON.
+#[derive(Debug, StructOpt)]
+pub struct MetricsSnapshotCmd {
+    /// Prometheus exporter endpoint of the node.
+    #[structopt(
+        long,
+        value_name = "URL",
+        default_value = "http://127.0.0.1:9615/metrics"
+    )]
+    pub metrics_url: String,
+}
+
+impl MetricsSnapshotCmd {
+    /// Print metric samples grouped by name, e.g. `{"name": [{"labels": {}, "value": 1.0}]}`.
+    pub fn run(&self) -> sc_cli::Result<()> {
+        let request = hyper::Request::get(&self.metrics_url)
+            .header(hyper::header::ACCEPT, "text/plain")
+            .body(hyper::Body::empty())
+            .map_err(|e| e.to_string())?;
+        let metrics = parse_metrics(&http_request(request)?)?;
+        let json = serde_json::to_string_pretty(&metrics).map_err(|e| e.to_string())?;
+        println!("{}", json);
+        Ok(())
+    }
+}
+
+/// Group samples of Prometheus text format by metric name, comments are skipped.
+fn parse_metrics(text: &str) -> Result<serde_json::Map<String, serde_json::Value>, String> {
+    let mut metrics = serde_json::Map::new();
+    for line in text.lines().map(str::trim) {
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let (name, labels, value) =
+            parse_sample(line).ok_or_else(|| format!("Invalid metrics sample: {}", line))?;
+        // JSON has no NaN and infinities, so non-finite values are kept as strings.
+        let value = match serde_json::Number::from_f64(value) {
+            Some(number) => serde_json::Value::Number(number),
+            None => serde_json::Value::String(format_value(value)),
+        };
+        let sample = serde_json::json!({ "labels": labels, "value": value });
+        metrics
+            .entry(name)
+            .or_insert_with(|| serde_json::Value::Array(Vec::new()))
+            .as_array_mut()
+            .expect("metric entries are arrays; qed")
+            .push(sample);
+    }
+    Ok(metrics)
+}
+
+/// Prometheus text format of non-finite sample value.
+fn format_value(value: f64) -> String {
+    if value.is_nan() {
+        "NaN".into()
+    } else if value > 0.0 {
+        "+Inf".into()
+    } else {
+        "-Inf".into()
+    }
+}
+
+/// Wait for running node sync using its HTTP RPC.
+#[derive(Debug, StructOpt)]
+pub struct WaitSyncedCmd {
+    /// HTTP RPC endpoint of the node.
+    #[structopt(long, value_name = "URL", default_value = "http://127.0.0.1:9933")]
+    pub rpc: String,
+
+    /// Maximum time to wait in seconds.
+    #[structopt(long, value_name = "SECS", default_value = "3600")]
+    pub timeout: u64,
+
+    /// Interval between node state checks in seconds.
+    #[structopt(long, value_name = "SECS", default_value = "2")]
+    pub interval: u64,
+}
+
+impl WaitSyncedCmd {
+    /// Return when node isn't syncing and finalized head advanced since the first check.
+    /// Notice: Node that synced state without finalizing new blocks isn't ready yet.
+    pub fn run(&self) -> sc_cli::Result<()> {
+        let deadline = std::time::Instant::now() + std::time::Duration::from_secs(self.timeout);
+        let mut first_finalized = None;
+        loop {
+            // Node could be still starting, so RPC errors are retried until timeout.
+            match self.check() {
+                Ok((is_syncing, finalized)) => {
+                    let first = *first_finalized.get_or_insert(finalized);
+                    if !is_syncing && finalized > first {
+                        println!("Synced, finalized #{}", finalized);
+                        return Ok(());
+                    }
+                    eprintln!("Syncing: {}, finalized #{}", is_syncing, finalized);
+                }
+                Err(e) => eprintln!("Node isn't available: {}", e),
+            }
+
+            if std::time::Instant::now() >= deadline {
+                return Err(format!("Node isn't synced in {} seconds", self.timeout).into());
+            }
+            std::thread::sleep(std::time::Duration::from_secs(self.interval));
+        }
+    }
+
+    /// Node syncing state and finalized block number.
+    fn check(&self) -> Result<(bool, u64), String> {
+        let health = rpc_call(&self.rpc, "system_health", serde_json::json!([]))?;
+        let is_syncing = health["isSyncing"]
+            .as_bool()
+            .ok_or("Malformed system_health response")?;
+        let hash = rpc_call(&self.rpc, "chain_getFinalizedHead", serde_json::json!([]))?;
+        let header = rpc_call(&self.rpc, "chain_getHeader", serde_json::json!([hash]))?;
+        let number = header["number"]
+            .as_str()
+            .and_then(|n| u64::from_str_radix(n.trim_start_matches("0x"), 16).ok())
+            .ok_or("Malformed chain_getHeader response")?;
+        Ok((is_syncing, number))
+    }
+}
+
+/// Call node HTTP JSON-RPC method and return its result.
+fn rpc_call(
+    url: &str,
+    method: &str,
+    params: serde_json::Value,
+) -> Result<serde_json::Value, String> {
+    let request = serde_json::json!({
+        "jsonrpc": "2.0",
+        "id": 1,
+        "method": method,
+        "params": params,
+    });
+    let request = hyper::Request::post(url)
+        .header(hyper::header::CONTENT_TYPE, "application/json")
+        .body(request.to_string().into())
+        .map_err(|e| e.to_string())?;
+    let body = http_request(request)?;
+    let mut response: serde_json::Value =
+        serde_json::from_str(&body).map_err(|e| format!("Malformed RPC response: {}", e))?;
+    if let Some(error) = response.get("error") {
+        return Err(format!("{} failed: {}", method, error));
+    }
+    Ok(response["result"].take())
+}
+
+/// Send HTTP request and read response body, non-success status is an error.
+fn http_request(request: hyper::Request<hyper::Body>) -> Result<String, String> {
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .map_err(|e| e.to_string())?;
+    runtime.block_on(async {
+        let uri = request.uri().clone();
+        let response = hyper::Client::new()
+            .request(request)
+            .await
+            .map_err(|e| format!("Request to {} failed: {}", uri, e))?;
+        if !response.status().is_success() {
+            return Err(format!("Unexpected HTTP status: {}", response.status()));
+        }
+        let body = hyper::body::to_bytes(response.into_body())
+            .await
+            .map_err(|e| e.to_string())?;
+        String::from_utf8(body.to_vec()).map_err(|e| e.to_string())
+    })
+}
+
+/// Parse Prometheus text format sample, e.g. `name{label="value"} 1.0 [timestamp]`.
+fn parse_sample(line: &str) -> Option<(String, serde_json::Map<String, serde_json::Value>, f64)> {
+    let mut labels = serde_json::Map::new();
+    let (name, rest) = match line.find(|c| c == '{' || c == ' ') {
+        Some(pos) => (&line[..pos], &line[pos..]),
+        None => return None,
+    };
+
+    let mut rest = rest;
+    if let Some(inner) = rest.strip_prefix('{') {
+        let mut chars = inner.char_indices();
+        let mut key = String::new();
+        let mut end = None;
+        while let Some((i, c)) = chars.next() {
+            match c {
+                '}' => {
+                    end = Some(i + 1);
+                    break;
+                }
+                ',' | ' ' => (),
+                '=' => {
+                    // Quoted value with `\"`, `\\` and `\n` escapes.
+                    if chars.next()?.1 != '"' {
+                        return None;
+                    }
+                    let mut value = String::new();
+                    loop {
+                        match chars.next()?.1 {
+                            '"' => break,
+                            '\\' => match chars.next()?.1 {
+                                'n' => value.push('\n'),
+                                c => value.push(c),
+                            },
+                            c => value.push(c),
+                        }
+                    }
+                    labels.insert(std::mem::take(&mut key), value.into());
+                }
+                c => key.push(c),
+            }
+        }
+        rest = &inner[end?..];
+    }
+
+    let value = match rest.split_whitespace().next()? {
+        "NaN" => f64::NAN,
+        "+Inf" => f64::INFINITY,
+        "-Inf" => f64::NEG_INFINITY,
+        v => v.parse().ok()?,
+    };
+    Some((name.into(), labels, value))
+}
+
+/// Benchmark robonomics pallet extrinsics of development runtime
+/// and write weights into `<output-dir>/<pallet>.rs` file.
+#[cfg(feature = "frame-benchmarking-cli")]
+#[derive(Debug, StructOpt)]
+pub struct BenchmarkPalletCmd {
+    /// Pallet name, e.g. `robonomics_datalog`, `pallet_` prefix could be omitted.
+    #[structopt(long)]
+    pub pallet: String,
+
+    /// Extrinsic name to benchmark. [default: all extrinsics]
+    #[structopt(long, default_value = "*")]
+    pub extrinsic: String,
+
+    /// Number of samples of benchmark components.
+    #[structopt(long, default_value = "50")]
+    pub steps: u32,
+
+    /// Number of repetitions of each sample.
+    #[structopt(long, default_value = "20")]
+    pub repeat: u32,
+
+    /// Directory to write weights file into.
+    #[structopt(long, default_value = "weights", parse(from_os_str))]
+    pub output_dir: std::path::PathBuf,
+}
+
+#[cfg(feature = "frame-benchmarking-cli")]
+impl BenchmarkPalletCmd {
+    /// Generic benchmark command for given robonomics pallet.
+    pub fn benchmark_cmd(&self) -> sc_cli::Result<frame_benchmarking_cli::BenchmarkCmd> {
+        let pallet = if self.pallet.starts_with("pallet_") {
+            self.pallet.clone()
+        } else {
+            format!("pallet_{}", self.pallet)
+        };
+        std::fs::create_dir_all(&self.output_dir)?;
+        let output = self.output_dir.join(format!("{}.rs", pallet));
+
+        Ok(frame_benchmarking_cli::BenchmarkCmd::from_iter(&[
+            "benchmark".to_string(),
+            "--chain=dev".to_string(),
+            "--execution=wasm".to_string(),
+            "--wasm-execution=compiled".to_string(),
+            format!("--pallet={}", pallet),
+            format!("--extrinsic={}", self.extrinsic),
+            format!("--steps={}", self.steps),
+            format!("--repeat={}", self.repeat),
+            format!("--output={}", output.display()),
+        ]))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_sample_with_labels() {
+        let (name, labels, value) =
+            parse_sample(r#"substrate_block_height{status="best",chain="dev"} 42"#).unwrap();
+        assert_eq!(name, "substrate_block_height");
+        assert_eq!(labels["status"], "best");
+        assert_eq!(labels["chain"], "dev");
+        assert_eq!(value, 42.0);
+    }
+
+    #[test]
+    fn parse_sample_with_escaped_label_and_timestamp() {
+        let line = r#"requests{path="/a,b}",note="say \"hi\"\n"} 1.5 1623000000000"#;
+        let (name, labels, value) = parse_sample(line).unwrap();
+        assert_eq!(name, "requests");
+        assert_eq!(labels["path"], "/a,b}");
+        assert_eq!(labels["note"], "say \"hi\"\n");
+        assert_eq!(value, 1.5);
+    }
+
+    #[test]
+    fn parse_sample_special_values() {
+        assert!(parse_sample("ratio NaN").unwrap().2.is_nan());
+        assert_eq!(
+            parse_sample(r#"bucket{le="+Inf"} +Inf"#).unwrap().2,
+            f64::INFINITY
+        );
+        assert_eq!(parse_sample("delta -Inf").unwrap().2, f64::NEG_INFINITY);
+    }
+
+    #[test]
+    fn parse_sample_invalid() {
+        assert!(parse_sample("no_value").is_none());
+        assert!(parse_sample(r#"name{label=unquoted} 1"#).is_none());
+        assert!(parse_sample("name one").is_none());
+    }
+
+    #[test]
+    fn parse_metrics_skips_comments() {
+        let text = "# HELP up Node is up.\n# TYPE up gauge\nup 1\n\nup{peer=\"a\"} NaN\n";
+        let metrics = parse_metrics(text).unwrap();
+        assert_eq!(metrics.len(), 1);
+        let samples = metrics["up"].as_array().unwrap();
+        assert_eq!(samples.len(), 2);
+        assert_eq!(samples[0]["value"], 1.0);
+        assert_eq!(samples[1]["labels"]["peer"], "a");
+        assert_eq!(samples[1]["value"], "NaN");
+    }
+
+    #[cfg(feature = "full")]
+    mod purge_forks {
+        use super::super::purge_forks;
+        use robonomics_primitives::{Block, BlockNumber, Hash, Header};
+        use sc_client_api::backend::{Backend as _, BlockImportOperation as _, NewBlockState};
+        use sc_client_db::{
+            Backend, DatabaseSettings, DatabaseSettingsSrc, KeepBlocks, PruningMode,
+            TransactionStorageMode,
+        };
+        use sp_blockchain::{Backend as _, HeaderBackend};
+        use sp_runtime::{
+            generic::{BlockId, Digest, DigestItem},
+            traits::{BlakeTwo256, Hash as _, Header as _},
+        };
+
+        fn backend(dir: &std::path::Path, state_pruning: PruningMode) -> Backend<Block> {
+            let settings = DatabaseSettings {
+                state_cache_size: 16 * 1024 * 1024,
+                state_cache_child_ratio: Some((0, 100)),
+                state_pruning,
+                source: DatabaseSettingsSrc::RocksDb {
+                    path: dir.into(),
+                    cache_size: 16,
+                },
+                keep_blocks: KeepBlocks::All,
+                transaction_storage: TransactionStorageMode::BlockBody,
+            };
+            Backend::new(settings, 16).unwrap()
+        }
+
+        fn insert_block(
+            backend: &Backend<Block>,
+            parent: Hash,
+            number: BlockNumber,
+            fork: u8,
+            state: NewBlockState,
+        ) -> Hash {
+            let header = Header::new(
+                number,
+                Default::default(),
+                BlakeTwo256::trie_root(Vec::new()),
+                parent,
+                Digest {
+                    logs: vec![DigestItem::Other(vec![fork])],
+                },
+            );
+            let hash = header.hash();
+            let mut op = backend.begin_operation().unwrap();
+            backend
+                .begin_state_operation(&mut op, BlockId::Hash(parent))
+                .unwrap();
+            op.set_block_data(header, Some(Vec::new()), None, state)
+                .unwrap();
+            backend.commit_operation(op).unwrap();
+            hash
+        }
+
+        /// Canonical chain `G-a1-a2-a3` finalized at `a2` with dead fork `G-b1-b2`
+        /// and live fork `a2-c3`, returns `(a3, b1, b2, c3)`.
+        fn forked_chain(backend: &Backend<Block>) -> (Hash, Hash, Hash, Hash) {
+            let genesis = insert_block(backend, Default::default(), 0, 0, NewBlockState::Final);
+            let a1 = insert_block(backend, genesis, 1, 0, NewBlockState::Best);
+            let a2 = insert_block(backend, a1, 2, 0, NewBlockState::Best);
+            let a3 = insert_block(backend, a2, 3, 0, NewBlockState::Best);
+            let b1 = insert_block(backend, genesis, 1, 1, NewBlockState::Normal);
+            let b2 = insert_block(backend, b1, 2, 1, NewBlockState::Normal);
+            let c3 = insert_block(backend, a2, 3, 2, NewBlockState::Normal);
+            backend.finalize_block(BlockId::Hash(a1), None).unwrap();
+            backend.finalize_block(BlockId::Hash(a2), None).unwrap();
+            (a3, b1, b2, c3)
+        }
+
+        #[test]
+        fn dead_fork_is_removed() {
+            let dir = tempfile::tempdir().unwrap();
+            let backend = backend(dir.path(), PruningMode::ArchiveAll);
+            let (a3, b1, b2, c3) = forked_chain(&backend);
+
+            assert_eq!(purge_forks(&backend).unwrap(), (2, 0));
+            let blockchain = backend.blockchain();
+            let mut leaves = blockchain.leaves().unwrap();
+            leaves.sort();
+            let mut expected = vec![a3, c3];
+            expected.sort();
+            assert_eq!(leaves, expected);
+            assert!(blockchain.header(BlockId::Hash(b1)).unwrap().is_none());
+            assert!(blockchain.header(BlockId::Hash(b2)).unwrap().is_none());
+            assert_eq!(blockchain.info().best_hash, a3);
+        }
+
+        #[test]
+        fn pruned_fork_is_skipped() {
+            let dir = tempfile::tempdir().unwrap();
+            let backend = backend(dir.path(), PruningMode::keep_blocks(16));
+            let (a3, _, b2, c3) = forked_chain(&backend);
+
+            assert_eq!(purge_forks(&backend).unwrap(), (0, 1));
+            let leaves = backend.blockchain().leaves().unwrap();
+            assert!(leaves.contains(&b2));
+            assert!(leaves.contains(&a3));
+            assert!(leaves.contains(&c3));
+        }
+    }
 }
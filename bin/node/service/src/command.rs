@@ -17,6 +17,7 @@
 ///////////////////////////////////////////////////////////////////////////////
 
 use crate::cli::{Cli, Subcommand};
+use crate::error::RobonomicsCliError;
 #[cfg(feature = "full")]
 use crate::{chain_spec::*, service::robonomics};
 use sc_cli::{ChainSpec, RuntimeVersion, SubstrateCli};
@@ -24,6 +25,34 @@ use sc_cli::{ChainSpec, RuntimeVersion, SubstrateCli};
 #[cfg(feature = "parachain")]
 use crate::parachain;
 
+/// Environment variable with telemetry endpoint for included parachain specs.
+#[cfg(feature = "parachain")]
+const TELEMETRY_URL_ENV: &str = "ROBONOMICS_TELEMETRY_URL";
+
+/// Run subcommand with partial components of the chain family.
+#[cfg(feature = "full")]
+macro_rules! async_run_partial {
+    ($cli:ident, $cmd:ident, |$components:ident, $config:ident| $code:expr) => {{
+        let runner = $cli.create_runner($cmd)?;
+        match runner.config().chain_spec.family() {
+            RobonomicsFamily::Development => runner.async_run(|$config| {
+                let $components = crate::service::new_partial::<
+                    local_runtime::RuntimeApi,
+                    robonomics::Executor,
+                >(&$config, None, Vec::new(), None, None)?;
+                let task_manager = $components.task_manager;
+                Ok(($code, task_manager))
+            }),
+            #[cfg(feature = "parachain")]
+            RobonomicsFamily::Parachain => runner.async_run(|$config| {
+                let $components = parachain::new_partial(&$config)?;
+                let task_manager = $components.task_manager;
+                Ok(($code, task_manager))
+            }),
+        }
+    }};
+}
+
 impl SubstrateCli for Cli {
     fn impl_name() -> String {
         "airalab-robonomics".into()
@@ -53,22 +82,8 @@ impl SubstrateCli for Cli {
         "robonomics".into()
     }
 
-    #[cfg(feature = "full")]
     fn load_spec(&self, id: &str) -> std::result::Result<Box<dyn sc_service::ChainSpec>, String> {
-        Ok(match id {
-            "dev" => Box::new(development_config()),
-            #[cfg(feature = "parachain")]
-            path => parachain::load_spec(path, self.run.parachain_id.unwrap_or(1000).into())?,
-            #[cfg(not(feature = "parachain"))]
-            path => Box::new(crate::chain_spec::ChainSpec::from_json_file(
-                std::path::PathBuf::from(path),
-            )?),
-        })
-    }
-
-    #[cfg(not(feature = "full"))]
-    fn load_spec(&self, _id: &str) -> std::result::Result<Box<dyn sc_service::ChainSpec>, String> {
-        Err("Chain spec isn't supported for zero build")?
+        Ok(self.load_chain_spec(id)?)
     }
 
     #[cfg(feature = "full")]
@@ -86,37 +101,417 @@ impl SubstrateCli for Cli {
     }
 }
 
+impl Cli {
+    /// Load chain spec by given id or path.
+    #[cfg(feature = "full")]
+    pub fn load_chain_spec(
+        &self,
+        id: &str,
+    ) -> std::result::Result<Box<dyn sc_service::ChainSpec>, RobonomicsCliError> {
+        let chain_spec: Box<dyn sc_service::ChainSpec> = match id {
+            "dev" => Box::new(development_config()),
+            #[cfg(feature = "parachain")]
+            path => {
+                // Telemetry endpoint for included chain specs: `--telemetry-url` or env variable.
+                let telemetry = if self.run.base.no_telemetry {
+                    None
+                } else {
+                    self.run
+                        .base
+                        .telemetry_endpoints
+                        .first()
+                        .cloned()
+                        .or_else(|| std::env::var(TELEMETRY_URL_ENV).ok().map(|url| (url, 0)))
+                };
+                parachain::load_spec(
+                    path,
+                    self.run.parachain_id.unwrap_or(1000).into(),
+                    telemetry,
+                )?
+            }
+            #[cfg(not(feature = "parachain"))]
+            path => Box::new(
+                crate::chain_spec::ChainSpec::from_json_file(std::path::PathBuf::from(path))
+                    .map_err(RobonomicsCliError::SpecParse)?,
+            ),
+        };
+
+        Ok(chain_spec)
+    }
+
+    /// Load chain spec by given id or path.
+    #[cfg(not(feature = "full"))]
+    pub fn load_chain_spec(
+        &self,
+        id: &str,
+    ) -> std::result::Result<Box<dyn sc_service::ChainSpec>, RobonomicsCliError> {
+        // Chain specs aren't supported for zero build.
+        Err(RobonomicsCliError::UnknownChain(id.into()))
+    }
+}
+
+/// Use chain family default ports when ports isn't set explicitly.
+#[cfg(feature = "full")]
+fn set_default_ports(cli: &mut Cli) -> sc_cli::Result<()> {
+    let shared_params = &cli.run.base.shared_params;
+    let chain_id = shared_params.chain_id(shared_params.dev);
+    let (p2p, rpc, ws, prometheus) = cli.load_spec(&chain_id)?.family().default_ports();
+
+    let run = &mut cli.run.base;
+    run.network_params.port.get_or_insert(p2p);
+    run.rpc_port.get_or_insert(rpc);
+    run.ws_port.get_or_insert(ws);
+    run.prometheus_port.get_or_insert(prometheus);
+    Ok(())
+}
+
+/// Shared parameters of the command that opens chain database or keystore.
+#[cfg(feature = "full")]
+fn database_shared_params(cli: &mut Cli) -> Option<&mut sc_cli::SharedParams> {
+    match &mut cli.subcommand {
+        None => Some(&mut cli.run.base.shared_params),
+        Some(Subcommand::PurgeChain(cmd)) => Some(&mut cmd.shared_params),
+        Some(Subcommand::CheckBlock(cmd)) => Some(&mut cmd.shared_params),
+        Some(Subcommand::ExportBlocks(cmd)) => Some(&mut cmd.shared_params),
+        Some(Subcommand::ExportState(cmd)) => Some(&mut cmd.shared_params),
+        Some(Subcommand::ImportBlocks(cmd)) => Some(&mut cmd.shared_params),
+        Some(Subcommand::Revert(cmd)) => Some(&mut cmd.shared_params),
+        Some(Subcommand::ExportBonus(cmd)) => Some(&mut cmd.shared_params),
+        Some(Subcommand::VerifyBlocks(cmd)) => Some(&mut cmd.shared_params),
+        Some(Subcommand::PurgeForks(cmd)) => Some(&mut cmd.shared_params),
+        Some(Subcommand::GenerateSessionKeys(cmd)) => Some(&mut cmd.shared_params),
+        Some(Subcommand::RotateNodeKey(cmd)) => Some(&mut cmd.shared_params),
+        _ => None,
+    }
+}
+
+/// Log targets of robonomics components.
+#[cfg(any(feature = "full", feature = "robonomics-cli"))]
+const ROBONOMICS_LOG_TARGETS: &[&str] = &[
+    "robonomics::service",
+    "robonomics::collator",
+    "robonomics::io",
+    "robonomics::protocol",
+    "robonomics::pubsub",
+];
+
+/// Log filter enabling debug level of all robonomics targets.
+#[cfg(any(feature = "full", feature = "robonomics-cli"))]
+fn robonomics_debug_filter() -> String {
+    ROBONOMICS_LOG_TARGETS
+        .iter()
+        .map(|target| format!("{}=debug", target))
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+/// Init logger of subcommands without node configuration when `--robonomics-debug` is given.
+#[cfg(feature = "robonomics-cli")]
+fn init_robonomics_debug_logger(cli: &Cli) {
+    if cli.robonomics_debug {
+        let _ = sc_cli::LoggerBuilder::new(robonomics_debug_filter()).init();
+    }
+}
+
+/// Append chain family subdirectory to `--base-path` unless `--no-family-subdir` is given.
+#[cfg(feature = "full")]
+fn set_family_base_path(cli: &mut Cli) -> sc_cli::Result<()> {
+    if cli.no_family_subdir {
+        return Ok(());
+    }
+
+    let (chain_id, base_path) = match database_shared_params(cli) {
+        Some(params) => match params.base_path.clone() {
+            Some(base_path) => (params.chain_id(params.dev), base_path),
+            None => return Ok(()),
+        },
+        None => return Ok(()),
+    };
+    let family = cli.load_spec(&chain_id)?.family();
+    if let Some(params) = database_shared_params(cli) {
+        params.base_path = Some(base_path.join(family.to_string()));
+    }
+    Ok(())
+}
+
+/// Abort when native runtime of the chain spec doesn't belong to chain family.
+#[cfg(feature = "full")]
+fn check_runtime_family(chain_spec: &Box<dyn ChainSpec>) -> Result<(), RobonomicsCliError> {
+    let family = chain_spec.family();
+    let spec_name = &Cli::native_runtime_version(chain_spec).spec_name;
+    if spec_name.as_ref() != family.spec_name() {
+        return Err(RobonomicsCliError::RuntimeMismatch {
+            family: family.to_string(),
+            spec_name: spec_name.to_string(),
+        });
+    }
+
+    Ok(())
+}
+
+/// Log single startup line with chain family, native runtime and parachain id.
+///
+/// Format is kept stable for monitoring, e.g. `family=parachain spec_version=12 para_id=2048`.
+#[cfg(feature = "full")]
+fn log_startup_banner(chain_spec: &Box<dyn ChainSpec>, _parachain_id: Option<u32>) {
+    let family = chain_spec.family();
+    let version = Cli::native_runtime_version(chain_spec);
+    #[allow(unused_mut)]
+    let mut para_id = None;
+    #[cfg(feature = "parachain")]
+    if let RobonomicsFamily::Parachain = family {
+        para_id = _parachain_id
+            .or_else(|| parachain::chain_spec::Extensions::try_get(chain_spec).map(|e| e.para_id));
+    }
+
+    log::info!(
+        target: "robonomics::service",
+        "[Robonomics] family={} spec_name={} spec_version={} para_id={}",
+        family,
+        version.spec_name,
+        version.spec_version,
+        para_id.map_or_else(|| "none".to_string(), |id| id.to_string()),
+    );
+}
+
+/// Wasm overrides directory of pinned runtime, removed on drop.
+#[cfg(feature = "full")]
+struct PinnedRuntimeDir(std::path::PathBuf);
+
+#[cfg(feature = "full")]
+impl Drop for PinnedRuntimeDir {
+    fn drop(&mut self) {
+        if let Err(e) = std::fs::remove_dir_all(&self.0) {
+            log::warn!(
+                target: "robonomics::service",
+                "Unable to remove {}: {}",
+                self.0.display(),
+                e
+            );
+        }
+    }
+}
+
+/// Execute blocks by given wasm runtime only, native runtime isn't used at all.
+///
+/// Runtime is given to the client as the only wasm override, so it replaces on-chain code
+/// of the same `spec_version` as the pinned runtime has, the service refuses chain of other
+/// runtime versions and stops the node once chain is upgraded. Overrides directory should be kept until the node exits.
+#[cfg(feature = "full")]
+fn pin_runtime(
+    config: &mut sc_service::Configuration,
+    wasm: &std::path::Path,
+) -> Result<PinnedRuntimeDir, RobonomicsCliError> {
+    use sc_client_api::{ExecutionStrategies, ExecutionStrategy};
+
+    if config.wasm_runtime_overrides.is_some() {
+        return Err(RobonomicsCliError::PinRuntime(
+            "--wasm-runtime-overrides can't be used together with --pin-runtime".into(),
+        ));
+    }
+    if config.role.is_authority() {
+        return Err(RobonomicsCliError::PinRuntime(
+            "validator can't run pinned runtime".into(),
+        ));
+    }
+    if !matches!(config.chain_spec.family(), RobonomicsFamily::Development) {
+        return Err(RobonomicsCliError::PinRuntime(
+            "runtime could be pinned for development chain only".into(),
+        ));
+    }
+
+    // Overrides directory should contain nothing but pinned runtime.
+    let dir = std::env::temp_dir().join(format!("robonomics-pinned-{}", std::process::id()));
+    std::fs::create_dir_all(&dir)
+        .map_err(|e| RobonomicsCliError::PinRuntime(format!("{}: {}", dir.display(), e)))?;
+    let dir = PinnedRuntimeDir(dir);
+    std::fs::copy(wasm, dir.0.join("pinned.wasm"))
+        .map_err(|e| RobonomicsCliError::PinRuntime(format!("{}: {}", wasm.display(), e)))?;
+    config.wasm_runtime_overrides = Some(dir.0.clone());
+    config.execution_strategies = ExecutionStrategies {
+        syncing: ExecutionStrategy::AlwaysWasm,
+        importing: ExecutionStrategy::AlwaysWasm,
+        block_construction: ExecutionStrategy::AlwaysWasm,
+        offchain_worker: ExecutionStrategy::AlwaysWasm,
+        other: ExecutionStrategy::AlwaysWasm,
+    };
+
+    log::warn!(
+        target: "robonomics::service",
+        "!!! Runtime is pinned to {}, results are NOT canonical !!!",
+        wasm.display()
+    );
+    log::warn!(
+        target: "robonomics::service",
+        "!!! Do not use this node for validation, RPC or block production !!!"
+    );
+    Ok(dir)
+}
+
+/// Log wasm runtime overrides given by `--wasm-runtime-overrides` to keep them auditable.
+#[cfg(feature = "full")]
+fn log_wasm_overrides(config: &sc_service::Configuration) -> Result<(), RobonomicsCliError> {
+    let dir = match &config.wasm_runtime_overrides {
+        Some(dir) => dir,
+        None => return Ok(()),
+    };
+
+    let entries = std::fs::read_dir(dir)
+        .map_err(|e| RobonomicsCliError::WasmOverrides(format!("{}: {}", dir.display(), e)))?;
+    for path in entries.filter_map(|e| e.ok()).map(|e| e.path()) {
+        if path.extension().map_or(false, |ext| ext == "wasm") {
+            let code = std::fs::read(&path).map_err(|e| {
+                RobonomicsCliError::WasmOverrides(format!("{}: {}", path.display(), e))
+            })?;
+            log::warn!(
+                target: "robonomics::service",
+                "Wasm runtime override {} with code hash 0x{}",
+                path.display(),
+                sp_core::hexdisplay::HexDisplay::from(&sp_core::hashing::blake2_256(&code)),
+            );
+        }
+    }
+
+    Ok(())
+}
+
+/// Merge `--bootnodes` with chain spec boot nodes, duplicated addresses are dropped.
+#[cfg(feature = "full")]
+fn merge_boot_nodes(config: &mut sc_service::Configuration) {
+    let given = std::mem::take(&mut config.network.boot_nodes);
+    config.network.boot_nodes = merge_addresses(config.chain_spec.boot_nodes(), given);
+}
+
+/// Chain spec addresses followed by given ones, the first of duplicated addresses is kept.
+#[cfg(feature = "full")]
+fn merge_addresses(
+    spec: &[sc_service::config::MultiaddrWithPeerId],
+    given: Vec<sc_service::config::MultiaddrWithPeerId>,
+) -> Vec<sc_service::config::MultiaddrWithPeerId> {
+    let mut seen = std::collections::HashSet::new();
+    spec.iter()
+        .cloned()
+        .chain(given)
+        .filter(|node| seen.insert(node.to_string()))
+        .collect()
+}
+
 /// Parse command line arguments into service configuration.
 pub fn run() -> sc_cli::Result<()> {
-    let cli = Cli::from_args();
+    #[allow(unused_mut)]
+    let mut cli = Cli::from_args();
+
+    #[cfg(feature = "full")]
+    if cli.subcommand.is_none() {
+        // The same arguments are parsed successfully above.
+        if let Ok(matches) = <Cli as structopt::StructOpt>::clap().get_matches_safe() {
+            cli.run.capture_matches(matches);
+        }
+        set_default_ports(&mut cli)?;
+    }
+
+    #[cfg(feature = "full")]
+    set_family_base_path(&mut cli)?;
+
+    #[cfg(feature = "full")]
+    if cli.robonomics_debug {
+        if let Some(params) = database_shared_params(&mut cli) {
+            params.log.push(robonomics_debug_filter());
+        }
+    }
 
     match &cli.subcommand {
         #[cfg(not(feature = "full"))]
         None => Ok(()),
         #[cfg(feature = "full")]
         None => {
-            let runner = cli.create_runner(&*cli.run)?;
+            let mut runner = cli.create_runner(&*cli.run)?;
+            check_runtime_family(&runner.config().chain_spec)?;
+            #[cfg(feature = "parachain")]
+            log_startup_banner(&runner.config().chain_spec, cli.run.parachain_id);
+            #[cfg(not(feature = "parachain"))]
+            log_startup_banner(&runner.config().chain_spec, None);
+            crate::set_storage_defaults(
+                runner.config_mut(),
+                cli.run.is_pruning_given(),
+                cli.run.is_state_cache_size_given(),
+            );
+            crate::set_peers_defaults(
+                runner.config_mut(),
+                cli.run.is_in_peers_given(),
+                cli.run.is_out_peers_given(),
+                cli.run.rpc_only,
+            );
+            merge_boot_nodes(runner.config_mut());
+            if let Some(protocol_id) = &cli.run.protocol_id {
+                crate::chain_spec::override_protocol_id(
+                    &mut runner.config_mut().chain_spec,
+                    protocol_id,
+                )?;
+            }
+            log::info!(
+                target: "robonomics::service",
+                "Network protocol id: {}",
+                runner.config().protocol_id().as_ref(),
+            );
+            if let Some(url) = &cli.run.remote_signer_url {
+                runner.config_mut().keystore_remote = Some(url.clone());
+            }
+            let _pinned_runtime = match &cli.run.pin_runtime {
+                Some(wasm) => Some(pin_runtime(runner.config_mut(), wasm)?),
+                None => None,
+            };
+            log_wasm_overrides(runner.config())?;
+            if !cli.run.base.no_telemetry {
+                crate::add_telemetry_endpoints(runner.config_mut(), &cli.run.telemetry_endpoint)?;
+            }
+
             match runner.config().chain_spec.family() {
                 RobonomicsFamily::Development => runner.run_node_until_exit(|config| async move {
+                    if cli.run.grandpa_observer && config.role.is_authority() {
+                        return Err("GRANDPA observer can't be used by validator node".into());
+                    }
+
                     match config.role {
                         sc_cli::Role::Light => robonomics::new_light(config).map(|r| r.0),
-                        _ => robonomics::new_full(config),
+                        _ => robonomics::new_full(config, cli.run.full_node_options()),
                     }
                 }),
 
                 #[cfg(feature = "parachain")]
                 RobonomicsFamily::Parachain => runner.run_node_until_exit(|config| async move {
-                    if matches!(config.role, sc_cli::Role::Light) {
-                        return Err("Light client not supporter!".into());
+                    if cli.run.sealing.is_some() {
+                        return Err("Sealing mode is available for development chain only".into());
                     }
 
                     if cli.run.validator && cli.run.collator_eth_account.is_none() {
                         return Err("For validating set --collator-eth-account option".into());
                     }
 
+                    // Embedded relay chain node arguments: config file ones come first.
+                    let mut relaychain_args = match &cli.run.relaychain_config {
+                        Some(path) => parachain::cli::read_relaychain_config(path)?,
+                        None => Vec::new(),
+                    };
+                    relaychain_args.extend(cli.relaychain_args.iter().cloned());
+
+                    let mut config = config;
+                    crate::set_pool_limits(
+                        &mut config,
+                        cli.run.pool_limit(),
+                        cli.run.pool_kbytes(),
+                    );
+
+                    // Embedded relay chain node follows parachain `--no-telemetry`.
+                    if cli.run.base.no_telemetry
+                        && !relaychain_args.iter().any(|arg| arg == "--no-telemetry")
+                    {
+                        relaychain_args.push("--no-telemetry".into());
+                    }
+
                     parachain::command::run(
                         config,
-                        &cli.relaychain_args,
+                        &relaychain_args,
                         cli.run.parachain_id,
                         cli.run.collator_eth_account,
                     )
@@ -132,15 +527,100 @@ pub fn run() -> sc_cli::Result<()> {
         #[cfg(feature = "full")]
         Some(Subcommand::BuildSpec(cmd)) => {
             let runner = cli.create_runner(cmd)?;
-            runner.sync_run(|config| cmd.run(config.chain_spec, config.network))
+            runner.sync_run(|mut config| {
+                // Pinned epoch duration is written into genesis storage of the built spec once.
+                if let RobonomicsFamily::Development = config.chain_spec.family() {
+                    pin_babe_epoch_duration(&mut config.chain_spec)
+                        .map_err(sc_cli::Error::Input)?;
+                }
+                cmd.run(config.chain_spec, config.network)
+            })
         }
         #[cfg(feature = "full")]
         Some(Subcommand::PurgeChain(cmd)) => {
             let runner = cli.create_runner(cmd)?;
             runner.sync_run(|config| cmd.run(config.database))
         }
+        #[cfg(feature = "full")]
+        Some(Subcommand::CheckBlock(cmd)) => {
+            async_run_partial!(cli, cmd, |parts, config| {
+                cmd.run(parts.client, parts.import_queue)
+            })
+        }
+        #[cfg(feature = "full")]
+        Some(Subcommand::ExportBlocks(cmd)) => {
+            async_run_partial!(cli, cmd, |parts, config| {
+                cmd.run(parts.client, config.database)
+            })
+        }
+        #[cfg(feature = "full")]
+        Some(Subcommand::ExportState(cmd)) => {
+            async_run_partial!(cli, cmd, |parts, config| {
+                cmd.run(parts.client, config.chain_spec)
+            })
+        }
+        #[cfg(feature = "full")]
+        Some(Subcommand::ImportBlocks(cmd)) => {
+            async_run_partial!(cli, cmd, |parts, config| {
+                cmd.run(parts.client, parts.import_queue)
+            })
+        }
+        #[cfg(feature = "full")]
+        Some(Subcommand::Revert(cmd)) => {
+            async_run_partial!(cli, cmd, |parts, config| {
+                cmd.run(parts.client, parts.backend)
+            })
+        }
+        #[cfg(feature = "full")]
+        Some(Subcommand::ExportBonus(cmd)) => {
+            async_run_partial!(cli, cmd, |parts, config| { cmd.run(parts.client) })
+        }
+        #[cfg(feature = "full")]
+        Some(Subcommand::VerifyBlocks(cmd)) => {
+            async_run_partial!(cli, cmd, |parts, config| { cmd.run(parts.client) })
+        }
+        #[cfg(feature = "full")]
+        Some(Subcommand::PurgeForks(cmd)) => {
+            async_run_partial!(cli, cmd, |parts, config| {
+                cmd.run(
+                    parts.backend,
+                    config.database.path().map(std::path::Path::to_path_buf),
+                )
+            })
+        }
+        #[cfg(feature = "full")]
+        Some(Subcommand::DecodeExtrinsic(cmd)) => cmd.run(),
+        #[cfg(feature = "full")]
+        Some(Subcommand::DeriveAccount(cmd)) => cmd.run(),
+        #[cfg(feature = "full")]
+        Some(Subcommand::GenesisHash(cmd)) => cmd.run(cli.load_chain_spec(&cmd.chain)?),
+        Some(Subcommand::MetricsSnapshot(cmd)) => cmd.run(),
+        Some(Subcommand::WaitSynced(cmd)) => cmd.run(),
+        #[cfg(feature = "full")]
+        Some(Subcommand::GenerateSessionKeys(cmd)) => {
+            let runner = cli.create_runner(cmd)?;
+            runner.sync_run(|config| cmd.run(config.keystore))
+        }
+        #[cfg(feature = "full")]
+        Some(Subcommand::RotateNodeKey(cmd)) => {
+            let runner = cli.create_runner(cmd)?;
+            runner.sync_run(|config| cmd.run(config.network.net_config_path))
+        }
         #[cfg(feature = "robonomics-cli")]
-        Some(Subcommand::Io(subcommand)) => subcommand.run().map_err(|e| e.to_string().into()),
+        Some(Subcommand::Io(subcommand)) => {
+            init_robonomics_debug_logger(&cli);
+            subcommand.run().map_err(|e| e.to_string().into())
+        }
+        #[cfg(feature = "robonomics-cli")]
+        Some(Subcommand::Soak(subcommand)) => {
+            init_robonomics_debug_logger(&cli);
+            subcommand.run().map_err(|e| e.to_string().into())
+        }
+        #[cfg(feature = "robonomics-cli")]
+        Some(Subcommand::Sudo(subcommand)) => {
+            init_robonomics_debug_logger(&cli);
+            subcommand.run().map_err(|e| e.to_string().into())
+        }
         #[cfg(feature = "frame-benchmarking-cli")]
         Some(Subcommand::Benchmark(subcommand)) => {
             let runner = cli.create_runner(subcommand)?;
@@ -151,6 +631,14 @@ pub fn run() -> sc_cli::Result<()> {
                 _ => Err("Unknown chain")?,
             }
         }
+        #[cfg(feature = "frame-benchmarking-cli")]
+        Some(Subcommand::BenchmarkPallet(cmd)) => {
+            let subcommand = cmd.benchmark_cmd()?;
+            let runner = cli.create_runner(&subcommand)?;
+            runner.sync_run(|config| {
+                subcommand.run::<robonomics_primitives::Block, robonomics::Executor>(config)
+            })
+        }
         #[cfg(feature = "parachain")]
         Some(Subcommand::ExportGenesisState(params)) => {
             use codec::Encode;
@@ -166,6 +654,7 @@ pub fn run() -> sc_cli::Result<()> {
                 parachain::generate_genesis_block(&parachain::load_spec(
                     &params.chain.clone().unwrap_or_default(),
                     params.parachain_id.into(),
+                    None,
                 )?)?;
             let raw_header = block.header().encode();
             let output_buf = if params.raw {
@@ -209,5 +698,90 @@ pub fn run() -> sc_cli::Result<()> {
 
             Ok(())
         }
+        #[cfg(feature = "parachain")]
+        Some(Subcommand::BuildParachainSpec(params)) => {
+            use std::io::Write;
+
+            let snapshot = params.snapshot()?;
+            let balances = params.genesis_balances(snapshot.as_ref())?;
+            // Staking bonus is carried over from snapshot, otherwise it equals to balances.
+            let bonus = snapshot.map_or_else(|| balances.clone(), |s| s.bonus);
+            let chain_spec = parachain::chain_spec::custom_parachain_config(
+                &params.name,
+                params.para_id.into(),
+                &params.relay_chain,
+                params.sudo_key()?,
+                balances,
+                bonus,
+                params.genesis_council(),
+                params.properties(),
+            )?;
+            let json = sc_service::chain_ops::build_spec(&chain_spec, true)?;
+            std::io::stdout().write_all(json.as_bytes())?;
+
+            Ok(())
+        }
+        #[cfg(feature = "parachain")]
+        Some(Subcommand::CheckSpec(params)) => {
+            parachain::chain_spec::check_chain_spec(params.path.clone(), params.strict)?;
+            println!("{}: OK", params.path.display());
+            Ok(())
+        }
+        #[cfg(feature = "parachain")]
+        Some(Subcommand::DiffSpec(params)) => {
+            let diff = parachain::chain_spec::diff_chain_specs(params.a.clone(), params.b.clone())?;
+            if diff.is_empty() {
+                println!("Genesis is equivalent");
+            }
+            for line in diff {
+                println!("{}", line);
+            }
+            Ok(())
+        }
+    }
+}
+
+#[cfg(all(test, feature = "full"))]
+mod tests {
+    use super::*;
+    use sc_service::config::MultiaddrWithPeerId;
+
+    const ALICE: &str = "12D3KooWEyoppNCUx8Yx66oV9fJnriXwCcXwDDUA2kj6vnc6iDEp";
+    const BOB: &str = "12D3KooWHdiAxVd8uMQR1hGWXccidmfCwLqcMpGwR6QcTP6QRMuD";
+
+    fn address(port: u16, peer: &str) -> MultiaddrWithPeerId {
+        format!("/ip4/127.0.0.1/tcp/{}/p2p/{}", port, peer)
+            .parse()
+            .unwrap()
+    }
+
+    #[test]
+    fn merge_addresses_keeps_spec_first() {
+        let spec = vec![address(30333, ALICE)];
+        let given = vec![address(30334, BOB)];
+        assert_eq!(
+            merge_addresses(&spec, given),
+            vec![address(30333, ALICE), address(30334, BOB)]
+        );
+    }
+
+    #[test]
+    fn merge_addresses_drops_duplicates() {
+        let spec = vec![address(30333, ALICE), address(30333, ALICE)];
+        let given = vec![
+            address(30333, ALICE),
+            address(30334, BOB),
+            address(30334, BOB),
+        ];
+        assert_eq!(
+            merge_addresses(&spec, given),
+            vec![address(30333, ALICE), address(30334, BOB)]
+        );
+    }
+
+    #[test]
+    fn merge_addresses_keeps_other_addresses_of_peer() {
+        let given = vec![address(30333, ALICE), address(30335, ALICE)];
+        assert_eq!(merge_addresses(&[], given.clone()), given);
     }
 }
@@ -0,0 +1,61 @@
+///////////////////////////////////////////////////////////////////////////////
+//
+//  Copyright 2018-2021 Robonomics Network <research@robonomics.network>
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+//
+///////////////////////////////////////////////////////////////////////////////
+//! Robonomics node CLI errors.
+
+/// Robonomics CLI errors.
+#[derive(Debug, derive_more::Display)]
+pub enum RobonomicsCliError {
+    /// Chain with given id isn't known for this build.
+    #[display(fmt = "Unknown chain: {}", _0)]
+    UnknownChain(String),
+    /// Light client isn't supported for the chain.
+    #[display(fmt = "Light client isn't supported for {} chain", _0)]
+    LightUnsupported(String),
+    /// Chain spec can't be loaded or parsed.
+    #[display(fmt = "Unable to load chain spec: {}", _0)]
+    SpecParse(String),
+    /// Native runtime doesn't match the chain family.
+    #[display(
+        fmt = "Chain family {} doesn't match native runtime {}",
+        family,
+        spec_name
+    )]
+    RuntimeMismatch { family: String, spec_name: String },
+    /// Wasm runtime overrides directory can't be read.
+    #[display(fmt = "Unable to read wasm runtime overrides: {}", _0)]
+    WasmOverrides(String),
+    /// Pinned wasm runtime can't be used.
+    #[display(fmt = "Unable to pin wasm runtime: {}", _0)]
+    PinRuntime(String),
+}
+
+impl std::error::Error for RobonomicsCliError {}
+
+/// `SubstrateCli` methods expect string errors.
+impl From<RobonomicsCliError> for String {
+    fn from(e: RobonomicsCliError) -> Self {
+        e.to_string()
+    }
+}
+
+#[cfg(feature = "sc-cli")]
+impl From<RobonomicsCliError> for sc_cli::Error {
+    fn from(e: RobonomicsCliError) -> Self {
+        sc_cli::Error::Input(e.to_string())
+    }
+}
@@ -0,0 +1,130 @@
+///////////////////////////////////////////////////////////////////////////////
+//
+//  Copyright 2018-2021 Robonomics Network <research@robonomics.network>
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+//
+///////////////////////////////////////////////////////////////////////////////
+//! Streaming of imported block headers into Unix domain socket.
+
+use futures::StreamExt;
+use robonomics_primitives::Block;
+use sc_client_api::BlockchainEvents;
+use sp_runtime::traits::Header;
+use std::{
+    io::{ErrorKind, Write},
+    os::unix::net::{UnixListener, UnixStream},
+    path::PathBuf,
+    sync::Arc,
+};
+
+/// Accept all pending connections of non-blocking listener.
+fn accept_readers(listener: &UnixListener, readers: &mut Vec<UnixStream>) {
+    loop {
+        match listener.accept() {
+            Ok((stream, _)) => match stream.set_nonblocking(true) {
+                Ok(()) => readers.push(stream),
+                Err(e) => log::warn!(
+                    target: "robonomics::service",
+                    "Unable to setup import notification reader: {}",
+                    e
+                ),
+            },
+            Err(e) if e.kind() == ErrorKind::WouldBlock => break,
+            Err(e) => {
+                log::warn!(
+                    target: "robonomics::service",
+                    "Unable to accept import notification reader: {}",
+                    e
+                );
+                break;
+            }
+        }
+    }
+}
+
+/// Stream newline delimited JSON of imported block headers to readers of Unix socket.
+///
+/// Notice: Readers connected in between blocks receive headers starting from the next
+/// imported one, readers not keeping up with block import are disconnected.
+pub async fn serve_imports<C>(client: Arc<C>, path: PathBuf)
+where
+    C: BlockchainEvents<Block>,
+{
+    // Socket file left by previous run prevents binding.
+    if path.exists() {
+        if let Err(e) = std::fs::remove_file(&path) {
+            log::error!(
+                target: "robonomics::service",
+                "Unable to remove stale socket {}: {}",
+                path.display(),
+                e
+            );
+            return;
+        }
+    }
+    let listener = match UnixListener::bind(&path).and_then(|l| {
+        l.set_nonblocking(true)?;
+        Ok(l)
+    }) {
+        Ok(listener) => listener,
+        Err(e) => {
+            log::error!(
+                target: "robonomics::service",
+                "Unable to listen import notification socket {}: {}",
+                path.display(),
+                e
+            );
+            return;
+        }
+    };
+    log::info!(
+        target: "robonomics::service",
+        "Streaming imported blocks into {}",
+        path.display()
+    );
+
+    let mut readers = Vec::new();
+    let mut notifications = client.import_notification_stream();
+    while let Some(notification) = notifications.next().await {
+        accept_readers(&listener, &mut readers);
+        if readers.is_empty() {
+            continue;
+        }
+
+        let header = &notification.header;
+        let mut line = serde_json::json!({
+            "number": header.number(),
+            "hash": format!("{:?}", notification.hash),
+            "parentHash": format!("{:?}", header.parent_hash()),
+            "stateRoot": format!("{:?}", header.state_root()),
+            "extrinsicsRoot": format!("{:?}", header.extrinsics_root()),
+            "origin": format!("{:?}", notification.origin),
+            "isNewBest": notification.is_new_best,
+        })
+        .to_string();
+        line.push('\n');
+
+        readers.retain(|mut reader| match reader.write_all(line.as_bytes()) {
+            Ok(()) => true,
+            Err(e) => {
+                log::debug!(
+                    target: "robonomics::service",
+                    "Import notification reader disconnected: {}",
+                    e
+                );
+                false
+            }
+        });
+    }
+}
@@ -0,0 +1,145 @@
+///////////////////////////////////////////////////////////////////////////////
+//
+//  Copyright 2018-2021 Robonomics Network <research@robonomics.network>
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+//
+///////////////////////////////////////////////////////////////////////////////
+//! IPFS pinning of finalized datalog records.
+
+use base58::ToBase58;
+use codec::{Decode, Encode};
+use futures::StreamExt;
+use ipfs_api::{IpfsClient, TryFromUri};
+use local_runtime::{Call, UncheckedExtrinsic};
+use node_rpc::finalized::finalized_blocks;
+use robonomics_primitives::{Block, BlockNumber};
+use sc_client_api::{BlockBackend, BlockchainEvents};
+use sp_blockchain::HeaderBackend;
+use sp_runtime::generic::BlockId;
+use std::sync::Arc;
+
+/// Extract IPFS CID from datalog record.
+///
+/// Record could keep CID as a string or raw SHA2-256 multihash bytes.
+pub fn record_cid(record: &[u8]) -> Option<String> {
+    // SHA2-256 multihash: hash function code, digest length and digest itself.
+    if record.len() == 34 && record[0] == 0x12 && record[1] == 0x20 {
+        return Some(record.to_base58());
+    }
+
+    let cid = std::str::from_utf8(record).ok()?.trim();
+    let is_v0 = cid.len() == 46 && cid.starts_with("Qm");
+    // CIDv1 in base32 or base58btc multibase encoding.
+    let is_v1 = cid.len() > 46
+        && (cid.starts_with('b') || cid.starts_with('z'))
+        && cid.chars().all(|c| c.is_ascii_alphanumeric());
+    if is_v0 || is_v1 {
+        Some(cid.into())
+    } else {
+        None
+    }
+}
+
+/// IPFS CIDs of datalog records in the block with given number.
+fn block_cids<C>(client: &C, number: BlockNumber) -> Vec<String>
+where
+    C: BlockBackend<Block> + HeaderBackend<Block>,
+{
+    let body = match client.block_body(&BlockId::Number(number)) {
+        Ok(Some(body)) => body,
+        Ok(None) => return Vec::new(),
+        Err(e) => {
+            log::warn!(target: "robonomics::service", "Unable to read block #{}: {}", number, e);
+            return Vec::new();
+        }
+    };
+
+    body.iter()
+        .filter_map(|xt| UncheckedExtrinsic::decode(&mut &xt.encode()[..]).ok())
+        .filter_map(|xt| match xt.function {
+            Call::Datalog(pallet_robonomics_datalog::Call::record(record)) => record_cid(&record),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Pin IPFS content of finalized datalog records on IPFS node with given API URL.
+pub async fn pin_datalog<C>(client: Arc<C>, url: String)
+where
+    C: BlockchainEvents<Block> + BlockBackend<Block> + HeaderBackend<Block>,
+{
+    let ipfs = match IpfsClient::from_str(&url) {
+        Ok(ipfs) => ipfs,
+        Err(e) => {
+            log::error!(target: "robonomics::service", "Invalid IPFS API URL {}: {}", url, e);
+            return;
+        }
+    };
+
+    let mut blocks = Box::pin(finalized_blocks(client.clone()));
+    while let Some((n, _)) = blocks.next().await {
+        for cid in block_cids(&*client, n) {
+            match ipfs.pin_add(&cid, true).await {
+                Ok(_) => log::debug!(
+                    target: "robonomics::service",
+                    "Pinned {} from block #{}",
+                    cid,
+                    n
+                ),
+                Err(e) => log::warn!(
+                    target: "robonomics::service",
+                    "Unable to pin {}: {}",
+                    cid,
+                    e
+                ),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use base58::FromBase58;
+
+    const CID_V0: &str = "QmYwAPJzv5CZsnA625s3Xf2nemtYgPpHdWEz79ojWnPbdG";
+    const CID_V1: &str = "bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi";
+
+    #[test]
+    fn record_cid_string() {
+        assert_eq!(record_cid(CID_V0.as_bytes()), Some(CID_V0.into()));
+        assert_eq!(record_cid(CID_V1.as_bytes()), Some(CID_V1.into()));
+        let padded = format!(" {}\n", CID_V0);
+        assert_eq!(record_cid(padded.as_bytes()), Some(CID_V0.into()));
+    }
+
+    #[test]
+    fn record_cid_multihash() {
+        let multihash = CID_V0.from_base58().unwrap();
+        assert_eq!(multihash.len(), 34);
+        assert_eq!(record_cid(&multihash), Some(CID_V0.into()));
+
+        let mut other_hash = multihash.clone();
+        other_hash[0] = 0x13;
+        assert_eq!(record_cid(&other_hash), None);
+    }
+
+    #[test]
+    fn record_cid_invalid() {
+        assert_eq!(record_cid(b"temperature=21.5"), None);
+        assert_eq!(record_cid(&CID_V0.as_bytes()[..45]), None);
+        assert_eq!(record_cid(format!("{}-", CID_V1).as_bytes()), None);
+        assert_eq!(record_cid(&[0xff, 0xfe]), None);
+    }
+}
@@ -22,10 +22,39 @@
 #[cfg(feature = "full")]
 pub mod chain_spec;
 
+pub mod error;
+
 #[macro_use]
 #[cfg(feature = "full")]
 pub mod service;
 
+#[cfg(feature = "full")]
+pub mod proposer;
+
+#[cfg(feature = "full")]
+pub mod ipfs;
+
+#[cfg(feature = "full")]
+pub mod nats;
+
+#[cfg(feature = "full")]
+pub mod ntp;
+
+#[cfg(all(feature = "full", unix))]
+pub mod import_notify;
+
+#[cfg(feature = "full")]
+pub mod authority_set;
+
+#[cfg(feature = "full")]
+pub mod remote_signer;
+
+#[cfg(feature = "full")]
+pub use service::{
+    add_telemetry_endpoints, robonomics, set_peers_defaults, set_pool_limits, set_storage_defaults,
+    FullNodeOptions, Sealing,
+};
+
 #[macro_use]
 #[cfg(feature = "parachain")]
 pub mod parachain;
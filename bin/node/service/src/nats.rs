@@ -0,0 +1,228 @@
+///////////////////////////////////////////////////////////////////////////////
+//
+//  Copyright 2018-2021 Robonomics Network <research@robonomics.network>
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+//
+///////////////////////////////////////////////////////////////////////////////
+//! Publishing of finalized blocks into NATS subject.
+
+use codec::Encode;
+use futures::StreamExt;
+use node_rpc::finalized::finalized_blocks;
+use robonomics_primitives::{Block, BlockNumber};
+use sc_client_api::{BlockBackend, BlockchainEvents};
+use sp_blockchain::HeaderBackend;
+use sp_runtime::{generic::BlockId, traits::Header};
+use std::{
+    collections::VecDeque,
+    io::{BufRead, BufReader, ErrorKind, Read, Write},
+    net::TcpStream,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+/// Maximum number of blocks buffered while broker is unreachable, the oldest are dropped.
+const MAX_BUFFERED_BLOCKS: usize = 4096;
+
+/// Maximum delay between broker reconnection attempts.
+const MAX_RECONNECT_BACKOFF: Duration = Duration::from_secs(60);
+
+/// Broker connection and handshake timeout.
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Default NATS port.
+const NATS_PORT: u16 = 4222;
+
+/// Plain text NATS protocol connection, enough to publish messages.
+struct Connection {
+    stream: TcpStream,
+}
+
+impl Connection {
+    /// Connect to `nats://host[:port]` and make protocol handshake.
+    fn open(url: &str) -> std::io::Result<Self> {
+        let invalid = |e: &str| std::io::Error::new(ErrorKind::InvalidInput, e.to_string());
+        let host = url
+            .strip_prefix("nats://")
+            .ok_or_else(|| invalid("Only nats:// URLs are supported"))?
+            .trim_end_matches('/');
+        let address = if host.contains(':') {
+            host.to_string()
+        } else {
+            format!("{}:{}", host, NATS_PORT)
+        };
+        let address = std::net::ToSocketAddrs::to_socket_addrs(&address)?
+            .next()
+            .ok_or_else(|| invalid("Unable to resolve broker address"))?;
+
+        let stream = TcpStream::connect_timeout(&address, CONNECT_TIMEOUT)?;
+        stream.set_read_timeout(Some(CONNECT_TIMEOUT))?;
+        stream.set_write_timeout(Some(CONNECT_TIMEOUT))?;
+
+        // Server greets with INFO, handshake is confirmed by PONG to our PING.
+        let mut reader = BufReader::new(stream.try_clone()?);
+        let mut line = String::new();
+        reader.read_line(&mut line)?;
+        if !line.starts_with("INFO") {
+            return Err(invalid("Unexpected NATS server greeting"));
+        }
+        (&stream).write_all(
+            b"CONNECT {\"verbose\":false,\"pedantic\":false,\"name\":\"robonomics\"}\r\nPING\r\n",
+        )?;
+        line.clear();
+        reader.read_line(&mut line)?;
+        if !line.starts_with("PONG") {
+            return Err(invalid(line.trim()));
+        }
+
+        Ok(Self { stream })
+    }
+
+    /// Publish message into subject, server pings received in between are answered.
+    fn publish(&mut self, subject: &str, payload: &[u8]) -> std::io::Result<()> {
+        self.answer_pings()?;
+        write!(self.stream, "PUB {} {}\r\n", subject, payload.len())?;
+        self.stream.write_all(payload)?;
+        self.stream.write_all(b"\r\n")
+    }
+
+    /// Read pending server messages without blocking: reply PINGs and fail on errors.
+    fn answer_pings(&mut self) -> std::io::Result<()> {
+        self.stream.set_nonblocking(true)?;
+        let mut pending = Vec::new();
+        let mut buf = [0u8; 1024];
+        let result = loop {
+            match self.stream.read(&mut buf) {
+                Ok(0) => break Err(ErrorKind::ConnectionAborted.into()),
+                Ok(n) => pending.extend_from_slice(&buf[..n]),
+                Err(e) if e.kind() == ErrorKind::WouldBlock => break Ok(()),
+                Err(e) => break Err(e),
+            }
+        };
+        self.stream.set_nonblocking(false)?;
+        result?;
+
+        for line in String::from_utf8_lossy(&pending).lines() {
+            if line.starts_with("PING") {
+                self.stream.write_all(b"PONG\r\n")?;
+            } else if line.starts_with("-ERR") {
+                return Err(std::io::Error::new(ErrorKind::Other, line.to_string()));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Finalized block header and extrinsics as JSON.
+fn block_json<C>(client: &C, number: BlockNumber) -> Option<Vec<u8>>
+where
+    C: BlockBackend<Block> + HeaderBackend<Block>,
+{
+    let id = BlockId::Number(number);
+    let header = client.header(id).ok()??;
+    let body = client.block_body(&id).ok()??;
+    let json = serde_json::json!({
+        "number": number,
+        "hash": format!("{:?}", header.hash()),
+        "parentHash": format!("{:?}", header.parent_hash()),
+        "stateRoot": format!("{:?}", header.state_root()),
+        "extrinsicsRoot": format!("{:?}", header.extrinsics_root()),
+        "extrinsics": body
+            .iter()
+            .map(|xt| format!("0x{}", hex::encode(xt.encode())))
+            .collect::<Vec<_>>(),
+    });
+    Some(json.to_string().into_bytes())
+}
+
+/// Publish finalized blocks as JSON into NATS subject.
+///
+/// Notice: Blocks are buffered while broker is unreachable, reconnection is tried
+/// on finality notifications with exponential backoff.
+pub async fn publish_finalized<C>(client: Arc<C>, url: String, subject: String)
+where
+    C: BlockchainEvents<Block> + BlockBackend<Block> + HeaderBackend<Block>,
+{
+    let mut connection = None;
+    let mut buffer = VecDeque::new();
+    let mut backoff = Duration::from_secs(1);
+    let mut next_attempt = Instant::now();
+
+    let mut blocks = Box::pin(finalized_blocks(client.clone()));
+    while let Some((n, _)) = blocks.next().await {
+        match block_json(&*client, n) {
+            Some(json) => buffer.push_back(json),
+            None => log::warn!(
+                target: "robonomics::service",
+                "Unable to read finalized block #{}",
+                n
+            ),
+        }
+        if buffer.len() > MAX_BUFFERED_BLOCKS {
+            buffer.pop_front();
+            log::warn!(
+                target: "robonomics::service",
+                "NATS buffer overflow, the oldest finalized block is dropped"
+            );
+        }
+
+        while let Some(json) = buffer.front() {
+            if connection.is_none() {
+                if Instant::now() < next_attempt {
+                    break;
+                }
+                match Connection::open(&url) {
+                    Ok(c) => {
+                        log::info!(target: "robonomics::service", "Connected to NATS {}", url);
+                        connection = Some(c);
+                        backoff = Duration::from_secs(1);
+                    }
+                    Err(e) => {
+                        log::warn!(
+                            target: "robonomics::service",
+                            "Unable to connect to NATS {}: {}, retry in {:?}",
+                            url,
+                            e,
+                            backoff
+                        );
+                        next_attempt = Instant::now() + backoff;
+                        backoff = (backoff * 2).min(MAX_RECONNECT_BACKOFF);
+                        break;
+                    }
+                }
+            }
+
+            let published = connection
+                .as_mut()
+                .expect("connection is opened above; qed")
+                .publish(&subject, json);
+            match published {
+                Ok(()) => {
+                    buffer.pop_front();
+                }
+                Err(e) => {
+                    log::warn!(
+                        target: "robonomics::service",
+                        "Unable to publish into NATS: {}, {} blocks buffered",
+                        e,
+                        buffer.len()
+                    );
+                    connection = None;
+                    next_attempt = Instant::now() + backoff;
+                    break;
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,192 @@
+///////////////////////////////////////////////////////////////////////////////
+//
+//  Copyright 2018-2021 Robonomics Network <research@robonomics.network>
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+//
+///////////////////////////////////////////////////////////////////////////////
+//! Local clock correction by NTP server offset.
+
+use std::{
+    net::UdpSocket,
+    sync::{
+        atomic::{AtomicBool, AtomicI64, Ordering},
+        Arc,
+    },
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+/// Seconds between NTP (1900) and Unix (1970) epochs.
+const NTP_UNIX_OFFSET: u64 = 2_208_988_800;
+
+/// Default NTP port.
+const NTP_PORT: u16 = 123;
+
+/// NTP request and response timeout.
+const QUERY_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Local clock corrected by offset periodically synced with NTP server.
+#[derive(Clone)]
+pub struct NtpClock {
+    offset_ms: Arc<AtomicI64>,
+    synced: Arc<AtomicBool>,
+    max_drift: Duration,
+}
+
+impl NtpClock {
+    /// Local clock allowed to drift from NTP time by `max_drift`, see `sync`.
+    ///
+    /// Notice: Local clock is used as is until the first successful sync.
+    pub fn new(max_drift: Duration) -> Self {
+        Self {
+            offset_ms: Arc::new(AtomicI64::new(0)),
+            synced: Arc::new(AtomicBool::new(false)),
+            max_drift,
+        }
+    }
+
+    /// Sync clock offset with given `host[:port]` server every `interval`.
+    ///
+    /// Notice: Server is queried synchronously, so the future should be spawned as blocking task.
+    pub async fn sync(self, server: String, interval: Duration) {
+        loop {
+            match query_offset(&server) {
+                Ok(offset) => {
+                    self.offset_ms.store(offset, Ordering::Relaxed);
+                    self.synced.store(true, Ordering::Relaxed);
+                    log::debug!(
+                        target: "robonomics::service",
+                        "Clock offset by NTP server {}: {} ms",
+                        server,
+                        offset
+                    );
+                }
+                Err(e) => log::warn!(
+                    target: "robonomics::service",
+                    "Unable to sync clock with NTP server {}: {}",
+                    server,
+                    e
+                ),
+            }
+            futures_timer::Delay::new(interval).await;
+        }
+    }
+
+    /// Corrected current time.
+    pub fn now(&self) -> sp_timestamp::Timestamp {
+        let offset = self.offset_ms.load(Ordering::Relaxed);
+        sp_timestamp::Timestamp::new((unix_millis() as i64 + offset) as u64)
+    }
+
+    /// Timestamp inherent data provider using corrected time.
+    pub fn timestamp(&self) -> sp_timestamp::InherentDataProvider {
+        sp_timestamp::InherentDataProvider::new(self.now())
+    }
+
+    /// Check that local clock drift doesn't exceed allowed one, e.g. before authoring.
+    pub fn check_drift(&self) -> Result<(), String> {
+        let offset = self.offset_ms.load(Ordering::Relaxed);
+        if self.synced.load(Ordering::Relaxed) && offset.abs() as u128 > self.max_drift.as_millis()
+        {
+            return Err(format!(
+                "Local clock drifts {} ms from NTP time, more than allowed {:?}",
+                offset, self.max_drift
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Milliseconds since Unix epoch by local clock.
+fn unix_millis() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_millis() as u64)
+}
+
+/// Convert NTP timestamp into milliseconds since Unix epoch.
+fn ntp_to_millis(bytes: &[u8]) -> i64 {
+    let seconds = u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]) as u64;
+    let fraction = u32::from_be_bytes([bytes[4], bytes[5], bytes[6], bytes[7]]) as u64;
+    (seconds.saturating_sub(NTP_UNIX_OFFSET) * 1000 + ((fraction * 1000) >> 32)) as i64
+}
+
+/// Query SNTP server and return local clock offset in milliseconds.
+fn query_offset(server: &str) -> std::io::Result<i64> {
+    let address = if server.contains(':') {
+        server.to_string()
+    } else {
+        format!("{}:{}", server, NTP_PORT)
+    };
+    let socket = UdpSocket::bind("0.0.0.0:0")?;
+    socket.set_read_timeout(Some(QUERY_TIMEOUT))?;
+    socket.connect(address)?;
+
+    // Client request of protocol version 3, server echoes nothing but timestamps.
+    let mut request = [0u8; 48];
+    request[0] = 0x1b;
+    let sent = unix_millis() as i64;
+    socket.send(&request)?;
+    let mut response = [0u8; 48];
+    let len = socket.recv(&mut response)?;
+    let received = unix_millis() as i64;
+    if len < 48 || response[0] & 0x07 != 4 {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidData,
+            "Invalid NTP server response",
+        ));
+    }
+    // Leap indicator 3 is unsynchronized server, stratum 0 is kiss-o'-death message.
+    if response[0] >> 6 == 3 || response[1] == 0 || response[1] > 15 {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidData,
+            format!(
+                "NTP server isn't synchronized, leap indicator {} stratum {}",
+                response[0] >> 6,
+                response[1]
+            ),
+        ));
+    }
+
+    let server_received = ntp_to_millis(&response[32..40]);
+    let server_sent = ntp_to_millis(&response[40..48]);
+    Ok(((server_received - sent) + (server_sent - received)) / 2)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ntp_bytes(seconds: u32, fraction: u32) -> Vec<u8> {
+        let mut bytes = seconds.to_be_bytes().to_vec();
+        bytes.extend_from_slice(&fraction.to_be_bytes());
+        bytes
+    }
+
+    #[test]
+    fn ntp_to_millis_since_unix_epoch() {
+        let unix_epoch = NTP_UNIX_OFFSET as u32;
+        assert_eq!(ntp_to_millis(&ntp_bytes(unix_epoch, 0)), 0);
+        assert_eq!(ntp_to_millis(&ntp_bytes(unix_epoch + 1, 1 << 31)), 1500);
+        assert_eq!(ntp_to_millis(&ntp_bytes(unix_epoch + 2, u32::MAX)), 2999);
+    }
+
+    #[test]
+    fn ntp_to_millis_before_unix_epoch() {
+        assert_eq!(ntp_to_millis(&ntp_bytes(0, 0)), 0);
+        assert_eq!(
+            ntp_to_millis(&ntp_bytes(NTP_UNIX_OFFSET as u32 - 1, 1 << 31)),
+            500
+        );
+    }
+}
@@ -17,8 +17,10 @@
 ///////////////////////////////////////////////////////////////////////////////
 //! Robonomics Node as a parachain collator.
 
+use crate::error::RobonomicsCliError;
 use robonomics_primitives::Block;
-use sc_service::{Configuration, PartialComponents, TFullBackend, TFullClient};
+use sc_client_api::RemoteBackend;
+use sc_service::{Configuration, PartialComponents, TFullBackend, TFullClient, TaskManager};
 use sp_runtime::traits::BlakeTwo256;
 use sp_trie::PrefixedMemoryDB;
 use std::sync::Arc;
@@ -107,16 +109,117 @@ pub fn new_partial(
     Ok(params)
 }
 
+/// Create a new parachain light client service.
+///
+/// Light client doesn't connect to relay chain, it only follows parachain headers
+/// and serves light RPC handlers.
+pub fn new_light(config: Configuration) -> Result<TaskManager, sc_service::Error> {
+    let telemetry = config
+        .telemetry_endpoints
+        .clone()
+        .filter(|x| !x.is_empty())
+        .map(|endpoints| -> Result<_, sc_telemetry::Error> {
+            let worker = sc_telemetry::TelemetryWorker::new(16)?;
+            let telemetry = worker.handle().new_telemetry(endpoints);
+            Ok((worker, telemetry))
+        })
+        .transpose()?;
+
+    let (client, backend, keystore_container, mut task_manager, on_demand) =
+        sc_service::new_light_parts::<Block, RuntimeApi, Executor>(
+            &config,
+            telemetry.as_ref().map(|(_, telemetry)| telemetry.handle()),
+        )?;
+
+    let mut telemetry = telemetry.map(|(worker, telemetry)| {
+        task_manager.spawn_handle().spawn("telemetry", worker.run());
+        telemetry
+    });
+
+    let transaction_pool = Arc::new(sc_transaction_pool::BasicPool::new_light(
+        config.transaction_pool.clone(),
+        config.prometheus_registry(),
+        task_manager.spawn_handle(),
+        client.clone(),
+        on_demand.clone(),
+    ));
+
+    let import_queue = cumulus_client_consensus_relay_chain::import_queue(
+        client.clone(),
+        client.clone(),
+        |_, _| async {
+            let timestamp = sp_timestamp::InherentDataProvider::from_system_time();
+            Ok(timestamp)
+        },
+        &task_manager.spawn_essential_handle(),
+        config.prometheus_registry(),
+    )?;
+
+    let (network, system_rpc_tx, network_starter) =
+        sc_service::build_network(sc_service::BuildNetworkParams {
+            config: &config,
+            client: client.clone(),
+            transaction_pool: transaction_pool.clone(),
+            spawn_handle: task_manager.spawn_handle(),
+            import_queue,
+            on_demand: Some(on_demand.clone()),
+            block_announce_validator_builder: None,
+        })?;
+
+    let light_deps = node_rpc::LightDeps {
+        remote_blockchain: backend.remote_blockchain(),
+        fetcher: on_demand.clone(),
+        client: client.clone(),
+        pool: transaction_pool.clone(),
+        status: node_rpc::status::StatusDeps {
+            system_rpc_tx: system_rpc_tx.clone(),
+            family: crate::chain_spec::RobonomicsFamily::Parachain.to_string(),
+        },
+    };
+    let rpc_extensions = node_rpc::create_light(light_deps);
+
+    sc_service::spawn_tasks(sc_service::SpawnTasksParams {
+        on_demand: Some(on_demand),
+        remote_blockchain: Some(backend.remote_blockchain()),
+        rpc_extensions_builder: Box::new(sc_service::NoopRpcExtensionBuilder(rpc_extensions)),
+        client,
+        transaction_pool,
+        keystore: keystore_container.sync_keystore(),
+        config,
+        backend,
+        system_rpc_tx,
+        network,
+        task_manager: &mut task_manager,
+        telemetry: telemetry.as_mut(),
+    })?;
+
+    network_starter.start_network();
+    Ok(task_manager)
+}
+
 pub fn load_spec(
     id: &str,
     para_id: cumulus_primitives_core::ParaId,
-) -> Result<Box<dyn sc_service::ChainSpec>, String> {
-    match id {
-        "" => Ok(Box::new(chain_spec::get_chain_spec(para_id))),
-        path => Ok(Box::new(chain_spec::ChainSpec::from_json_file(
-            path.into(),
-        )?)),
+    telemetry: Option<(String, u8)>,
+) -> Result<Box<dyn sc_service::ChainSpec>, RobonomicsCliError> {
+    let chain_spec: Box<dyn sc_service::ChainSpec> = match id {
+        "" => Box::new(
+            chain_spec::get_chain_spec(para_id, telemetry)
+                .map_err(RobonomicsCliError::SpecParse)?,
+        ),
+        "polkadot-parachain" => Box::new(chain_spec::polkadot_parachain_config(para_id)),
+        path => Box::new(
+            chain_spec::ChainSpec::from_json_file(path.into())
+                .map_err(RobonomicsCliError::SpecParse)?,
+        ),
+    };
+
+    if let Some(extension) = chain_spec::Extensions::try_get(&chain_spec) {
+        chain_spec::check_relay_chain(&extension.relay_chain)
+            .map_err(RobonomicsCliError::SpecParse)?;
     }
+
+    Ok(chain_spec)
 }
 
 pub fn extract_genesis_wasm(
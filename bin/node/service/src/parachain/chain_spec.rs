@@ -18,17 +18,23 @@
 //! Chain specification and utils.
 
 use alpha_runtime::{
-    wasm_binary_unwrap, BalancesConfig, GenesisConfig, ParachainInfoConfig, StakingConfig,
+    wasm_binary, wasm_binary_unwrap, BalancesConfig, CouncilConfig, DesiredMembers,
+    ElectionsConfig, ExistentialDeposit, GenesisConfig, ParachainInfoConfig, StakingConfig,
     SudoConfig, SystemConfig,
 };
+use codec::{Decode, Encode};
 use cumulus_primitives_core::ParaId;
 use robonomics_primitives::{AccountId, Balance};
 use sc_chain_spec::ChainSpecExtension;
 use sc_service::ChainType;
 use serde::{Deserialize, Serialize};
-use sp_core::sr25519;
+use sp_core::{crypto::Ss58Codec, hashing::blake2_256, sr25519, Get};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    path::PathBuf,
+};
 
-use crate::chain_spec::get_account_id_from_seed;
+use crate::chain_spec::{get_account_id_from_seed, xrt_properties};
 
 /// Earth parachain ID
 const EARTH_ID: u32 = 1000;
@@ -61,35 +67,257 @@ impl Extensions {
     }
 }
 
+/// Relay chains supported by Robonomics parachain collator.
+pub const RELAY_CHAINS: &[&str] = &[
+    "polkadot",
+    "kusama",
+    "westend",
+    "westend-dev",
+    "rococo",
+    "rococo-local",
+    "rococo_local_testnet",
+];
+
+/// Check that relay chain is one of the supported.
+pub fn check_relay_chain(relay_chain: &str) -> Result<(), String> {
+    if RELAY_CHAINS.contains(&relay_chain) {
+        Ok(())
+    } else {
+        Err(format!(
+            "Unknown relay chain {}, expected one of: {}",
+            relay_chain,
+            RELAY_CHAINS.join(", ")
+        ))
+    }
+}
+
 /// Specialized `ChainSpec`.
 pub type ChainSpec = sc_service::GenericChainSpec<GenesisConfig, Extensions>;
 
-pub fn get_chain_spec(id: ParaId) -> ChainSpec {
+/// Load parachain spec and use given telemetry endpoint when it's set.
+pub fn get_chain_spec(id: ParaId, telemetry: Option<(String, u8)>) -> Result<ChainSpec, String> {
+    let chain_spec = included_chain_spec(id)?;
+    match telemetry {
+        Some((url, verbosity)) => with_telemetry(chain_spec, url, verbosity),
+        None => Ok(chain_spec),
+    }
+}
+
+/// Replace telemetry endpoints of JSON based chain spec.
+fn with_telemetry(chain_spec: ChainSpec, url: String, verbosity: u8) -> Result<ChainSpec, String> {
+    let mut json: serde_json::Value =
+        serde_json::from_str(&chain_spec.as_json(false)?).map_err(|e| e.to_string())?;
+    json["telemetryEndpoints"] = serde_json::json!([[url, verbosity]]);
+    ChainSpec::from_json_bytes(json.to_string().into_bytes())
+}
+
+/// Check genesis balances against runtime existential deposit.
+///
+/// Balances below it are reaped, so they're reported as warnings or error when `strict` is set.
+pub fn check_existential_deposit(
+    balances: &[(AccountId, Balance)],
+    strict: bool,
+) -> Result<(), String> {
+    let existential_deposit = ExistentialDeposit::get();
+    let dust: Vec<_> = balances
+        .iter()
+        .filter(|(_, balance)| *balance < existential_deposit)
+        .collect();
+
+    for (account, balance) in &dust {
+        eprintln!(
+            "Warning: genesis balance {} of {} is below existential deposit {}",
+            balance,
+            account.to_ss58check(),
+            existential_deposit
+        );
+    }
+
+    if strict && !dust.is_empty() {
+        return Err(format!(
+            "{} genesis balances are below existential deposit",
+            dust.len()
+        ));
+    }
+
+    Ok(())
+}
+
+/// Genesis runtime config of not raw chain spec.
+fn genesis_config(chain_spec: &ChainSpec) -> Result<GenesisConfig, String> {
+    let json: serde_json::Value =
+        serde_json::from_str(&chain_spec.as_json(false)?).map_err(|e| e.to_string())?;
+    let runtime = json
+        .pointer("/genesis/runtime")
+        .cloned()
+        .ok_or("Raw chain spec can't be checked, genesis runtime config expected")?;
+    serde_json::from_value(runtime).map_err(|e| format!("Invalid genesis config: {}", e))
+}
+
+/// Differences of account amounts, e.g. `balance 4GKL...: 100 -> 200`.
+fn diff_amounts(what: &str, a: &[(AccountId, Balance)], b: &[(AccountId, Balance)]) -> Vec<String> {
+    let a: BTreeMap<_, _> = a.iter().cloned().collect();
+    let b: BTreeMap<_, _> = b.iter().cloned().collect();
+    let amount = |v: Option<&Balance>| v.map_or_else(|| "none".to_string(), |v| v.to_string());
+    a.keys()
+        .chain(b.keys())
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .filter(|account| a.get(account) != b.get(account))
+        .map(|account| {
+            format!(
+                "{} {}: {} -> {}",
+                what,
+                account.to_ss58check(),
+                amount(a.get(account)),
+                amount(b.get(account)),
+            )
+        })
+        .collect()
+}
+
+/// Compare genesis of two parachain specs: balances, sudo key, staking bonus and parachain id.
+pub fn diff_chain_specs(a: PathBuf, b: PathBuf) -> Result<Vec<String>, String> {
+    let a = genesis_config(&ChainSpec::from_json_file(a)?)?;
+    let b = genesis_config(&ChainSpec::from_json_file(b)?)?;
+
+    let mut diff = diff_amounts(
+        "balance",
+        &a.pallet_balances.balances,
+        &b.pallet_balances.balances,
+    );
+    if a.pallet_sudo.key != b.pallet_sudo.key {
+        diff.push(format!(
+            "sudo: {} -> {}",
+            a.pallet_sudo.key.to_ss58check(),
+            b.pallet_sudo.key.to_ss58check()
+        ));
+    }
+    diff.extend(diff_amounts(
+        "bonus",
+        &a.pallet_robonomics_staking.bonus,
+        &b.pallet_robonomics_staking.bonus,
+    ));
+    let (a_id, b_id): (u32, u32) = (
+        a.parachain_info.parachain_id.into(),
+        b.parachain_info.parachain_id.into(),
+    );
+    if a_id != b_id {
+        diff.push(format!("para_id: {} -> {}", a_id, b_id));
+    }
+    Ok(diff)
+}
+
+/// Check parachain spec consistency: extensions, genesis balances and sudo key.
+pub fn check_chain_spec(path: PathBuf, strict: bool) -> Result<(), String> {
+    let chain_spec = ChainSpec::from_json_file(path)?;
+    let extensions = chain_spec.extensions();
+    check_relay_chain(&extensions.relay_chain)?;
+
+    let genesis = genesis_config(&chain_spec)?;
+
+    let para_id: u32 = genesis.parachain_info.parachain_id.into();
+    if para_id != extensions.para_id {
+        return Err(format!(
+            "Parachain id {} of extensions doesn't match genesis parachain id {}",
+            extensions.para_id, para_id
+        ));
+    }
+
+    let mut accounts = BTreeSet::new();
+    for (account, _) in &genesis.pallet_balances.balances {
+        if !accounts.insert(account) {
+            return Err(format!(
+                "Duplicate genesis balance of {}",
+                account.to_ss58check()
+            ));
+        }
+    }
+
+    check_existential_deposit(&genesis.pallet_balances.balances, strict)?;
+
+    if !accounts.contains(&genesis.pallet_sudo.key) {
+        return Err(format!(
+            "Sudo key {} has no genesis balance",
+            genesis.pallet_sudo.key.to_ss58check()
+        ));
+    }
+
+    Ok(())
+}
+
+/// Parachains which chain specs are included into the node binary.
+pub fn known_parachains() -> Vec<node_rpc::parachain::KnownParachain> {
+    let mut ids = vec![EARTH_ID, MARS_ID, VENUS_ID, URANUS_ID];
+    if cfg!(feature = "kusama-parachain") {
+        ids.push(KUSAMA_ID);
+    }
+    ids.into_iter()
+        .filter_map(|id| included_chain_spec(id.into()).ok())
+        .map(|spec| node_rpc::parachain::KnownParachain {
+            para_id: spec.extensions().para_id,
+            name: spec.name().into(),
+            relay_chain: spec.extensions().relay_chain.clone(),
+        })
+        .collect()
+}
+
+fn included_chain_spec(id: ParaId) -> Result<ChainSpec, String> {
     if id == ParaId::from(EARTH_ID) {
-        return earth_parachain_config();
+        return Ok(earth_parachain_config());
     }
 
     if id == ParaId::from(MARS_ID) {
-        return mars_parachain_config();
+        return Ok(mars_parachain_config());
     }
 
     if id == ParaId::from(VENUS_ID) {
-        return venus_parachain_config();
+        return Ok(venus_parachain_config());
     }
 
     if id == ParaId::from(URANUS_ID) {
-        return uranus_parachain_config();
+        return Ok(uranus_parachain_config());
     }
 
     #[cfg(feature = "kusama-parachain")]
     if id == ParaId::from(KUSAMA_ID) {
-        return kusama_parachain_config();
+        return Ok(kusama_parachain_config());
     }
 
     test_chain_spec(id)
 }
 
-fn test_chain_spec(id: ParaId) -> ChainSpec {
+/// Robonomics parachain on Polkadot relay chain.
+///
+/// Genesis uses development accounts, build production spec
+/// by `build-parachain-spec --relay-chain polkadot` instead.
+pub fn polkadot_parachain_config(id: ParaId) -> ChainSpec {
+    let sudo_key = get_account_id_from_seed::<sr25519::Public>("Alice");
+    ChainSpec::from_genesis(
+        "Robonomics Polkadot",
+        "robonomics_polkadot",
+        ChainType::Development,
+        move || {
+            mk_genesis(
+                vec![(sudo_key.clone(), 1_000_000_000_000u128)],
+                sudo_key.clone(),
+                wasm_binary_unwrap().to_vec(),
+                id,
+            )
+        },
+        vec![],
+        None,
+        Some(ROBONOMICS_PROTOCOL_ID),
+        Some(xrt_properties()),
+        Extensions {
+            relay_chain: "polkadot".into(),
+            para_id: id.into(),
+        },
+    )
+}
+
+fn test_chain_spec(id: ParaId) -> Result<ChainSpec, String> {
+    let wasm_binary = wasm_binary()?.to_vec();
     let balances = vec![
         get_account_id_from_seed::<sr25519::Public>("Alice"),
         get_account_id_from_seed::<sr25519::Public>("Bob"),
@@ -98,7 +326,7 @@ fn test_chain_spec(id: ParaId) -> ChainSpec {
         get_account_id_from_seed::<sr25519::Public>("Eve"),
         get_account_id_from_seed::<sr25519::Public>("Ferdie"),
     ];
-    ChainSpec::from_genesis(
+    Ok(ChainSpec::from_genesis(
         "Local Testnet",
         "local_testnet",
         ChainType::Local,
@@ -110,19 +338,152 @@ fn test_chain_spec(id: ParaId) -> ChainSpec {
                     .map(|a| (a, 1_000_000_000_000u128))
                     .collect(),
                 get_account_id_from_seed::<sr25519::Public>("Alice"),
-                wasm_binary_unwrap().to_vec(),
+                wasm_binary.clone(),
                 id,
             )
         },
         vec![],
         None,
         None,
-        None,
+        Some(xrt_properties()),
         Extensions {
             relay_chain: "westend-dev".into(),
             para_id: id.into(),
         },
-    )
+    ))
+}
+
+/// Custom parachain config with given genesis parameters.
+pub fn custom_parachain_config(
+    name: &str,
+    para_id: ParaId,
+    relay_chain: &str,
+    sudo: SudoKey,
+    balances: Vec<(AccountId, Balance)>,
+    bonus: Vec<(AccountId, Balance)>,
+    council: GenesisCouncil,
+    properties: sc_service::Properties,
+) -> Result<ChainSpec, String> {
+    council.check(&balances)?;
+    let wasm_binary = wasm_binary()?.to_vec();
+    Ok(ChainSpec::from_genesis(
+        name,
+        &format!("robonomics_{}", u32::from(para_id)),
+        ChainType::Live,
+        move || {
+            mk_genesis_with_sudo(
+                balances.clone(),
+                bonus.clone(),
+                sudo.clone(),
+                council.clone(),
+                wasm_binary.clone(),
+                para_id,
+            )
+        },
+        vec![],
+        None,
+        Some(ROBONOMICS_PROTOCOL_ID),
+        Some(properties),
+        Extensions {
+            relay_chain: relay_chain.into(),
+            para_id: para_id.into(),
+        },
+    ))
+}
+
+/// Genesis sudo key holder.
+#[derive(Debug, Clone)]
+pub enum SudoKey {
+    /// Single account.
+    Account(AccountId),
+    /// Multisig account of given signatories.
+    Multisig {
+        /// Multisig signatories, order doesn't matter.
+        signatories: Vec<AccountId>,
+        /// Approvals required to dispatch a call.
+        threshold: u16,
+    },
+}
+
+impl SudoKey {
+    /// Account id of sudo key holder.
+    pub fn account_id(&self) -> AccountId {
+        match self {
+            SudoKey::Account(account) => account.clone(),
+            SudoKey::Multisig {
+                signatories,
+                threshold,
+            } => multisig_account_id(signatories, *threshold),
+        }
+    }
+}
+
+/// Genesis council of governed chain.
+///
+/// Notice: runtime council membership is initialized by elections, so fixed
+/// members and elected seats can't be used together.
+#[derive(Debug, Clone, Default)]
+pub struct GenesisCouncil {
+    /// Council members without elections backing.
+    pub members: Vec<AccountId>,
+    /// Elected council seats with self vote stake.
+    pub elected: Vec<(AccountId, Balance)>,
+}
+
+impl GenesisCouncil {
+    /// Check council against genesis balances, elected stake is locked at genesis.
+    pub fn check(&self, balances: &[(AccountId, Balance)]) -> Result<(), String> {
+        if !self.members.is_empty() && !self.elected.is_empty() {
+            return Err("Council members and elected seats can't be set together".into());
+        }
+
+        if self.elected.len() > DesiredMembers::get() as usize {
+            return Err(format!(
+                "Too many elected seats, runtime desires at most {} members",
+                DesiredMembers::get()
+            ));
+        }
+
+        let mut accounts = BTreeSet::new();
+        for account in self
+            .members
+            .iter()
+            .chain(self.elected.iter().map(|(account, _)| account))
+        {
+            if !accounts.insert(account) {
+                return Err(format!(
+                    "Duplicate council member {}",
+                    account.to_ss58check()
+                ));
+            }
+        }
+
+        for (account, stake) in &self.elected {
+            let balance = balances
+                .iter()
+                .filter(|(a, _)| a == account)
+                .map(|(_, balance)| *balance)
+                .sum::<Balance>();
+            if *stake == 0 || *stake > balance {
+                return Err(format!(
+                    "Elected seat stake {} of {} should be positive and covered by genesis balance {}",
+                    stake,
+                    account.to_ss58check(),
+                    balance
+                ));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Canonical multisig account id, the same as `pallet_multisig::Pallet::multi_account_id`.
+pub fn multisig_account_id(signatories: &[AccountId], threshold: u16) -> AccountId {
+    let mut who = signatories.to_vec();
+    who.sort();
+    let entropy = (b"modlpy/utilisuba", who, threshold).using_encoded(blake2_256);
+    AccountId::decode(&mut &entropy[..]).expect("account id is 32 bytes long; qed")
 }
 
 /// Helper function to create GenesisConfig for parachain
@@ -133,14 +494,40 @@ fn mk_genesis(
     parachain_id: ParaId,
 ) -> GenesisConfig {
     let bonus = balances.clone();
+    mk_genesis_with_sudo(
+        balances,
+        bonus,
+        SudoKey::Account(sudo_key),
+        Default::default(),
+        code,
+        parachain_id,
+    )
+}
+
+/// Helper function to create GenesisConfig for parachain with given staking bonus,
+/// sudo key holder and council.
+fn mk_genesis_with_sudo(
+    balances: Vec<(AccountId, Balance)>,
+    bonus: Vec<(AccountId, Balance)>,
+    sudo: SudoKey,
+    council: GenesisCouncil,
+    code: Vec<u8>,
+    parachain_id: ParaId,
+) -> GenesisConfig {
+    let sudo_key = sudo.account_id();
     GenesisConfig {
         frame_system: SystemConfig {
             code,
             changes_trie_config: Default::default(),
         },
         pallet_balances: BalancesConfig { balances },
-        pallet_elections_phragmen: Default::default(),
-        pallet_collective_Instance1: Default::default(),
+        pallet_elections_phragmen: ElectionsConfig {
+            members: council.elected,
+        },
+        pallet_collective_Instance1: CouncilConfig {
+            members: council.members,
+            phantom: Default::default(),
+        },
         pallet_treasury: Default::default(),
         pallet_robonomics_staking: StakingConfig { bonus },
         pallet_sudo: SudoConfig { key: sudo_key },
@@ -267,7 +654,7 @@ pub fn kusama_parachain_config() -> ChainSpec {
                 .unwrap(),
         ),
         Some(ROBONOMICS_PROTOCOL_ID),
-        None,
+        Some(xrt_properties()),
         Extensions {
             relay_chain: "kusama".into(),
             para_id: KUSAMA_ID.into(),
@@ -302,3 +689,84 @@ pub fn kusama_parachain_config() -> ChainSpec {
     ChainSpec::from_json_bytes(&include_bytes!("../../res/robonomics.json")[..]).unwrap()
 }
 */
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use hex_literal::hex;
+
+    #[test]
+    fn multisig_account_id_matches_pallet_multisig() {
+        let alice = get_account_id_from_seed::<sr25519::Public>("Alice");
+        let bob = get_account_id_from_seed::<sr25519::Public>("Bob");
+        let charlie = get_account_id_from_seed::<sr25519::Public>("Charlie");
+        // pallet_multisig::Pallet::<Runtime>::multi_account_id(&[alice, bob, charlie], 2)
+        let expected = AccountId::from(hex![
+            "49daa32c7287890f38b7e1a8cd2961723d36d20baa0bf3b82e0c4bdda93b1c0a"
+        ]);
+        assert_eq!(
+            multisig_account_id(&[alice.clone(), bob.clone(), charlie.clone()], 2),
+            expected
+        );
+        assert_eq!(multisig_account_id(&[charlie, alice, bob], 2), expected);
+    }
+
+    fn account(seed: &str) -> AccountId {
+        get_account_id_from_seed::<sr25519::Public>(seed)
+    }
+
+    #[test]
+    fn genesis_council_members_and_elected_are_exclusive() {
+        let council = GenesisCouncil {
+            members: vec![account("Alice")],
+            elected: vec![(account("Bob"), 10)],
+        };
+        assert!(council.check(&[(account("Bob"), 10)]).is_err());
+    }
+
+    #[test]
+    fn genesis_council_rejects_duplicates() {
+        let council = GenesisCouncil {
+            members: vec![account("Alice"), account("Alice")],
+            elected: vec![],
+        };
+        assert!(council.check(&[]).is_err());
+
+        let council = GenesisCouncil {
+            members: vec![],
+            elected: vec![(account("Alice"), 1), (account("Alice"), 1)],
+        };
+        assert!(council.check(&[(account("Alice"), 10)]).is_err());
+    }
+
+    #[test]
+    fn genesis_council_checks_elected_stake() {
+        let balances = vec![(account("Alice"), 10), (account("Alice"), 5)];
+        let council = |stake| GenesisCouncil {
+            members: vec![],
+            elected: vec![(account("Alice"), stake)],
+        };
+        assert!(council(0).check(&balances).is_err());
+        assert!(council(16).check(&balances).is_err());
+        assert!(council(15).check(&balances).is_ok());
+    }
+
+    #[test]
+    fn genesis_council_rejects_too_many_seats() {
+        let seats = DesiredMembers::get() as usize;
+        let elected: Vec<_> = (0..=seats)
+            .map(|i| (account(&format!("Member{}", i)), 1))
+            .collect();
+        let council = GenesisCouncil {
+            members: vec![],
+            elected: elected.clone(),
+        };
+        assert!(council.check(&elected).is_err());
+
+        let council = GenesisCouncil {
+            members: vec![],
+            elected: elected[..seats].to_vec(),
+        };
+        assert!(council.check(&elected).is_ok());
+    }
+}
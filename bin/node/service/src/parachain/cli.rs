@@ -16,6 +16,8 @@
 //
 ///////////////////////////////////////////////////////////////////////////////
 
+use robonomics_primitives::{AccountId, Balance};
+use sp_core::crypto::Ss58Codec;
 use std::path::PathBuf;
 use structopt::StructOpt;
 
@@ -54,3 +56,335 @@ pub struct ExportGenesisWasmCommand {
     #[structopt(long)]
     pub chain: Option<String>,
 }
+
+/// Command for checking consistency of the parachain chain specification file.
+#[derive(Debug, StructOpt)]
+pub struct CheckSpecCommand {
+    /// Path to the chain specification JSON file.
+    #[structopt(parse(from_os_str))]
+    pub path: PathBuf,
+
+    /// Fail when genesis balance is below existential deposit instead of warning.
+    #[structopt(long)]
+    pub strict: bool,
+}
+
+/// Command for comparing genesis of two parachain chain specification files.
+#[derive(Debug, StructOpt)]
+pub struct DiffSpecCommand {
+    /// Path to the original chain specification JSON file.
+    #[structopt(parse(from_os_str))]
+    pub a: PathBuf,
+
+    /// Path to the compared chain specification JSON file.
+    #[structopt(parse(from_os_str))]
+    pub b: PathBuf,
+}
+
+/// Command for building a raw chain specification of a new parachain.
+#[derive(Debug, StructOpt)]
+pub struct BuildParachainSpecCommand {
+    /// Id of the parachain.
+    #[structopt(long)]
+    pub para_id: u32,
+
+    /// The relay chain of the parachain, e.g. `polkadot` or `kusama`.
+    #[structopt(long, parse(try_from_str = parse_relay_chain))]
+    pub relay_chain: String,
+
+    /// Sudo account address.
+    #[structopt(
+        long,
+        value_name = "SS58",
+        parse(try_from_str = parse_account),
+        required_unless = "sudo-signatory"
+    )]
+    pub sudo: Option<AccountId>,
+
+    /// Sudo multisig signatory address, should be repeated at least twice.
+    /// Notice: Sudo key is set to multisig account of given signatories.
+    #[structopt(
+        long,
+        value_name = "SS58",
+        parse(try_from_str = parse_account),
+        conflicts_with = "sudo",
+        requires = "sudo-threshold"
+    )]
+    pub sudo_signatory: Vec<AccountId>,
+
+    /// Sudo multisig approvals threshold.
+    #[structopt(long, value_name = "THRESHOLD")]
+    pub sudo_threshold: Option<u16>,
+
+    /// Endowed account with initial balance, could be repeated.
+    #[structopt(long = "balance", value_name = "SS58=BALANCE", parse(try_from_str = parse_balance))]
+    pub balances: Vec<(AccountId, Balance)>,
+
+    /// JSON file of endowed accounts with initial balances: `{ "SS58": BALANCE }`.
+    #[structopt(long, value_name = "PATH", parse(from_os_str))]
+    pub allocations: Option<PathBuf>,
+
+    /// JSON snapshot of previous chain state to carry balances and staking bonus over:
+    /// `{ "balances": { "SS58": BALANCE }, "bonus": { "SS58": BALANCE }, "totalIssuance": BALANCE }`.
+    #[structopt(long, value_name = "PATH", parse(from_os_str))]
+    pub from_snapshot: Option<PathBuf>,
+
+    /// Genesis council member address, could be repeated.
+    #[structopt(
+        long = "council",
+        value_name = "SS58",
+        parse(try_from_str = parse_account),
+        conflicts_with = "elected"
+    )]
+    pub council: Vec<AccountId>,
+
+    /// Genesis elected council seat with self vote stake, could be repeated.
+    /// Notice: Stake is locked from the genesis balance of the member.
+    #[structopt(long = "elected", value_name = "SS58=STAKE", parse(try_from_str = parse_balance))]
+    pub elected: Vec<(AccountId, Balance)>,
+
+    /// Fail when genesis balance is below existential deposit instead of warning.
+    #[structopt(long)]
+    pub strict: bool,
+
+    /// Token decimals shown by wallets.
+    #[structopt(long, default_value = "9")]
+    pub token_decimals: u32,
+
+    /// Token symbol shown by wallets.
+    #[structopt(long, default_value = "XRT")]
+    pub token_symbol: String,
+
+    /// SS58 address format shown by wallets, should match runtime `SS58Prefix`.
+    #[structopt(long, default_value = "32")]
+    pub ss58_format: u16,
+
+    /// Human readable name of the chain.
+    #[structopt(long, default_value = "Robonomics")]
+    pub name: String,
+}
+
+impl BuildParachainSpecCommand {
+    /// Genesis sudo key holder.
+    pub fn sudo_key(&self) -> Result<super::chain_spec::SudoKey, String> {
+        if let Some(account) = &self.sudo {
+            return Ok(super::chain_spec::SudoKey::Account(account.clone()));
+        }
+
+        if self.sudo_signatory.len() < 2 {
+            return Err("Sudo multisig requires at least 2 signatories".into());
+        }
+
+        let threshold = self.sudo_threshold.unwrap_or(1);
+        if threshold == 0 || threshold as usize > self.sudo_signatory.len() {
+            return Err(format!(
+                "Sudo threshold should be in 1..={} range",
+                self.sudo_signatory.len()
+            ));
+        }
+
+        Ok(super::chain_spec::SudoKey::Multisig {
+            signatories: self.sudo_signatory.clone(),
+            threshold,
+        })
+    }
+
+    /// Genesis council given by `--council` or `--elected` options.
+    pub fn genesis_council(&self) -> super::chain_spec::GenesisCouncil {
+        super::chain_spec::GenesisCouncil {
+            members: self.council.clone(),
+            elected: self.elected.clone(),
+        }
+    }
+
+    /// Chain spec properties given by `--token-decimals`, `--token-symbol` and `--ss58-format`.
+    pub fn properties(&self) -> sc_service::Properties {
+        crate::chain_spec::token_properties(
+            self.token_decimals,
+            &self.token_symbol,
+            self.ss58_format,
+        )
+    }
+
+    /// Chain state snapshot given by `--from-snapshot`.
+    pub fn snapshot(&self) -> Result<Option<Snapshot>, String> {
+        self.from_snapshot.as_ref().map(read_snapshot).transpose()
+    }
+
+    /// Genesis balances given by `--balance` options, allocations and snapshot files.
+    pub fn genesis_balances(
+        &self,
+        snapshot: Option<&Snapshot>,
+    ) -> Result<Vec<(AccountId, Balance)>, String> {
+        let mut balances = self.balances.clone();
+        if let Some(path) = &self.allocations {
+            balances.extend(read_allocations(path)?);
+        }
+        if let Some(snapshot) = snapshot {
+            balances.extend(snapshot.balances.iter().cloned());
+        }
+
+        let mut accounts = std::collections::BTreeSet::new();
+        for (account, _) in &balances {
+            if !accounts.insert(account) {
+                return Err(format!("Duplicate balance of {}", account.to_ss58check()));
+            }
+        }
+
+        let total = balances
+            .iter()
+            .try_fold(0 as Balance, |total, (_, balance)| {
+                total.checked_add(*balance)
+            })
+            .ok_or("Total genesis balance overflow")?;
+        // Chain spec is written into stdout, so sanity check goes to stderr.
+        eprintln!(
+            "Genesis balances: {} accounts, {} in total",
+            balances.len(),
+            total
+        );
+        super::chain_spec::check_existential_deposit(&balances, self.strict)?;
+
+        Ok(balances)
+    }
+}
+
+/// Read `{ "SS58": BALANCE }` JSON allocations file, balance could be number or string.
+fn read_allocations(path: &PathBuf) -> Result<Vec<(AccountId, Balance)>, String> {
+    let file = std::fs::File::open(path)
+        .map_err(|e| format!("Unable to open {}: {}", path.display(), e))?;
+    let allocations: serde_json::Map<String, serde_json::Value> = serde_json::from_reader(file)
+        .map_err(|e| format!("Invalid allocations file {}: {}", path.display(), e))?;
+    parse_allocations(allocations)
+}
+
+/// Parse `{ "SS58": BALANCE }` JSON map, balance could be number or string.
+fn parse_allocations(
+    allocations: serde_json::Map<String, serde_json::Value>,
+) -> Result<Vec<(AccountId, Balance)>, String> {
+    allocations
+        .into_iter()
+        .map(|(address, balance)| {
+            let account = parse_account(&address)?;
+            let balance = parse_amount(&balance)
+                .map_err(|e| format!("Invalid balance of {}: {}", address, e))?;
+            Ok((account, balance))
+        })
+        .collect()
+}
+
+/// Parse JSON number or string amount.
+fn parse_amount(amount: &serde_json::Value) -> Result<Balance, std::num::ParseIntError> {
+    amount.to_string().trim_matches('"').parse::<Balance>()
+}
+
+/// Account balances and staking bonus of previous chain state.
+pub struct Snapshot {
+    /// Account free balances.
+    pub balances: Vec<(AccountId, Balance)>,
+    /// Account staking bonus.
+    pub bonus: Vec<(AccountId, Balance)>,
+}
+
+/// Read chain state snapshot and check its balances against total issuance when given.
+fn read_snapshot(path: &PathBuf) -> Result<Snapshot, String> {
+    let file = std::fs::File::open(path)
+        .map_err(|e| format!("Unable to open {}: {}", path.display(), e))?;
+    let mut snapshot: serde_json::Map<String, serde_json::Value> = serde_json::from_reader(file)
+        .map_err(|e| format!("Invalid snapshot file {}: {}", path.display(), e))?;
+
+    let mut section = |name: &str| match snapshot.remove(name) {
+        Some(serde_json::Value::Object(map)) => parse_allocations(map),
+        Some(_) => Err(format!("Snapshot {} should be an object", name)),
+        None => Ok(Vec::new()),
+    };
+    let balances = section("balances")?;
+    let bonus = section("bonus")?;
+
+    let total = balances
+        .iter()
+        .try_fold(0 as Balance, |total, (_, balance)| {
+            total.checked_add(*balance)
+        })
+        .ok_or("Snapshot total balance overflow")?;
+    if let Some(issuance) = snapshot.get("totalIssuance") {
+        let issuance =
+            parse_amount(issuance).map_err(|e| format!("Invalid snapshot issuance: {}", e))?;
+        if issuance != total {
+            return Err(format!(
+                "Snapshot balances total {} doesn't match total issuance {}",
+                total, issuance
+            ));
+        }
+    }
+
+    // Chain spec is written into stdout, so summary goes to stderr.
+    eprintln!(
+        "Snapshot {}: {} accounts, {} in total, {} staking bonuses",
+        path.display(),
+        balances.len(),
+        total,
+        bonus.len()
+    );
+    Ok(Snapshot { balances, bonus })
+}
+
+/// Read relay chain arguments from TOML or YAML (by `.yaml`/`.yml` extension) file.
+///
+/// Keys are option names without `--`: `true` value is a flag, array value repeats
+/// the option, e.g. `chain = "kusama"` and `bootnodes = ["/ip4/..", "/ip4/.."]`.
+pub fn read_relaychain_config(path: &PathBuf) -> Result<Vec<String>, String> {
+    let content = std::fs::read_to_string(path)
+        .map_err(|e| format!("Unable to read {}: {}", path.display(), e))?;
+    let is_yaml = path
+        .extension()
+        .map_or(false, |ext| ext == "yaml" || ext == "yml");
+    let config: serde_json::Map<String, serde_json::Value> = if is_yaml {
+        serde_yaml::from_str(&content).map_err(|e| e.to_string())
+    } else {
+        toml::from_str(&content).map_err(|e| e.to_string())
+    }
+    .map_err(|e| format!("Invalid relay chain config {}: {}", path.display(), e))?;
+
+    let mut args = Vec::new();
+    for (name, value) in config {
+        let values = match value {
+            serde_json::Value::Array(values) => values,
+            value => vec![value],
+        };
+        for value in values {
+            match value {
+                serde_json::Value::Bool(true) => args.push(format!("--{}", name)),
+                serde_json::Value::Bool(false) => (),
+                serde_json::Value::String(value) => {
+                    args.extend_from_slice(&[format!("--{}", name), value])
+                }
+                serde_json::Value::Number(value) => {
+                    args.extend_from_slice(&[format!("--{}", name), value.to_string()])
+                }
+                _ => return Err(format!("Unsupported value of relay chain option {}", name)),
+            }
+        }
+    }
+    Ok(args)
+}
+
+fn parse_relay_chain(relay_chain: &str) -> Result<String, String> {
+    super::chain_spec::check_relay_chain(relay_chain)?;
+    Ok(relay_chain.into())
+}
+
+fn parse_account(address: &str) -> Result<AccountId, String> {
+    AccountId::from_ss58check(address).map_err(|e| format!("Invalid address {}: {:?}", address, e))
+}
+
+fn parse_balance(record: &str) -> Result<(AccountId, Balance), String> {
+    let mut parts = record.splitn(2, '=');
+    let account = parse_account(parts.next().unwrap_or_default())?;
+    let balance = parts
+        .next()
+        .ok_or_else(|| format!("Balance not found in {}", record))?
+        .parse::<Balance>()
+        .map_err(|e| format!("Invalid balance in {}: {}", record, e))?;
+    Ok((account, balance))
+}
@@ -18,6 +18,7 @@
 //! Polkadot collator service implementation.
 
 use super::{new_partial, Executor, RuntimeApi};
+use cumulus_client_consensus_common::{ParachainCandidate, ParachainConsensus};
 use cumulus_client_consensus_relay_chain::{
     build_relay_chain_consensus, BuildRelayChainConsensusParams,
 };
@@ -25,11 +26,67 @@ use cumulus_client_network::build_block_announce_validator;
 use cumulus_client_service::{
     prepare_node_config, start_collator, start_full_node, StartCollatorParams, StartFullNodeParams,
 };
+use cumulus_primitives_core::PersistedValidationData;
 use cumulus_primitives_parachain_inherent::ParachainInherentData;
-use robonomics_primitives::Block;
+use node_rpc::parachain::{Parachain, ParachainApi, ParachainStatus, SharedParachainStatus};
+use robonomics_primitives::{Block, Hash};
 use sc_service::{Configuration, Role, TFullClient, TaskManager};
+use sp_runtime::traits::{Block as BlockT, Header as HeaderT};
 use std::sync::Arc;
 
+/// Parachain consensus wrapper that emits structured event for every collated block.
+#[derive(Clone)]
+struct CollationTracing {
+    inner: Box<dyn ParachainConsensus<Block>>,
+    para_id: polkadot_primitives::v0::Id,
+}
+
+#[async_trait::async_trait]
+impl ParachainConsensus<Block> for CollationTracing {
+    async fn produce_candidate(
+        &mut self,
+        parent: &<Block as BlockT>::Header,
+        relay_parent: Hash,
+        validation_data: &PersistedValidationData,
+    ) -> Option<ParachainCandidate<Block>> {
+        let candidate = self
+            .inner
+            .produce_candidate(parent, relay_parent, validation_data)
+            .await?;
+
+        tracing::info!(
+            target: "robonomics::collator",
+            para_id = u32::from(self.para_id),
+            block_number = *candidate.block.header().number(),
+            block_hash = ?candidate.block.hash(),
+            relay_parent = ?relay_parent,
+            "Parachain block collated",
+        );
+
+        Some(candidate)
+    }
+}
+
+/// Update shared parachain status with the collation context of a new relay parent.
+fn record_parachain_status(
+    status: &SharedParachainStatus,
+    relay_parent: Hash,
+    data: &ParachainInherentData,
+) {
+    let validation_data = &data.validation_data;
+    *status
+        .write()
+        .expect("parachain status lock is not poisoned; qed") = Some(ParachainStatus {
+        relay_parent,
+        relay_parent_number: validation_data.relay_parent_number,
+        relay_parent_storage_root: validation_data.relay_parent_storage_root,
+        parent_head: validation_data.parent_head.0.clone().into(),
+        max_pov_size: validation_data.max_pov_size,
+        pending_dmp: data.downward_messages.len(),
+        pending_hrmp: data.horizontal_messages.values().map(Vec::len).sum(),
+    });
+}
+
 /// Start a node with the given parachain `Configuration` and relay chain `Configuration`.
 ///
 /// This is the actual implementation that is abstract over the executor and the runtime api.
@@ -80,10 +137,24 @@ async fn start_node_impl(
             block_announce_validator_builder: Some(Box::new(|_| block_announce_validator)),
         })?;
 
+    let parachain_status = SharedParachainStatus::default();
+    let rpc_extensions_builder = {
+        let parachain_status = parachain_status.clone();
+        let known_parachains = super::chain_spec::known_parachains();
+        Box::new(move |_, _| {
+            let mut io = node_rpc::IoHandler::default();
+            io.extend_with(ParachainApi::to_delegate(Parachain::new(
+                parachain_status.clone(),
+                known_parachains.clone(),
+            )));
+            io
+        })
+    };
+
     sc_service::spawn_tasks(sc_service::SpawnTasksParams {
         on_demand: None,
         remote_blockchain: None,
-        rpc_extensions_builder: Box::new(|_, _| ()),
+        rpc_extensions_builder,
         client: client.clone(),
         transaction_pool: transaction_pool.clone(),
         task_manager: &mut task_manager,
@@ -124,6 +195,9 @@ async fn start_node_impl(
                     &validation_data,
                     id,
                 );
+                if let Some(data) = &parachain_inherent {
+                    record_parachain_status(&parachain_status, relay_parent, data);
+                }
                 async move {
                     let timestamp = sp_timestamp::InherentDataProvider::from_system_time();
                     let lighthouse = pallet_robonomics_lighthouse::InherentDataProvider(Vec::from(
@@ -139,6 +213,11 @@ async fn start_node_impl(
             },
         });
 
+        let parachain_consensus = Box::new(CollationTracing {
+            inner: parachain_consensus,
+            para_id: id,
+        });
+
         let spawner = task_manager.spawn_handle();
         let params = StartCollatorParams {
             para_id: id,
@@ -39,7 +39,21 @@ pub async fn run(
     parachain_id: Option<u32>,
     validator_account: Option<sp_core::H160>,
 ) -> sc_service::error::Result<TaskManager> {
+    if matches!(config.role, sc_service::Role::Light) {
+        info!(target: "robonomics::collator", "[Parachain] Starting light client");
+        return super::new_light(config);
+    }
+
     let extension = super::chain_spec::Extensions::try_get(&config.chain_spec);
+    if let (Some(id), Some(extension)) = (parachain_id, extension) {
+        if id != extension.para_id {
+            return Err(format!(
+                "Parachain id {} doesn't match id {} of the chain spec",
+                id, extension.para_id
+            )
+            .into());
+        }
+    }
     let parachain_id = ParaId::from(parachain_id.or(extension.map(|e| e.para_id)).unwrap_or(100));
     let relay_chain_id = extension.map(|e| e.relay_chain.clone());
     let polkadot_cli = RelayChainCli::new(
@@ -56,10 +70,11 @@ pub async fn run(
     let parachain_account =
         AccountIdConversion::<polkadot_primitives::v0::AccountId>::into_account(&parachain_id);
 
-    info!("[Parachain] ID: {}", parachain_id);
-    info!("[Parachain] Account: {}", parachain_account);
-    info!("[Parachain] Genesis State: {}", genesis_state);
+    info!(target: "robonomics::collator", "[Parachain] ID: {}", parachain_id);
+    info!(target: "robonomics::collator", "[Parachain] Account: {}", parachain_account);
+    info!(target: "robonomics::collator", "[Parachain] Genesis State: {}", genesis_state);
     info!(
+        target: "robonomics::collator",
         "[Parachain] Is collating: {}",
         if let Some(account) = validator_account {
             format!("yes ({})", account)
@@ -0,0 +1,355 @@
+///////////////////////////////////////////////////////////////////////////////
+//
+//  Copyright 2018-2021 Robonomics Network <research@robonomics.network>
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+//
+///////////////////////////////////////////////////////////////////////////////
+//! Block proposer extensions.
+
+use futures::{future::BoxFuture, FutureExt, TryFutureExt};
+use robonomics_primitives::{Block, BlockNumber};
+use sc_consensus_slots::{BackoffAuthoringBlocksStrategy, BackoffAuthoringOnFinalizedHeadLagging};
+use sp_consensus::{Environment, Proposal, Proposer};
+use sp_consensus_babe::Slot;
+use sp_inherents::InherentData;
+use sp_runtime::{traits::Block as BlockT, DigestFor};
+use sp_transaction_pool::TransactionPool;
+use std::{
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+use substrate_prometheus_endpoint::{
+    register, Counter, Gauge, Histogram, HistogramOpts, PrometheusError, Registry, U64,
+};
+
+/// Block authoring metrics.
+#[derive(Clone)]
+pub struct AuthoringMetrics {
+    missed_slots: Counter<U64>,
+    proposal_time: Histogram,
+}
+
+impl AuthoringMetrics {
+    /// Register authoring metrics in given registry.
+    pub fn register(registry: &Registry) -> Result<Self, PrometheusError> {
+        Ok(Self {
+            missed_slots: register(
+                Counter::new(
+                    "robonomics_babe_missed_slots_total",
+                    "Number of claimed slots without proposed block",
+                )?,
+                registry,
+            )?,
+            proposal_time: register(
+                Histogram::with_opts(HistogramOpts::new(
+                    "robonomics_block_proposal_seconds",
+                    "Time taken to propose a block",
+                ))?,
+                registry,
+            )?,
+        })
+    }
+}
+
+/// Counts missed slot when dropped before proposal is done.
+struct MissedSlotGuard(Option<AuthoringMetrics>);
+
+impl MissedSlotGuard {
+    fn done(mut self) {
+        self.0 = None;
+    }
+}
+
+impl Drop for MissedSlotGuard {
+    fn drop(&mut self) {
+        if let Some(metrics) = &self.0 {
+            metrics.missed_slots.inc();
+        }
+    }
+}
+
+/// Proposer factory that measures block authoring.
+pub struct MeteredProposerFactory<E> {
+    inner: E,
+    metrics: Option<AuthoringMetrics>,
+}
+
+impl<E> MeteredProposerFactory<E> {
+    /// Wrap proposer factory and register authoring metrics when registry given.
+    pub fn new(inner: E, registry: Option<&Registry>) -> Result<Self, PrometheusError> {
+        let metrics = registry.map(AuthoringMetrics::register).transpose()?;
+        Ok(Self { inner, metrics })
+    }
+}
+
+impl<E> Environment<Block> for MeteredProposerFactory<E>
+where
+    E: Environment<Block>,
+{
+    type Proposer = MeteredProposer<E::Proposer>;
+    type CreateProposer = BoxFuture<'static, Result<Self::Proposer, Self::Error>>;
+    type Error = E::Error;
+
+    fn init(&mut self, parent_header: &<Block as BlockT>::Header) -> Self::CreateProposer {
+        let metrics = self.metrics.clone();
+        self.inner
+            .init(parent_header)
+            .map_ok(move |inner| MeteredProposer { inner, metrics })
+            .boxed()
+    }
+}
+
+/// Proposer that measures block proposal time and missed slots.
+pub struct MeteredProposer<P> {
+    inner: P,
+    metrics: Option<AuthoringMetrics>,
+}
+
+impl<P> Proposer<Block> for MeteredProposer<P>
+where
+    P: Proposer<Block>,
+{
+    type Error = P::Error;
+    type Transaction = P::Transaction;
+    type Proposal =
+        BoxFuture<'static, Result<Proposal<Block, Self::Transaction, Self::Proof>, Self::Error>>;
+    type ProofRecording = P::ProofRecording;
+    type Proof = P::Proof;
+
+    fn propose(
+        self,
+        inherent_data: InherentData,
+        inherent_digests: DigestFor<Block>,
+        max_duration: Duration,
+        block_size_limit: Option<usize>,
+    ) -> Self::Proposal {
+        let timer = self.metrics.as_ref().map(|m| m.proposal_time.start_timer());
+        let guard = MissedSlotGuard(self.metrics);
+        let proposal = self.inner.propose(
+            inherent_data,
+            inherent_digests,
+            max_duration,
+            block_size_limit,
+        );
+
+        async move {
+            let result = proposal.await;
+            if result.is_ok() {
+                guard.done();
+                if let Some(timer) = timer {
+                    timer.observe_duration();
+                }
+            }
+            result
+        }
+        .boxed()
+    }
+}
+
+/// Block authoring backoff strategy of the node.
+///
+/// Authoring is paused while finality lags behind more than given number of blocks and
+/// skipped when transaction pool has no ready transactions until given number of slots
+/// elapsed since the chain head, finality lag backoff is applied otherwise.
+/// Notice: Authoring is never skipped for more than half of the epoch to keep BABE alive.
+pub struct AuthoringBackoff<P> {
+    lagging: Option<BackoffAuthoringOnFinalizedHeadLagging<BlockNumber>>,
+    on_demand: Option<(Arc<P>, u64)>,
+    pause_on_lag: Option<BlockNumber>,
+    paused: AtomicBool,
+    paused_gauge: Option<Gauge<U64>>,
+    max_skipped_slots: u64,
+}
+
+impl<P> AuthoringBackoff<P> {
+    /// Create backoff strategy for given BABE epoch length in slots.
+    pub fn new(
+        lagging: Option<BackoffAuthoringOnFinalizedHeadLagging<BlockNumber>>,
+        epoch_length: u64,
+    ) -> Self {
+        Self {
+            lagging,
+            on_demand: None,
+            pause_on_lag: None,
+            paused: AtomicBool::new(false),
+            paused_gauge: None,
+            max_skipped_slots: epoch_length / 2,
+        }
+    }
+
+    /// Maximum number of slots authoring could be skipped for.
+    pub fn max_skipped_slots(&self) -> u64 {
+        self.max_skipped_slots
+    }
+
+    /// Author blocks only when pool has ready transactions or given number of slots elapsed.
+    pub fn on_demand(mut self, pool: Arc<P>, max_empty_slots: u64) -> Self {
+        self.on_demand = Some((pool, max_empty_slots));
+        self
+    }
+
+    /// Pause authoring while finality lags behind more than given number of blocks,
+    /// paused authoring gauge is registered when registry given.
+    pub fn pause_on_lag(
+        mut self,
+        max_lag: BlockNumber,
+        registry: Option<&Registry>,
+    ) -> Result<Self, PrometheusError> {
+        self.paused_gauge = registry
+            .map(|registry| {
+                register(
+                    Gauge::new(
+                        "robonomics_authoring_paused",
+                        "Block authoring is paused because of finality lag",
+                    )?,
+                    registry,
+                )
+            })
+            .transpose()?;
+        self.pause_on_lag = Some(max_lag);
+        Ok(self)
+    }
+
+    fn set_paused(&self, paused: bool, lag: BlockNumber) {
+        if self.paused.swap(paused, Ordering::Relaxed) == paused {
+            return;
+        }
+        if let Some(gauge) = &self.paused_gauge {
+            gauge.set(paused as u64);
+        }
+        if paused {
+            log::warn!(
+                target: "robonomics::service",
+                "Block authoring paused: finality lags {} blocks behind",
+                lag
+            );
+        } else {
+            log::info!(
+                target: "robonomics::service",
+                "Block authoring resumed: finality lags {} blocks behind",
+                lag
+            );
+        }
+    }
+
+    /// Authoring decision by finality lag and skipped slots, `None` when it isn't decided yet.
+    fn lag_backoff(
+        &self,
+        chain_head_number: BlockNumber,
+        finalized_number: BlockNumber,
+        skipped_slots: u64,
+    ) -> Option<bool> {
+        if let Some(max_lag) = self.pause_on_lag {
+            let lag = chain_head_number.saturating_sub(finalized_number);
+            self.set_paused(lag > max_lag, lag);
+        }
+        // Paused authoring is resumed for a block when epoch is going to be left empty.
+        if skipped_slots >= self.max_skipped_slots {
+            return Some(false);
+        }
+        if self.paused.load(Ordering::Relaxed) {
+            return Some(true);
+        }
+        None
+    }
+}
+
+impl<P> BackoffAuthoringBlocksStrategy<BlockNumber> for AuthoringBackoff<P>
+where
+    P: TransactionPool<Block = Block>,
+{
+    fn should_backoff(
+        &self,
+        chain_head_number: BlockNumber,
+        chain_head_slot: Slot,
+        finalized_number: BlockNumber,
+        slot_now: Slot,
+        logging_target: &str,
+    ) -> bool {
+        let skipped_slots = (*slot_now).saturating_sub(*chain_head_slot);
+        if let Some(backoff) = self.lag_backoff(chain_head_number, finalized_number, skipped_slots)
+        {
+            return backoff;
+        }
+
+        if let Some((pool, max_empty_slots)) = &self.on_demand {
+            if skipped_slots < *max_empty_slots && pool.status().ready == 0 {
+                log::debug!(
+                    target: logging_target,
+                    "Skipping block authoring at slot {}: no ready transactions",
+                    *slot_now,
+                );
+                return true;
+            }
+        }
+
+        self.lagging.as_ref().map_or(false, |lagging| {
+            lagging.should_backoff(
+                chain_head_number,
+                chain_head_slot,
+                finalized_number,
+                slot_now,
+                logging_target,
+            )
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn backoff(registry: Option<&Registry>) -> AuthoringBackoff<()> {
+        AuthoringBackoff::new(None, 20)
+            .pause_on_lag(5, registry)
+            .unwrap()
+    }
+
+    #[test]
+    fn lag_under_limit_is_not_paused() {
+        let registry = Registry::new();
+        let backoff = backoff(Some(&registry));
+        assert_eq!(backoff.lag_backoff(15, 10, 0), None);
+        assert!(!backoff.paused.load(Ordering::Relaxed));
+        assert_eq!(backoff.paused_gauge.as_ref().unwrap().get(), 0);
+    }
+
+    #[test]
+    fn lag_over_limit_is_paused() {
+        let registry = Registry::new();
+        let backoff = backoff(Some(&registry));
+        assert_eq!(backoff.lag_backoff(16, 10, 0), Some(true));
+        assert!(backoff.paused.load(Ordering::Relaxed));
+        assert_eq!(backoff.paused_gauge.as_ref().unwrap().get(), 1);
+
+        assert_eq!(backoff.lag_backoff(16, 11, 0), None);
+        assert!(!backoff.paused.load(Ordering::Relaxed));
+        assert_eq!(backoff.paused_gauge.as_ref().unwrap().get(), 0);
+    }
+
+    #[test]
+    fn skipped_slots_limit_overrides_pause() {
+        let registry = Registry::new();
+        let backoff = backoff(Some(&registry));
+        assert_eq!(backoff.lag_backoff(16, 10, 9), Some(true));
+        // Block is authored, but authoring is still paused because of finality lag.
+        assert_eq!(backoff.lag_backoff(16, 10, 10), Some(false));
+        assert!(backoff.paused.load(Ordering::Relaxed));
+        assert_eq!(backoff.paused_gauge.as_ref().unwrap().get(), 1);
+        assert_eq!(backoff.lag_backoff(17, 10, 0), Some(true));
+    }
+}
@@ -0,0 +1,375 @@
+///////////////////////////////////////////////////////////////////////////////
+//
+//  Copyright 2018-2021 Robonomics Network <research@robonomics.network>
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+//
+///////////////////////////////////////////////////////////////////////////////
+//! Remote signer keystore, e.g. for validator keys kept in HSM.
+//!
+//! Signer is accessed over Unix socket by newline delimited JSON requests:
+//! `{"method": "public_keys", "key_type": "babe", "crypto": "sr25"}` responds
+//! `{"result": ["<hex public>"]}`, `sign` and `vrf_sign` methods respond hex encoded
+//! signature, any failure is responded as `{"error": "<message>"}`.
+//!
+//! Requests fail when signer doesn't respond within a timeout, async keystore requests
+//! are run on blocking tasks to keep async executor threads free.
+
+use async_trait::async_trait;
+use futures::channel::oneshot;
+use sc_service::SpawnTaskHandle;
+use serde_json::{json, Value};
+use sp_core::{
+    crypto::{CryptoTypeId, CryptoTypePublicPair, KeyTypeId},
+    ecdsa, ed25519, sr25519,
+};
+use sp_keystore::{
+    vrf::{VRFSignature, VRFTranscriptData, VRFTranscriptValue},
+    CryptoStore, Error, SyncCryptoStore,
+};
+use std::convert::TryFrom;
+use std::io::{BufRead, BufReader, Write};
+use std::os::unix::net::UnixStream;
+use std::path::PathBuf;
+use std::time::Duration;
+
+/// Maximal time of remote signer request reading or writing.
+/// Notice: Signing should be done well within BABE slot to author a block.
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Keystore that keeps no keys locally and forwards signing to remote signer.
+#[derive(Clone)]
+pub struct RemoteSigner {
+    socket: PathBuf,
+    spawner: SpawnTaskHandle,
+}
+
+impl RemoteSigner {
+    /// Connect to remote signer by `unix:///path/to/socket` URL, async requests are spawned
+    /// by given handle.
+    pub fn connect(url: &str, spawner: SpawnTaskHandle) -> Result<Self, String> {
+        let socket = url
+            .strip_prefix("unix://")
+            .map(PathBuf::from)
+            .ok_or("Only unix:// remote signer URLs are supported")?;
+        let signer = Self { socket, spawner };
+        signer
+            .request(json!({ "method": "ping" }))
+            .map_err(|e| format!("{:?}", e))?;
+        Ok(signer)
+    }
+
+    fn request(&self, request: Value) -> Result<Value, Error> {
+        let other = |e: std::io::Error| Error::Other(e.to_string());
+        let mut stream = UnixStream::connect(&self.socket).map_err(|_| Error::Unavailable)?;
+        stream
+            .set_read_timeout(Some(REQUEST_TIMEOUT))
+            .map_err(other)?;
+        stream
+            .set_write_timeout(Some(REQUEST_TIMEOUT))
+            .map_err(other)?;
+        writeln!(stream, "{}", request).map_err(other)?;
+
+        let mut line = String::new();
+        BufReader::new(stream).read_line(&mut line).map_err(other)?;
+        let mut response: Value =
+            serde_json::from_str(&line).map_err(|e| Error::Other(e.to_string()))?;
+        if let Some(error) = response.get("error") {
+            return Err(Error::Other(error.to_string()));
+        }
+        Ok(response["result"].take())
+    }
+
+    /// Run signer requests on blocking task, `None` when the task is dropped.
+    async fn blocking<F, R>(&self, f: F) -> Option<R>
+    where
+        F: FnOnce(&RemoteSigner) -> R + Send + 'static,
+        R: Send + 'static,
+    {
+        let signer = self.clone();
+        let (sender, receiver) = oneshot::channel();
+        self.spawner.spawn_blocking("remote-signer", async move {
+            let _ = sender.send(f(&signer));
+        });
+        receiver.await.ok()
+    }
+
+    fn public_keys(&self, id: KeyTypeId, crypto: CryptoTypeId) -> Result<Vec<Vec<u8>>, Error> {
+        let keys = self.request(json!({
+            "method": "public_keys",
+            "key_type": key_type(id),
+            "crypto": crypto_type(crypto),
+        }))?;
+        keys.as_array()
+            .ok_or_else(|| Error::Other("Public keys should be an array".into()))?
+            .iter()
+            .map(decode_hex)
+            .collect()
+    }
+
+    fn typed_public_keys<T: for<'a> TryFrom<&'a [u8]>>(
+        &self,
+        id: KeyTypeId,
+        crypto: CryptoTypeId,
+    ) -> Vec<T> {
+        match self.public_keys(id, crypto) {
+            Ok(keys) => keys
+                .iter()
+                .filter_map(|key| T::try_from(key.as_slice()).ok())
+                .collect(),
+            Err(e) => {
+                log::warn!(target: "robonomics::service", "Unable to fetch public keys: {:?}", e);
+                Vec::new()
+            }
+        }
+    }
+}
+
+fn key_type(id: KeyTypeId) -> String {
+    String::from_utf8_lossy(&id.0).into()
+}
+
+fn crypto_type(crypto: CryptoTypeId) -> String {
+    String::from_utf8_lossy(&crypto.0).into()
+}
+
+fn decode_hex(value: &Value) -> Result<Vec<u8>, Error> {
+    let hex = value
+        .as_str()
+        .ok_or_else(|| Error::Other("Hex string expected".into()))?;
+    hex::decode(hex.trim_start_matches("0x")).map_err(|e| Error::Other(e.to_string()))
+}
+
+fn transcript(data: &VRFTranscriptData) -> Value {
+    let items: Vec<Value> = data
+        .items
+        .iter()
+        .map(|(label, value)| match value {
+            VRFTranscriptValue::Bytes(bytes) => {
+                json!({ "label": label, "bytes": hex::encode(bytes) })
+            }
+            VRFTranscriptValue::U64(number) => json!({ "label": label, "u64": number }),
+        })
+        .collect();
+    json!({ "label": hex::encode(data.label), "items": items })
+}
+
+impl SyncCryptoStore for RemoteSigner {
+    fn sr25519_public_keys(&self, id: KeyTypeId) -> Vec<sr25519::Public> {
+        self.typed_public_keys(id, sr25519::CRYPTO_ID)
+    }
+
+    fn sr25519_generate_new(
+        &self,
+        _id: KeyTypeId,
+        _seed: Option<&str>,
+    ) -> Result<sr25519::Public, Error> {
+        Err(Error::Unavailable)
+    }
+
+    fn ed25519_public_keys(&self, id: KeyTypeId) -> Vec<ed25519::Public> {
+        self.typed_public_keys(id, ed25519::CRYPTO_ID)
+    }
+
+    fn ed25519_generate_new(
+        &self,
+        _id: KeyTypeId,
+        _seed: Option<&str>,
+    ) -> Result<ed25519::Public, Error> {
+        Err(Error::Unavailable)
+    }
+
+    fn ecdsa_public_keys(&self, id: KeyTypeId) -> Vec<ecdsa::Public> {
+        self.typed_public_keys(id, ecdsa::CRYPTO_ID)
+    }
+
+    fn ecdsa_generate_new(
+        &self,
+        _id: KeyTypeId,
+        _seed: Option<&str>,
+    ) -> Result<ecdsa::Public, Error> {
+        Err(Error::Unavailable)
+    }
+
+    fn insert_unknown(&self, _key_type: KeyTypeId, _suri: &str, _public: &[u8]) -> Result<(), ()> {
+        // Keys are provisioned on the signer side only.
+        Err(())
+    }
+
+    fn supported_keys(
+        &self,
+        id: KeyTypeId,
+        keys: Vec<CryptoTypePublicPair>,
+    ) -> Result<Vec<CryptoTypePublicPair>, Error> {
+        let all_keys = SyncCryptoStore::keys(self, id)?;
+        Ok(keys
+            .into_iter()
+            .filter(|key| all_keys.contains(key))
+            .collect())
+    }
+
+    fn keys(&self, id: KeyTypeId) -> Result<Vec<CryptoTypePublicPair>, Error> {
+        let mut keys = Vec::new();
+        for crypto in &[sr25519::CRYPTO_ID, ed25519::CRYPTO_ID, ecdsa::CRYPTO_ID] {
+            keys.extend(
+                self.public_keys(id, *crypto)?
+                    .into_iter()
+                    .map(|public| CryptoTypePublicPair(*crypto, public)),
+            );
+        }
+        Ok(keys)
+    }
+
+    fn has_keys(&self, public_keys: &[(Vec<u8>, KeyTypeId)]) -> bool {
+        public_keys.iter().all(|(public, id)| {
+            SyncCryptoStore::keys(self, *id)
+                .map(|keys| keys.iter().any(|key| &key.1 == public))
+                .unwrap_or(false)
+        })
+    }
+
+    fn sign_with(
+        &self,
+        id: KeyTypeId,
+        key: &CryptoTypePublicPair,
+        msg: &[u8],
+    ) -> Result<Vec<u8>, Error> {
+        let signature = self.request(json!({
+            "method": "sign",
+            "key_type": key_type(id),
+            "crypto": crypto_type(key.0),
+            "public": hex::encode(&key.1),
+            "message": hex::encode(msg),
+        }))?;
+        decode_hex(&signature)
+    }
+
+    fn sr25519_vrf_sign(
+        &self,
+        key_type_id: KeyTypeId,
+        public: &sr25519::Public,
+        transcript_data: VRFTranscriptData,
+    ) -> Result<VRFSignature, Error> {
+        let response = self.request(json!({
+            "method": "vrf_sign",
+            "key_type": key_type(key_type_id),
+            "public": hex::encode(public),
+            "transcript": transcript(&transcript_data),
+        }))?;
+        let invalid = |e: schnorrkel::SignatureError| Error::Other(e.to_string());
+        Ok(VRFSignature {
+            output: schnorrkel::vrf::VRFOutput::from_bytes(&decode_hex(&response["output"])?)
+                .map_err(invalid)?,
+            proof: schnorrkel::vrf::VRFProof::from_bytes(&decode_hex(&response["proof"])?)
+                .map_err(invalid)?,
+        })
+    }
+}
+
+#[async_trait]
+impl CryptoStore for RemoteSigner {
+    async fn sr25519_public_keys(&self, id: KeyTypeId) -> Vec<sr25519::Public> {
+        self.blocking(move |signer| SyncCryptoStore::sr25519_public_keys(signer, id))
+            .await
+            .unwrap_or_default()
+    }
+
+    async fn sr25519_generate_new(
+        &self,
+        id: KeyTypeId,
+        seed: Option<&str>,
+    ) -> Result<sr25519::Public, Error> {
+        SyncCryptoStore::sr25519_generate_new(self, id, seed)
+    }
+
+    async fn ed25519_public_keys(&self, id: KeyTypeId) -> Vec<ed25519::Public> {
+        self.blocking(move |signer| SyncCryptoStore::ed25519_public_keys(signer, id))
+            .await
+            .unwrap_or_default()
+    }
+
+    async fn ed25519_generate_new(
+        &self,
+        id: KeyTypeId,
+        seed: Option<&str>,
+    ) -> Result<ed25519::Public, Error> {
+        SyncCryptoStore::ed25519_generate_new(self, id, seed)
+    }
+
+    async fn ecdsa_public_keys(&self, id: KeyTypeId) -> Vec<ecdsa::Public> {
+        self.blocking(move |signer| SyncCryptoStore::ecdsa_public_keys(signer, id))
+            .await
+            .unwrap_or_default()
+    }
+
+    async fn ecdsa_generate_new(
+        &self,
+        id: KeyTypeId,
+        seed: Option<&str>,
+    ) -> Result<ecdsa::Public, Error> {
+        SyncCryptoStore::ecdsa_generate_new(self, id, seed)
+    }
+
+    async fn insert_unknown(&self, id: KeyTypeId, suri: &str, public: &[u8]) -> Result<(), ()> {
+        SyncCryptoStore::insert_unknown(self, id, suri, public)
+    }
+
+    async fn supported_keys(
+        &self,
+        id: KeyTypeId,
+        keys: Vec<CryptoTypePublicPair>,
+    ) -> Result<Vec<CryptoTypePublicPair>, Error> {
+        self.blocking(move |signer| SyncCryptoStore::supported_keys(signer, id, keys))
+            .await
+            .unwrap_or(Err(Error::Unavailable))
+    }
+
+    async fn keys(&self, id: KeyTypeId) -> Result<Vec<CryptoTypePublicPair>, Error> {
+        self.blocking(move |signer| SyncCryptoStore::keys(signer, id))
+            .await
+            .unwrap_or(Err(Error::Unavailable))
+    }
+
+    async fn has_keys(&self, public_keys: &[(Vec<u8>, KeyTypeId)]) -> bool {
+        let public_keys = public_keys.to_vec();
+        self.blocking(move |signer| SyncCryptoStore::has_keys(signer, &public_keys))
+            .await
+            .unwrap_or(false)
+    }
+
+    async fn sign_with(
+        &self,
+        id: KeyTypeId,
+        key: &CryptoTypePublicPair,
+        msg: &[u8],
+    ) -> Result<Vec<u8>, Error> {
+        let (key, msg) = (key.clone(), msg.to_vec());
+        self.blocking(move |signer| SyncCryptoStore::sign_with(signer, id, &key, &msg))
+            .await
+            .unwrap_or(Err(Error::Unavailable))
+    }
+
+    async fn sr25519_vrf_sign(
+        &self,
+        key_type: KeyTypeId,
+        public: &sr25519::Public,
+        transcript_data: VRFTranscriptData,
+    ) -> Result<VRFSignature, Error> {
+        let public = *public;
+        self.blocking(move |signer| {
+            SyncCryptoStore::sr25519_vrf_sign(signer, key_type, &public, transcript_data)
+        })
+        .await
+        .unwrap_or(Err(Error::Unavailable))
+    }
+}
@@ -17,15 +17,24 @@
 ///////////////////////////////////////////////////////////////////////////////
 //! Service and ServiceFactory implementation. Specialized wrapper over Substrate service.
 
-use robonomics_primitives::{AccountId, Balance, Block, Index};
+use crate::chain_spec::RobonomicsChain;
+use codec::{Decode, Encode};
+use jsonrpc_pubsub::manager::SubscriptionManager;
+use robonomics_primitives::{AccountId, Balance, Block, Hash, Index};
 use sc_client_api::{ExecutorProvider, RemoteBackend};
 use sc_consensus_babe;
+use sc_consensus_manual_seal::{self as manual_seal, rpc::EngineCommand};
 use sc_finality_grandpa::{self as grandpa, FinalityProofProvider as GrandpaFinalityProofProvider};
 use sc_network::NetworkService;
 use sc_service::{config::Configuration, error::Error as ServiceError, RpcHandlers, TaskManager};
 use sp_api::ConstructRuntimeApi;
-use sp_runtime::traits::{BlakeTwo256, Block as BlockT};
-use std::sync::Arc;
+use sp_blockchain::HeaderBackend;
+use sp_runtime::{
+    generic::BlockId,
+    traits::{BlakeTwo256, Block as BlockT},
+};
+use sp_transaction_pool::{InPoolTransaction, TransactionPool, TransactionSource};
+use std::{path::PathBuf, sync::Arc};
 
 type FullClient<Runtime, Executor> = sc_service::TFullClient<Block, Runtime, Executor>;
 type FullBackend = sc_service::TFullBackend<Block>;
@@ -36,10 +45,399 @@ type LightBackend = sc_service::TLightBackendWithHash<Block, BlakeTwo256>;
 type LightClient<Runtime, Executor> =
     sc_service::TLightClientWithBackend<Block, Runtime, Executor, LightBackend>;
 
+/// Block sealing mode of development chain.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Sealing {
+    /// Seal block on `engine_createBlock` RPC call.
+    Manual,
+    /// Seal block for every incoming transaction.
+    Instant,
+}
+
+impl std::str::FromStr for Sealing {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "manual" => Ok(Sealing::Manual),
+            "instant" => Ok(Sealing::Instant),
+            _ => Err(format!("Unknown sealing mode: {}", s)),
+        }
+    }
+}
+
+/// Robonomics specific options of the full node service.
+#[derive(Debug, Clone)]
+pub struct FullNodeOptions {
+    /// Development chain block sealing mode, BABE is used when `None`.
+    pub sealing: Option<Sealing>,
+    /// Proportion of the BABE slot dedicated to block proposing.
+    pub babe_slot_proportion: f32,
+    /// Run GRANDPA observer instead of the voter, ignored for authorities.
+    pub grandpa_observer: bool,
+    /// GRANDPA gossip duration, overrides chain spec value.
+    pub grandpa_gossip_duration: Option<std::time::Duration>,
+    /// GRANDPA justification generation period, overrides chain spec value.
+    pub grandpa_justification_period: Option<u32>,
+    /// GRANDPA votes on blocks that are at least given number of blocks behind the best one.
+    pub grandpa_before_best_by: robonomics_primitives::BlockNumber,
+    /// GRANDPA votes on at most three quarters of the unfinalized chain.
+    pub grandpa_three_quarters_rule: bool,
+    /// File to keep ready transactions between node restarts.
+    pub tx_pool_dump: Option<PathBuf>,
+    /// Unsafe RPC methods allowed for external RPC interfaces.
+    pub rpc_allow: Vec<String>,
+    /// Maximum number of robonomics RPC subscriptions per connection, unlimited when `None`.
+    pub rpc_max_subscriptions_per_connection: Option<usize>,
+    /// Transaction pool ready transactions limit, overrides chain family value.
+    pub pool_limit: Option<usize>,
+    /// Transaction pool size limit in kilobytes, overrides chain family value.
+    pub pool_kbytes: Option<usize>,
+    /// IPFS API URL to pin content of finalized datalog records, requires offchain workers.
+    pub ipfs_pin_url: Option<String>,
+    /// Run offchain workers only when keystore holds the key of given account.
+    pub offchain_worker_account: Option<AccountId>,
+    /// Slow down block authoring when finality lags behind the best block.
+    pub backoff_authoring: bool,
+    /// Maximum interval in blocks between authored blocks when backing off.
+    pub backoff_max_lag: Option<robonomics_primitives::BlockNumber>,
+    /// Maximum size of proposed block in bytes, runtime limit is used when `None`.
+    pub max_block_bytes: Option<usize>,
+    /// Author BABE blocks only when transaction pool has ready transactions.
+    pub author_on_demand: bool,
+    /// Maximum interval between authored blocks in on-demand mode, required by on-demand mode.
+    pub max_empty_interval: Option<std::time::Duration>,
+    /// Pause block authoring while finality lags behind the best block more than given blocks.
+    pub pause_authoring_on_lag: Option<robonomics_primitives::BlockNumber>,
+    /// NTP server to correct timestamp inherent by, local clock is used when `None`.
+    pub ntp_server: Option<String>,
+    /// Maximum local clock drift from NTP time that block authoring is allowed with.
+    pub max_clock_drift: std::time::Duration,
+    /// Serve RPC without block authoring and GRANDPA voting, regardless of node role.
+    pub rpc_only: bool,
+    /// Maximum size of robonomics pubsub notification in bytes.
+    pub pubsub_max_notification_size: u64,
+    /// Reserved peers of robonomics pubsub protocol.
+    pub pubsub_reserved_nodes: Vec<sc_network::config::MultiaddrWithPeerId>,
+    /// Seed of BABE and GRANDPA keys inserted into keystore before consensus start.
+    pub authority_seed: Option<String>,
+    /// NATS URL to publish finalized blocks into, e.g. `nats://127.0.0.1:4222`.
+    pub publish_finalized: Option<String>,
+    /// NATS subject of published finalized blocks.
+    pub publish_subject: String,
+    /// Suffix of keystore directory, e.g. `keystore-1` for `1`, default keystore when `None`.
+    pub keystore_suffix: Option<String>,
+    /// Wasm runtime pinned by `--pin-runtime`, node stops once chain upgrades beyond it.
+    pub pinned_runtime: Option<PathBuf>,
+    /// Unix socket path to stream imported block headers into as newline delimited JSON.
+    pub import_notify_socket: Option<std::path::PathBuf>,
+}
+
+impl Default for FullNodeOptions {
+    fn default() -> Self {
+        Self {
+            sealing: None,
+            babe_slot_proportion: 0.5,
+            grandpa_observer: false,
+            grandpa_gossip_duration: None,
+            grandpa_justification_period: None,
+            grandpa_before_best_by: 2,
+            grandpa_three_quarters_rule: true,
+            tx_pool_dump: None,
+            rpc_allow: Vec::new(),
+            rpc_max_subscriptions_per_connection: None,
+            pool_limit: None,
+            pool_kbytes: None,
+            ipfs_pin_url: None,
+            offchain_worker_account: None,
+            backoff_authoring: true,
+            backoff_max_lag: None,
+            max_block_bytes: None,
+            author_on_demand: false,
+            max_empty_interval: None,
+            pause_authoring_on_lag: None,
+            ntp_server: None,
+            max_clock_drift: std::time::Duration::from_millis(500),
+            rpc_only: false,
+            pubsub_max_notification_size: 1024 * 1024,
+            pubsub_reserved_nodes: Vec::new(),
+            authority_seed: None,
+            publish_finalized: None,
+            publish_subject: "robonomics.finalized".into(),
+            keystore_suffix: None,
+            pinned_runtime: None,
+            import_notify_socket: None,
+        }
+    }
+}
+
+/// Read version of wasm runtime by calling its `Core_version`.
+fn wasm_runtime_version<Executor>(code: Vec<u8>) -> Result<sp_api::RuntimeVersion, String>
+where
+    Executor: sc_executor::NativeExecutionDispatch + 'static,
+{
+    use sp_core::traits::{CodeExecutor, RuntimeCode, WrappedRuntimeCode};
+
+    let executor = sc_executor::NativeExecutor::<Executor>::new(
+        sc_executor::WasmExecutionMethod::Interpreted,
+        None,
+        1,
+    );
+    let hash = sp_core::hashing::blake2_256(&code).to_vec();
+    let runtime_code = RuntimeCode {
+        code_fetcher: &WrappedRuntimeCode(code.into()),
+        heap_pages: None,
+        hash,
+    };
+    let mut ext = sp_state_machine::BasicExternalities::default();
+    let version = executor
+        .call::<sp_core::NeverNativeValue, fn() -> _>(
+            &mut ext,
+            &runtime_code,
+            "Core_version",
+            &[],
+            false,
+            None,
+        )
+        .0
+        .map_err(|e| format!("{:?}", e))?
+        .into_encoded();
+    sp_api::RuntimeVersion::decode(&mut &version[..]).map_err(|e| e.to_string())
+}
+
+/// Refuse chain having blocks of other runtime versions and stop the node once chain is
+/// upgraded beyond the pinned runtime.
+///
+/// Client overrides on-chain code of the same spec version only, so blocks of other runtimes
+/// would be silently executed by on-chain code instead of pinned one.
+fn guard_pinned_runtime<Runtime, Executor>(
+    wasm: &std::path::Path,
+    client: Arc<FullClient<Runtime, Executor>>,
+    task_manager: &TaskManager,
+) -> Result<(), ServiceError>
+where
+    Runtime: ConstructRuntimeApi<Block, FullClient<Runtime, Executor>> + Send + Sync + 'static,
+    Runtime::RuntimeApi:
+        RuntimeApiCollection<StateBackend = sc_client_api::StateBackendFor<FullBackend, Block>>,
+    Executor: sc_executor::NativeExecutionDispatch + 'static,
+{
+    use futures::StreamExt;
+    use sc_client_api::BlockchainEvents;
+
+    let code = std::fs::read(wasm)
+        .map_err(|e| ServiceError::Other(format!("{}: {}", wasm.display(), e)))?;
+    let pinned = wasm_runtime_version::<Executor>(code)
+        .map_err(|e| ServiceError::Other(format!("Invalid pinned runtime: {}", e)))?
+        .spec_version;
+
+    // Spec version never decreases, so the chain is checked by its first and best blocks.
+    let info = client.info();
+    for hash in &[info.genesis_hash, info.best_hash] {
+        let spec_version = client
+            .runtime_version_at(&BlockId::hash(*hash))?
+            .spec_version;
+        if spec_version != pinned {
+            return Err(ServiceError::Other(format!(
+                "Chain has blocks of runtime spec_version {}, pinned runtime overrides \
+                 spec_version {} only",
+                spec_version, pinned
+            )));
+        }
+    }
+
+    task_manager
+        .spawn_essential_handle()
+        .spawn("pinned-runtime-guard", async move {
+            let mut imports = client.import_notification_stream();
+            while let Some(notification) = imports.next().await {
+                // Runtime of the imported block executes its children.
+                match client.runtime_version_at(&BlockId::hash(notification.hash)) {
+                    Ok(version) if version.spec_version == pinned => (),
+                    Ok(version) => {
+                        log::error!(
+                            target: "robonomics::service",
+                            "Runtime is upgraded to spec_version {} at {}, \
+                             pinned runtime {} can't execute next blocks",
+                            version.spec_version,
+                            notification.hash,
+                            pinned
+                        );
+                        return;
+                    }
+                    Err(e) => {
+                        log::error!(
+                            target: "robonomics::service",
+                            "Unable to check runtime version at {}: {:?}",
+                            notification.hash,
+                            e
+                        );
+                        return;
+                    }
+                }
+            }
+        });
+    Ok(())
+}
+
+/// Apply `--reserved-only` to notification protocols besides the default peer set.
+///
+/// Notice: GRANDPA and pubsub peer sets accept any peers otherwise, so reserved
+/// nodes of the default set become the only peers of all sets.
+pub fn set_reserved_only(network: &mut sc_network::config::NetworkConfiguration) {
+    use sc_network::config::NonReservedPeerMode;
+
+    if network.default_peers_set.non_reserved_mode != NonReservedPeerMode::Deny {
+        return;
+    }
+
+    let reserved_nodes = network.default_peers_set.reserved_nodes.clone();
+    for set in network.extra_sets.iter_mut() {
+        set.set_config.non_reserved_mode = NonReservedPeerMode::Deny;
+        for node in &reserved_nodes {
+            if !set.set_config.reserved_nodes.contains(node) {
+                set.set_config.reserved_nodes.push(node.clone());
+            }
+        }
+    }
+    log::info!(
+        target: "robonomics::service",
+        "Reserved only network mode, {} reserved nodes",
+        reserved_nodes.len()
+    );
+}
+
+/// Name of the robonomics pubsub notifications protocol.
+pub const PUBSUB_PROTOCOL_NAME: &str = "/robonomics/pubsub/1";
+
+/// Peer set of robonomics pubsub protocol, gossip channel for off-chain messaging of robots.
+pub fn pubsub_peers_set_config(
+    max_notification_size: u64,
+    reserved_nodes: Vec<sc_network::config::MultiaddrWithPeerId>,
+) -> sc_network::config::NonDefaultSetConfig {
+    sc_network::config::NonDefaultSetConfig {
+        notifications_protocol: PUBSUB_PROTOCOL_NAME.into(),
+        max_notification_size,
+        set_config: sc_network::config::SetConfig {
+            in_peers: 25,
+            out_peers: 25,
+            reserved_nodes,
+            non_reserved_mode: sc_network::config::NonReservedPeerMode::Accept,
+        },
+    }
+}
+
+/// Set transaction pool limits of the chain family, explicitly given values take precedence.
+pub fn set_pool_limits(
+    config: &mut Configuration,
+    pool_limit: Option<usize>,
+    pool_kbytes: Option<usize>,
+) {
+    let (default_limit, default_kbytes) = config.chain_spec.family().default_pool_limits();
+    let limit = pool_limit.unwrap_or(default_limit);
+    let bytes = pool_kbytes.unwrap_or(default_kbytes) * 1024;
+
+    let pool = &mut config.transaction_pool;
+    pool.ready.count = limit;
+    pool.ready.total_bytes = bytes;
+    pool.future.count = limit / 10;
+    pool.future.total_bytes = bytes / 10;
+}
+
+/// Set state pruning and cache size of the chain family, explicitly given values take precedence.
+///
+/// Authorities keep archive pruning required by substrate unless `--pruning` is given.
+pub fn set_storage_defaults(
+    config: &mut Configuration,
+    pruning_given: bool,
+    state_cache_size_given: bool,
+) {
+    let (pruning, state_cache_size) = config.chain_spec.family().default_storage();
+    if !pruning_given && !config.role.is_authority() {
+        config.state_pruning = match pruning {
+            Some(blocks) => sc_service::PruningMode::keep_blocks(blocks),
+            None => sc_service::PruningMode::ArchiveAll,
+        };
+    }
+    if !state_cache_size_given {
+        config.state_cache_size = state_cache_size;
+    }
+}
+
+/// Set peer counts of the chain family, explicitly given values take precedence.
+///
+/// Archive and RPC only nodes keep substrate defaults to serve more peers.
+pub fn set_peers_defaults(
+    config: &mut Configuration,
+    in_peers_given: bool,
+    out_peers_given: bool,
+    rpc_only: bool,
+) {
+    if rpc_only || (config.state_pruning.is_archive() && !config.role.is_authority()) {
+        return;
+    }
+    let (in_peers, out_peers) = config.chain_spec.family().default_peers();
+    let peers = &mut config.network.default_peers_set;
+    if !in_peers_given {
+        peers.in_peers = in_peers;
+    }
+    if !out_peers_given {
+        peers.out_peers = out_peers;
+    }
+}
+
+/// Add telemetry endpoints to configured ones, i.e. chain spec or `--telemetry-url` endpoints.
+pub fn add_telemetry_endpoints(
+    config: &mut Configuration,
+    endpoints: &[(String, u8)],
+) -> Result<(), String> {
+    if endpoints.is_empty() {
+        return Ok(());
+    }
+
+    let mut all: Vec<(String, u8)> = match &config.telemetry_endpoints {
+        Some(configured) => serde_json::to_value(configured)
+            .and_then(serde_json::from_value)
+            .map_err(|e| e.to_string())?,
+        None => Vec::new(),
+    };
+    all.extend_from_slice(endpoints);
+    let endpoints = sc_telemetry::TelemetryEndpoints::new(all).map_err(|e| e.to_string())?;
+    config.telemetry_endpoints = Some(endpoints);
+    Ok(())
+}
+
+/// Transaction pool keeper: stores ready transactions into file when dropped.
+struct TransactionPoolDump<Pool> {
+    pool: Arc<Pool>,
+    path: PathBuf,
+}
+
+impl<Pool: TransactionPool<Block = Block>> Drop for TransactionPoolDump<Pool> {
+    fn drop(&mut self) {
+        let ready: Vec<_> = self.pool.ready().map(|tx| tx.data().clone()).collect();
+        // Previous dump is replaced only by completely written one.
+        let tmp = self.path.with_extension("tmp");
+        match std::fs::write(&tmp, ready.encode()).and_then(|_| std::fs::rename(&tmp, &self.path)) {
+            Ok(()) => log::info!(
+                target: "robonomics::service",
+                "Saved {} ready transactions into {}",
+                ready.len(),
+                self.path.display()
+            ),
+            Err(e) => log::warn!(
+                target: "robonomics::service",
+                "Unable to save transaction pool: {}",
+                e
+            ),
+        }
+    }
+}
+
 /// A set of APIs that robonomics-like runtimes must implement.
 pub trait RuntimeApiCollection:
     sp_transaction_pool::runtime_api::TaggedTransactionQueue<Block>
     + sp_api::ApiExt<Block>
+    + sp_api::Core<Block>
     + sp_consensus_babe::BabeApi<Block>
     + sp_finality_grandpa::GrandpaApi<Block>
     + sp_block_builder::BlockBuilder<Block>
@@ -57,6 +455,7 @@ impl<Api> RuntimeApiCollection for Api
 where
     Api: sp_transaction_pool::runtime_api::TaggedTransactionQueue<Block>
         + sp_api::ApiExt<Block>
+        + sp_api::Core<Block>
         + sp_consensus_babe::BabeApi<Block>
         + sp_finality_grandpa::GrandpaApi<Block>
         + sp_block_builder::BlockBuilder<Block>
@@ -71,6 +470,10 @@ where
 
 pub fn new_partial<Runtime, Executor>(
     config: &Configuration,
+    sealing: Option<Sealing>,
+    rpc_allow: Vec<String>,
+    rpc_max_subscriptions_per_connection: Option<usize>,
+    clock: Option<crate::ntp::NtpClock>,
 ) -> Result<
     sc_service::PartialComponents<
         FullClient<Runtime, Executor>,
@@ -79,7 +482,11 @@ pub fn new_partial<Runtime, Executor>(
         sp_consensus::DefaultImportQueue<Block, FullClient<Runtime, Executor>>,
         sc_transaction_pool::FullPool<Block, FullClient<Runtime, Executor>>,
         (
-            impl Fn(node_rpc::DenyUnsafe, sc_rpc::SubscriptionTaskExecutor) -> node_rpc::IoHandler,
+            impl Fn(
+                node_rpc::DenyUnsafe,
+                sc_rpc::SubscriptionTaskExecutor,
+                Option<node_rpc::status::StatusDeps>,
+            ) -> node_rpc::IoHandler,
             (
                 sc_consensus_babe::BabeBlockImport<
                     Block,
@@ -91,6 +498,7 @@ pub fn new_partial<Runtime, Executor>(
             ),
             grandpa::SharedVoterState,
             Option<sc_telemetry::Telemetry>,
+            Option<futures::channel::mpsc::Receiver<EngineCommand<Hash>>>,
         ),
     >,
     ServiceError,
@@ -111,12 +519,22 @@ where
             Ok((worker, telemetry))
         })
         .transpose()?;
-    let (client, backend, keystore_container, task_manager) =
+    let (client, backend, mut keystore_container, task_manager) =
         sc_service::new_full_parts::<Block, Runtime, Executor>(
             &config,
             telemetry.as_ref().map(|(_, telemetry)| telemetry.handle()),
         )?;
 
+    // Consensus keystore is replaced by remote signer, e.g. given by `--remote-signer-url`.
+    if let Some(url) = &config.keystore_remote {
+        let signer = crate::remote_signer::RemoteSigner::connect(url, task_manager.spawn_handle())
+            .map_err(|e| {
+                ServiceError::Other(format!("Unable to use remote signer {}: {}", url, e))
+            })?;
+        log::info!(target: "robonomics::service", "Using remote signer {}", url);
+        keystore_container.set_remote_keystore(Arc::new(signer));
+    }
+
     let client = Arc::new(client);
     let select_chain = sc_consensus::LongestChain::new(backend.clone());
 
@@ -141,35 +559,66 @@ where
     )?;
     let justification_import = grandpa_block_import.clone();
 
-    let (block_import, babe_link) = sc_consensus_babe::block_import(
-        sc_consensus_babe::Config::get_or_compute(&*client)?,
-        grandpa_block_import,
-        client.clone(),
-    )?;
+    let babe_config = sc_consensus_babe::Config::get_or_compute(&*client)?;
+    // Pinned epoch duration should be applied to the runtime by genesis storage.
+    if let Some(duration) = crate::chain_spec::Extensions::try_get(&config.chain_spec)
+        .and_then(|e| e.babe_epoch_duration)
+    {
+        if babe_config.epoch_length != duration {
+            return Err(ServiceError::Other(format!(
+                "BABE epoch duration {} doesn't match pinned by chain spec {}, \
+                 genesis storage is pinned by `build-spec`",
+                babe_config.epoch_length, duration
+            )));
+        }
+    }
 
-    let slot_duration = babe_link.config().slot_duration();
-    let import_queue = sc_consensus_babe::import_queue(
-        babe_link.clone(),
-        block_import.clone(),
-        Some(Box::new(justification_import)),
-        client.clone(),
-        select_chain.clone(),
-        move |_, ()| async move {
-            let timestamp = sp_timestamp::InherentDataProvider::from_system_time();
-            let slot =
-                sp_consensus_babe::inherents::InherentDataProvider::from_timestamp_and_duration(
-                    *timestamp,
-                    slot_duration,
+    let (block_import, babe_link) =
+        sc_consensus_babe::block_import(babe_config, grandpa_block_import, client.clone())?;
+
+    let import_queue = if sealing.is_some() {
+        manual_seal::import_queue(
+            Box::new(block_import.clone()),
+            &task_manager.spawn_essential_handle(),
+            config.prometheus_registry(),
+        )
+    } else {
+        let slot_duration = babe_link.config().slot_duration();
+        sc_consensus_babe::import_queue(
+            babe_link.clone(),
+            block_import.clone(),
+            Some(Box::new(justification_import)),
+            client.clone(),
+            select_chain.clone(),
+            move |_, ()| {
+                let timestamp = clock.as_ref().map_or_else(
+                    sp_timestamp::InherentDataProvider::from_system_time,
+                    crate::ntp::NtpClock::timestamp,
                 );
-            let uncles =
-                sp_authorship::InherentDataProvider::<<Block as BlockT>::Header>::check_inherents();
-            Ok((timestamp, slot, uncles))
-        },
-        &task_manager.spawn_essential_handle(),
-        config.prometheus_registry(),
-        sp_consensus::CanAuthorWithNativeVersion::new(client.executor().clone()),
-        telemetry.as_ref().map(|x| x.handle()),
-    )?;
+                async move {
+                    let slot =
+                        sp_consensus_babe::inherents::InherentDataProvider::from_timestamp_and_duration(
+                            *timestamp,
+                            slot_duration,
+                        );
+                    let uncles =
+                        sp_authorship::InherentDataProvider::<<Block as BlockT>::Header>::check_inherents();
+                    Ok((timestamp, slot, uncles))
+                }
+            },
+            &task_manager.spawn_essential_handle(),
+            config.prometheus_registry(),
+            sp_consensus::CanAuthorWithNativeVersion::new(client.executor().clone()),
+            telemetry.as_ref().map(|x| x.handle()),
+        )?
+    };
+
+    let (command_sink, commands_stream) = if let Some(Sealing::Manual) = sealing {
+        let (sink, stream) = futures::channel::mpsc::channel(1024);
+        (Some(sink), Some(stream))
+    } else {
+        (None, None)
+    };
 
     let import_setup = (block_import, grandpa_link, babe_link);
 
@@ -194,30 +643,70 @@ where
         let select_chain = select_chain.clone();
         let keystore = keystore_container.sync_keystore();
         let chain_spec = config.chain_spec.cloned_box();
+        let subscriptions_limit =
+            node_rpc::subscriptions::SubscriptionsLimit::new(rpc_max_subscriptions_per_connection);
 
-        let rpc_extensions_builder = move |deny_unsafe, subscription_executor| {
-            let deps = node_rpc::FullDeps {
-                client: client.clone(),
-                pool: pool.clone(),
-                select_chain: select_chain.clone(),
-                chain_spec: chain_spec.cloned_box(),
-                deny_unsafe,
-                babe: node_rpc::BabeDeps {
-                    babe_config: babe_config.clone(),
-                    shared_epoch_changes: shared_epoch_changes.clone(),
-                    keystore: keystore.clone(),
-                },
-                grandpa: node_rpc::GrandpaDeps {
-                    shared_voter_state: shared_voter_state.clone(),
-                    shared_authority_set: shared_authority_set.clone(),
-                    justification_stream: justification_stream.clone(),
-                    subscription_executor,
-                    finality_provider: finality_proof_provider.clone(),
-                },
-            };
+        let rpc_extensions_builder =
+            move |deny_unsafe,
+                  subscription_executor: sc_rpc::SubscriptionTaskExecutor,
+                  status: Option<node_rpc::status::StatusDeps>| {
+                let create_full = |deny_unsafe, subscription_executor| {
+                    let deps = node_rpc::FullDeps {
+                        client: client.clone(),
+                        pool: pool.clone(),
+                        select_chain: select_chain.clone(),
+                        chain_spec: chain_spec.cloned_box(),
+                        deny_unsafe,
+                        babe: node_rpc::BabeDeps {
+                            babe_config: babe_config.clone(),
+                            shared_epoch_changes: shared_epoch_changes.clone(),
+                            keystore: keystore.clone(),
+                        },
+                        grandpa: node_rpc::GrandpaDeps {
+                            shared_voter_state: shared_voter_state.clone(),
+                            shared_authority_set: shared_authority_set.clone(),
+                            justification_stream: justification_stream.clone(),
+                            subscription_executor,
+                            finality_provider: finality_proof_provider.clone(),
+                        },
+                        command_sink: command_sink.clone(),
+                        status: status.clone(),
+                        subscriptions_limit: subscriptions_limit.clone(),
+                    };
 
-            node_rpc::create_full(deps)
-        };
+                    node_rpc::create_full(deps)
+                };
+
+                let mut io = create_full(deny_unsafe, subscription_executor.clone());
+
+                if matches!(deny_unsafe, node_rpc::DenyUnsafe::Yes) && !rpc_allow.is_empty() {
+                    // Allowed methods are served by handlers with unsafe calls enabled,
+                    // they override default `author` and node specific handlers.
+                    use sc_rpc::author::AuthorApi;
+
+                    let mut unrestricted = jsonrpc_core::MetaIoHandler::default();
+                    unrestricted.extend_with(AuthorApi::to_delegate(sc_rpc::author::Author::new(
+                        client.clone(),
+                        pool.clone(),
+                        SubscriptionManager::new(Arc::new(subscription_executor.clone())),
+                        keystore.clone(),
+                        node_rpc::DenyUnsafe::No,
+                    )));
+                    // Node handlers go last to keep limited extrinsic watching.
+                    unrestricted.extend_with(jsonrpc_core::MetaIoHandler::from(create_full(
+                        node_rpc::DenyUnsafe::No,
+                        subscription_executor,
+                    )));
+
+                    io.extend_with(
+                        unrestricted
+                            .into_iter()
+                            .filter(|(method, _)| rpc_allow.contains(method)),
+                    );
+                }
+
+                io
+            };
 
         (rpc_extensions_builder, rpc_setup)
     };
@@ -230,13 +719,20 @@ where
         select_chain,
         import_queue,
         transaction_pool,
-        other: (rpc_extensions_builder, import_setup, rpc_setup, telemetry),
+        other: (
+            rpc_extensions_builder,
+            import_setup,
+            rpc_setup,
+            telemetry,
+            commands_stream,
+        ),
     })
 }
 
 /// Creates a full service from the configuration.
 pub fn new_full_base<Runtime, Executor>(
     mut config: Configuration,
+    options: FullNodeOptions,
 ) -> Result<
     (
         TaskManager,
@@ -252,6 +748,62 @@ where
         RuntimeApiCollection<StateBackend = sc_client_api::StateBackendFor<FullBackend, Block>>,
     Executor: sc_executor::NativeExecutionDispatch + 'static,
 {
+    let FullNodeOptions {
+        sealing,
+        babe_slot_proportion,
+        grandpa_observer,
+        grandpa_gossip_duration,
+        grandpa_justification_period,
+        grandpa_before_best_by,
+        grandpa_three_quarters_rule,
+        tx_pool_dump,
+        rpc_allow,
+        rpc_max_subscriptions_per_connection,
+        pool_limit,
+        pool_kbytes,
+        ipfs_pin_url,
+        offchain_worker_account,
+        backoff_authoring,
+        backoff_max_lag,
+        max_block_bytes,
+        author_on_demand,
+        max_empty_interval,
+        pause_authoring_on_lag,
+        ntp_server,
+        max_clock_drift,
+        rpc_only,
+        pubsub_max_notification_size,
+        pubsub_reserved_nodes,
+        authority_seed,
+        publish_finalized,
+        publish_subject,
+        keystore_suffix,
+        import_notify_socket,
+        pinned_runtime,
+    } = options;
+
+    set_pool_limits(&mut config, pool_limit, pool_kbytes);
+
+    // Clock offset is synced every 10 minutes, drift is checked before block authoring.
+    let clock = ntp_server
+        .as_ref()
+        .map(|_| crate::ntp::NtpClock::new(max_clock_drift));
+
+    // Validators sharing the base path keep their keys isolated.
+    if let Some(suffix) = keystore_suffix {
+        if let sc_service::config::KeystoreConfig::Path { path, .. } = &mut config.keystore {
+            let name = path
+                .file_name()
+                .map_or_else(|| "keystore".into(), |n| n.to_string_lossy().into_owned());
+            path.set_file_name(format!("{}-{}", name, suffix));
+            log::info!(
+                target: "robonomics::service",
+                "Using keystore {}",
+                path.display()
+            );
+        }
+    }
+
     let sc_service::PartialComponents {
         client,
         backend,
@@ -260,17 +812,107 @@ where
         keystore_container,
         select_chain,
         transaction_pool,
-        other: (rpc_extensions_builder, import_setup, rpc_setup, mut telemetry),
-    } = new_partial(&config)?;
+        other: (rpc_extensions_builder, import_setup, rpc_setup, mut telemetry, commands_stream),
+    } = new_partial(
+        &config,
+        sealing,
+        rpc_allow,
+        rpc_max_subscriptions_per_connection,
+        clock.clone(),
+    )?;
 
     let shared_voter_state = rpc_setup;
 
+    // Sync task stops with the node, its UDP queries are blocking.
+    if let (Some(clock), Some(server)) = (&clock, ntp_server) {
+        let sync = clock
+            .clone()
+            .sync(server, std::time::Duration::from_secs(600));
+        task_manager
+            .spawn_handle()
+            .spawn_blocking("ntp-clock", sync);
+    }
+
+    if let Some(wasm) = pinned_runtime {
+        guard_pinned_runtime::<Runtime, Executor>(&wasm, client.clone(), &task_manager)?;
+    }
+
+    if let Some(seed) = authority_seed {
+        use sp_keystore::SyncCryptoStore;
+
+        let keystore = keystore_container.sync_keystore();
+        SyncCryptoStore::sr25519_generate_new(&*keystore, sp_consensus_babe::KEY_TYPE, Some(&seed))
+            .map_err(|e| ServiceError::Other(format!("Unable to insert BABE key: {}", e)))?;
+        SyncCryptoStore::ed25519_generate_new(
+            &*keystore,
+            sp_finality_grandpa::KEY_TYPE,
+            Some(&seed),
+        )
+        .map_err(|e| ServiceError::Other(format!("Unable to insert GRANDPA key: {}", e)))?;
+    }
+
+    if let Some(path) = tx_pool_dump {
+        let restored = std::fs::read(&path).ok().and_then(|data| {
+            Vec::<<Block as BlockT>::Extrinsic>::decode(&mut &data[..])
+                .map_err(|e| {
+                    log::warn!(
+                        target: "robonomics::service",
+                        "Invalid transaction pool dump {}: {}, removed",
+                        path.display(),
+                        e
+                    );
+                    let _ = std::fs::remove_file(&path);
+                })
+                .ok()
+        });
+        if let Some(xts) = restored {
+            let at = BlockId::hash(client.info().best_hash);
+            let pool = transaction_pool.clone();
+            let dump = path.clone();
+            task_manager
+                .spawn_handle()
+                .spawn("transaction-pool-restore", async move {
+                    match pool.submit_at(&at, TransactionSource::External, xts).await {
+                        Ok(results) => log::info!(
+                            target: "robonomics::service",
+                            "Restored {} of {} transactions from pool dump",
+                            results.iter().filter(|r| r.is_ok()).count(),
+                            results.len(),
+                        ),
+                        Err(e) => log::warn!(
+                            target: "robonomics::service",
+                            "Unable to restore transaction pool: {}",
+                            e
+                        ),
+                    }
+                    // Restored dump shouldn't be submitted again after unclean shutdown.
+                    if let Err(e) = std::fs::remove_file(&dump) {
+                        log::warn!(
+                            target: "robonomics::service",
+                            "Unable to remove {}: {}",
+                            dump.display(),
+                            e
+                        );
+                    }
+                });
+        }
+
+        task_manager.keep_alive(TransactionPoolDump {
+            pool: transaction_pool.clone(),
+            path,
+        });
+    }
+
     config
         .network
         .extra_sets
         .push(grandpa::grandpa_peers_set_config());
+    config.network.extra_sets.push(pubsub_peers_set_config(
+        pubsub_max_notification_size,
+        pubsub_reserved_nodes,
+    ));
+    set_reserved_only(&mut config.network);
 
-    #[cfg(feature = "cli")]
     config.network.request_response_protocols.push(
         sc_finality_grandpa_warp_sync::request_response_config_for_chain(
             &config,
@@ -291,22 +933,74 @@ where
             block_announce_validator_builder: None,
         })?;
 
-    if config.offchain_worker.enabled {
+    // Offchain workers run on designated nodes only, e.g. one IPFS pinner of the fleet.
+    let is_offchain_worker_node = match &offchain_worker_account {
+        Some(account) => {
+            use sp_keystore::SyncCryptoStore;
+            let key = (account.encode(), sp_core::crypto::key_types::ACCOUNT);
+            let has_key = SyncCryptoStore::has_keys(&*keystore_container.sync_keystore(), &[key]);
+            if !has_key {
+                log::info!(
+                    target: "robonomics::service",
+                    "Offchain workers are disabled, keystore has no key of {}",
+                    account
+                );
+            }
+            has_key
+        }
+        None => true,
+    };
+
+    if config.offchain_worker.enabled && is_offchain_worker_node {
         sc_service::build_offchain_workers(
             &config,
             task_manager.spawn_handle(),
             client.clone(),
             network.clone(),
         );
+
+        if let Some(url) = ipfs_pin_url {
+            task_manager
+                .spawn_handle()
+                .spawn("ipfs-pin", crate::ipfs::pin_datalog(client.clone(), url));
+        }
+    } else if !config.offchain_worker.enabled && ipfs_pin_url.is_some() {
+        log::warn!(
+            target: "robonomics::service",
+            "IPFS pinning is disabled, offchain workers should be enabled for it"
+        );
     }
 
     let role = config.role.clone();
     let force_authoring = config.force_authoring;
-    let backoff_authoring_blocks =
-        Some(sc_consensus_slots::BackoffAuthoringOnFinalizedHeadLagging::default());
+    // Backing off keeps chain from growing on finality stall, but slower block production
+    // could delay the finality recovery, so it could be disabled or tuned.
+    let backoff_lagging = if backoff_authoring {
+        let mut backoff = sc_consensus_slots::BackoffAuthoringOnFinalizedHeadLagging::default();
+        if let Some(max_lag) = backoff_max_lag {
+            backoff.max_interval = max_lag;
+        }
+        Some(backoff)
+    } else {
+        None
+    };
     let name = config.network.node_name.clone();
     let enable_grandpa = !config.disable_grandpa;
+    let (gossip_duration_ms, justification_period) =
+        crate::chain_spec::Extensions::try_get(&config.chain_spec)
+            .map(|e| (e.grandpa_gossip_duration_ms, e.grandpa_justification_period))
+            .unwrap_or_default();
+    let gossip_duration = grandpa_gossip_duration
+        .or_else(|| gossip_duration_ms.map(std::time::Duration::from_millis))
+        .unwrap_or_else(|| std::time::Duration::from_millis(333));
+    let justification_period = grandpa_justification_period
+        .or(justification_period)
+        .unwrap_or(512);
     let prometheus_registry = config.prometheus_registry().cloned();
+    let status = node_rpc::status::StatusDeps {
+        system_rpc_tx: system_rpc_tx.clone(),
+        family: config.chain_spec.family().to_string(),
+    };
 
     sc_service::spawn_tasks(sc_service::SpawnTasksParams {
         config,
@@ -314,7 +1008,9 @@ where
         client: client.clone(),
         keystore: keystore_container.sync_keystore(),
         network: network.clone(),
-        rpc_extensions_builder: Box::new(rpc_extensions_builder),
+        rpc_extensions_builder: Box::new(move |deny_unsafe, subscription_executor| {
+            rpc_extensions_builder(deny_unsafe, subscription_executor, Some(status.clone()))
+        }),
         transaction_pool: transaction_pool.clone(),
         task_manager: &mut task_manager,
         on_demand: None,
@@ -325,20 +1021,151 @@ where
 
     let (block_import, grandpa_link, babe_link) = import_setup;
 
-    if let sc_service::config::Role::Authority { .. } = &role {
-        let proposer = sc_basic_authorship::ProposerFactory::new(
+    if enable_grandpa {
+        task_manager.spawn_handle().spawn(
+            "grandpa-authority-set-monitor",
+            crate::authority_set::monitor(
+                client.clone(),
+                grandpa_link.shared_authority_set().clone(),
+                prometheus_registry.clone(),
+            ),
+        );
+    }
+
+    if let Some(url) = publish_finalized {
+        // Broker connection is blocking, so task runs on dedicated thread.
+        task_manager.spawn_handle().spawn_blocking(
+            "nats-publish-finalized",
+            crate::nats::publish_finalized(client.clone(), url, publish_subject),
+        );
+    }
+
+    #[cfg(unix)]
+    if let Some(path) = import_notify_socket {
+        // Socket writes are non-blocking, but accept and bind are not.
+        task_manager.spawn_handle().spawn_blocking(
+            "import-notify-socket",
+            crate::import_notify::serve_imports(client.clone(), path),
+        );
+    }
+
+    if rpc_only {
+        // Chain is followed by block import and justifications from the network.
+        network_starter.start_network();
+        return Ok((task_manager, client, network, transaction_pool));
+    }
+
+    if let Some(sealing) = sealing {
+        let mut proposer = sc_basic_authorship::ProposerFactory::new(
             task_manager.spawn_handle(),
             client.clone(),
             transaction_pool.clone(),
             prometheus_registry.as_ref(),
             telemetry.as_ref().map(|x| x.handle()),
         );
+        if let Some(limit) = max_block_bytes {
+            // Proposal only, imported blocks are checked against runtime limit.
+            proposer.set_default_block_size_limit(limit);
+        }
+
+        let consensus_data_provider = manual_seal::consensus::babe::BabeConsensusDataProvider::new(
+            client.clone(),
+            keystore_container.sync_keystore(),
+            babe_link.epoch_changes().clone(),
+            babe_link.config().genesis_authorities.clone(),
+        )
+        .map_err(|e| ServiceError::Other(format!("{:?}", e)))?;
+
+        let client_clone = client.clone();
+        let create_inherent_data_providers = move |_, ()| {
+            let client_clone = client_clone.clone();
+            async move {
+                let timestamp =
+                    manual_seal::consensus::babe::SlotTimestampProvider::new(client_clone)
+                        .map_err(|e| format!("{:?}", e))?;
+                let slot = sp_consensus_babe::inherents::InherentDataProvider::new(
+                    timestamp.slot().into(),
+                );
+                Ok((timestamp, slot))
+            }
+        };
+
+        let authorship_future = match sealing {
+            Sealing::Manual => {
+                let commands_stream =
+                    commands_stream.expect("commands stream is created for manual sealing; qed");
+                futures::future::Either::Left(manual_seal::run_manual_seal(
+                    manual_seal::ManualSealParams {
+                        block_import,
+                        env: proposer,
+                        client: client.clone(),
+                        pool: transaction_pool.pool().clone(),
+                        commands_stream,
+                        select_chain,
+                        consensus_data_provider: Some(Box::new(consensus_data_provider)),
+                        create_inherent_data_providers,
+                    },
+                ))
+            }
+            Sealing::Instant => futures::future::Either::Right(manual_seal::run_instant_seal(
+                manual_seal::InstantSealParams {
+                    block_import,
+                    env: proposer,
+                    client: client.clone(),
+                    pool: transaction_pool.pool().clone(),
+                    select_chain,
+                    consensus_data_provider: Some(Box::new(consensus_data_provider)),
+                    create_inherent_data_providers,
+                },
+            )),
+        };
+
+        task_manager
+            .spawn_essential_handle()
+            .spawn_blocking("manual-seal", authorship_future);
+    } else if let sc_service::config::Role::Authority { .. } = &role {
+        let mut proposer = sc_basic_authorship::ProposerFactory::new(
+            task_manager.spawn_handle(),
+            client.clone(),
+            transaction_pool.clone(),
+            prometheus_registry.as_ref(),
+            telemetry.as_ref().map(|x| x.handle()),
+        );
+        if let Some(limit) = max_block_bytes {
+            // Proposal only, imported blocks are checked against runtime limit.
+            proposer.set_default_block_size_limit(limit);
+        }
+        let proposer =
+            crate::proposer::MeteredProposerFactory::new(proposer, prometheus_registry.as_ref())?;
 
         let can_author_with =
             sp_consensus::CanAuthorWithNativeVersion::new(client.executor().clone());
 
         let client_clone = client.clone();
         let slot_duration = babe_link.config().slot_duration();
+        let mut backoff_authoring_blocks = crate::proposer::AuthoringBackoff::new(
+            backoff_lagging,
+            babe_link.config().epoch_length,
+        );
+        if author_on_demand {
+            // Backoff never exceeds half of epoch, so BABE epochs are never left without blocks.
+            let interval = max_empty_interval.ok_or_else(|| {
+                ServiceError::Other("Max empty interval is required to author on demand".into())
+            })?;
+            let max_empty_slots = interval.as_millis() as u64 / slot_duration.as_millis() as u64;
+            if max_empty_slots >= backoff_authoring_blocks.max_skipped_slots() {
+                return Err(ServiceError::Other(format!(
+                    "Max empty interval should be less than half of epoch: {} slots",
+                    backoff_authoring_blocks.max_skipped_slots()
+                )));
+            }
+            backoff_authoring_blocks =
+                backoff_authoring_blocks.on_demand(transaction_pool.clone(), max_empty_slots);
+        }
+        if let Some(max_lag) = pause_authoring_on_lag {
+            backoff_authoring_blocks =
+                backoff_authoring_blocks.pause_on_lag(max_lag, prometheus_registry.as_ref())?;
+        }
         let babe_config = sc_consensus_babe::BabeParams {
             keystore: keystore_container.sync_keystore(),
             client: client.clone(),
@@ -348,12 +1175,20 @@ where
             sync_oracle: network.clone(),
             create_inherent_data_providers: move |parent, ()| {
                 let client_clone = client_clone.clone();
+                let clock = clock.clone();
                 async move {
                     let uncles = sc_consensus_uncles::create_uncles_inherent_data_provider(
                         &*client_clone,
                         parent,
                     )?;
-                    let timestamp = sp_timestamp::InherentDataProvider::from_system_time();
+                    // Slot isn't claimed by timestamp of drifted clock.
+                    let timestamp = match &clock {
+                        Some(clock) => {
+                            clock.check_drift()?;
+                            clock.timestamp()
+                        }
+                        None => sp_timestamp::InherentDataProvider::from_system_time(),
+                    };
                     let slot =
                         sp_consensus_babe::inherents::InherentDataProvider::from_timestamp_and_duration(
                             *timestamp,
@@ -363,10 +1198,12 @@ where
                 }
             },
             force_authoring,
-            backoff_authoring_blocks,
+            backoff_authoring_blocks: Some(backoff_authoring_blocks),
             babe_link,
             can_author_with,
-            block_proposal_slot_portion: sc_consensus_babe::SlotProportion::new(0.5),
+            block_proposal_slot_portion: sc_consensus_babe::SlotProportion::new(
+                babe_slot_proportion,
+            ),
             telemetry: telemetry.as_ref().map(|x| x.handle()),
         };
 
@@ -384,29 +1221,44 @@ where
         None
     };
 
+    // authorities always run the full voter.
+    let observer_enabled = grandpa_observer && !role.is_authority();
+
     let config = grandpa::Config {
-        // FIXME #1578 make this available through chainspec
-        gossip_duration: std::time::Duration::from_millis(333),
-        justification_period: 512,
+        gossip_duration,
+        justification_period,
         name: Some(name),
-        observer_enabled: false,
+        observer_enabled,
         local_role: role,
         keystore,
         telemetry: telemetry.as_ref().map(|x| x.handle()),
     };
 
-    if enable_grandpa {
+    if enable_grandpa && observer_enabled {
+        // start the GRANDPA observer: it follows finality without voting
+        // and suits non-authority nodes with constrained bandwidth.
+        task_manager.spawn_essential_handle().spawn_blocking(
+            "grandpa-observer",
+            grandpa::run_grandpa_observer(config, grandpa_link, network.clone())?,
+        );
+    } else if enable_grandpa {
         // start the full GRANDPA voter
         // NOTE: non-authorities could run the GRANDPA observer protocol, but at
         // this point the full voter should provide better guarantees of block
         // and vote data availability than the observer. The observer has not
         // been tested extensively yet and having most nodes in a network run it
         // could lead to finality stalls.
+        let mut voting_rule = grandpa::VotingRulesBuilder::new()
+            .add(grandpa::BeforeBestBlockBy(grandpa_before_best_by));
+        if grandpa_three_quarters_rule {
+            voting_rule = voting_rule.add(grandpa::ThreeQuartersOfTheUnfinalizedChain);
+        }
+
         let grandpa_config = grandpa::GrandpaParams {
             config,
             link: grandpa_link,
             network: network.clone(),
-            voting_rule: grandpa::VotingRulesBuilder::default().build(),
+            voting_rule: voting_rule.build(),
             prometheus_registry,
             shared_voter_state,
             telemetry: telemetry.as_ref().map(|x| x.handle()),
@@ -555,6 +1407,10 @@ where
         fetcher: on_demand.clone(),
         client: client.clone(),
         pool: transaction_pool.clone(),
+        status: node_rpc::status::StatusDeps {
+            system_rpc_tx: system_rpc_tx.clone(),
+            family: config.chain_spec.family().to_string(),
+        },
     };
 
     let rpc_extensions = node_rpc::create_light(light_deps);
@@ -604,11 +1460,22 @@ pub mod robonomics {
     );
 
     /// Create a new Robonomics service for a full node.
-    pub fn new_full(config: Configuration) -> Result<TaskManager> {
-        super::new_full_base::<RuntimeApi, Executor>(config)
+    pub fn new_full(config: Configuration, options: super::FullNodeOptions) -> Result<TaskManager> {
+        super::new_full_base::<RuntimeApi, Executor>(config, options)
             .map(|(task_manager, _, _, _)| task_manager)
     }
 
+    /// Create a new Robonomics development authority with keys of given seed, e.g. `//Alice`.
+    ///
+    /// Keys are inserted before consensus start, so in-memory keystore of tests could be used.
+    pub fn new_full_dev(config: Configuration, authority_seed: &str) -> Result<TaskManager> {
+        let options = super::FullNodeOptions {
+            authority_seed: Some(authority_seed.into()),
+            ..Default::default()
+        };
+        new_full(config, options)
+    }
+
     pub fn new_light(config: Configuration) -> Result<(TaskManager, RpcHandlers)> {
         super::new_light_base::<RuntimeApi, Executor>(config)
             .map(|(task_manager, rpc_handlers, _, _, _)| (task_manager, rpc_handlers))
@@ -19,7 +19,16 @@
 
 #![deny(missing_docs)]
 
-use crate::error::Result;
+use crate::error::{Error, Result};
+use async_std::task;
+use futures::prelude::*;
+use robonomics_io::{
+    sink::{gpio, virt as virt_sink},
+    source::{mqtt, virt},
+};
+use robonomics_protocol::subxt::{datalog, launch, rws, ConnectPolicy};
+use sp_core::{crypto::Ss58Codec, sr25519, Pair};
+use std::time::Duration;
 
 /// Substrate friendly CLI I/O subsystem interaction.
 #[derive(structopt::StructOpt, Debug)]
@@ -27,18 +36,365 @@ pub struct IoCmd {
     /// I/O device operation to run.
     #[structopt(subcommand)]
     pub operation: Operation,
+    /// Retry connection to Robonomics node API given times with exponential backoff.
+    #[structopt(long, value_name = "COUNT", default_value = "0")]
+    pub connect_retries: usize,
+    /// Timeout of single connection attempt to Robonomics node API, in seconds.
+    #[structopt(long, value_name = "SECS")]
+    pub connect_timeout: Option<u64>,
 }
 
 impl IoCmd {
     /// Run I/O operation on device.
     pub fn run(&self) -> Result<()> {
+        let policy = ConnectPolicy {
+            retries: self.connect_retries,
+            timeout: self.connect_timeout.map(Duration::from_secs),
+        };
+
         match &self.operation {
-            Operation::Read(source) => source.run(),
-            Operation::Write(sink) => sink.run(),
+            Operation::Read(source) => source.run(policy),
+            Operation::Write(sink) => sink.run(policy),
+            Operation::LaunchListener {
+                rpc,
+                account,
+                gpio: pin,
+                debounce,
+                reconnect,
+            } => {
+                let launches = virt::robot_launch(
+                    rpc.clone(),
+                    policy,
+                    account.clone(),
+                    Duration::from_secs(*reconnect),
+                )?;
+                let output = gpio::sysfs(*pin, Duration::from_millis(*debounce))?;
+                task::block_on(launches.map(Ok).forward(output))?;
+                Ok(())
+            }
+            Operation::RwsCall {
+                rpc,
+                suri,
+                subscription,
+                call,
+            } => {
+                let pair = sr25519::Pair::from_string(suri.as_str(), None)
+                    .map_err(|e| Error::Other(format!("Invalid secret URI: {:?}", e)))?;
+                let call = hex::decode(call.trim_start_matches("0x"))
+                    .map_err(|e| Error::Other(format!("Invalid call hex: {}", e)))?;
+                let hash = task::block_on(rws::call(
+                    pair,
+                    rpc.clone(),
+                    policy,
+                    subscription.clone(),
+                    call,
+                ))?;
+                println!("{}", hex::encode(hash));
+                Ok(())
+            }
+            Operation::RecordBatch { file, rpc, suri } => {
+                let pair = sr25519::Pair::from_string(suri.as_str(), None)
+                    .map_err(|e| Error::Other(format!("Invalid secret URI: {:?}", e)))?;
+                let records = read_readings(file)?;
+                let hashes =
+                    task::block_on(datalog::submit_batch(pair, rpc.clone(), policy, records))?;
+                for hash in hashes {
+                    println!("{}", hex::encode(hash));
+                }
+                Ok(())
+            }
+            Operation::VerifyEthSig {
+                message,
+                signature,
+                expected,
+                mapping,
+                last_seen,
+                max_age,
+                rpc,
+                suri,
+            } => {
+                let message = hex::decode(message.trim_start_matches("0x"))
+                    .map_err(|e| Error::Other(format!("Invalid message hex: {}", e)))?;
+                let signature = hex::decode(signature.trim_start_matches("0x"))
+                    .map_err(|e| Error::Other(format!("Invalid signature hex: {}", e)))?;
+                let expected = parse_eth_address(expected)?;
+                let signer = recover_eth_signer(&message, &signature)?;
+                if signer != expected {
+                    return Err(Error::Other(format!(
+                        "Message is signed by 0x{}, expected 0x{}",
+                        hex::encode(signer),
+                        hex::encode(expected)
+                    )));
+                }
+
+                let (param, time) = parse_eth_command(&message, unix_time()?, *max_age)?;
+                let mut seen = read_last_seen(last_seen)?;
+                check_replay(&seen, &signer, time)?;
+
+                let robot = read_eth_mapping(mapping)?
+                    .remove(&signer)
+                    .ok_or_else(|| format!("No account mapped to 0x{}", hex::encode(signer)))?;
+                let pair = sr25519::Pair::from_string(suri.as_str(), None)
+                    .map_err(|e| Error::Other(format!("Invalid secret URI: {:?}", e)))?;
+                let hash = task::block_on(launch::submit(
+                    pair,
+                    rpc.clone(),
+                    policy,
+                    robot,
+                    param,
+                    None,
+                ))?;
+                seen.insert(signer, time);
+                write_last_seen(last_seen, &seen)?;
+                println!("{}", hex::encode(hash));
+                Ok(())
+            }
+            Operation::MqttBridge {
+                broker,
+                topic,
+                rpc,
+                account,
+                suri,
+                twin_mapping,
+                ipfs,
+                ipfs_threshold,
+                keep_alive,
+            } => {
+                let pair = sr25519::Pair::from_string(suri.as_str(), None)
+                    .map_err(|e| Error::Other(format!("Invalid secret URI: {:?}", e)))?;
+                let expected =
+                    sr25519::Public::from_ss58check(account).map_err(|_| Error::Ss58CodecError)?;
+                if pair.public() != expected {
+                    return Err(Error::Other(format!(
+                        "Secret URI doesn't belong to account {}",
+                        account
+                    )));
+                }
+                let twins = match twin_mapping {
+                    Some(file) => read_twin_mapping(file)?,
+                    None => Default::default(),
+                };
+                let mut offload = ipfs
+                    .as_ref()
+                    .map(|uri| virt_sink::ipfs::<Vec<u8>>(uri.as_str()))
+                    .transpose()?
+                    .map(|(upload, cids)| (Box::pin(upload), Box::pin(cids)));
+                let mut messages = Box::pin(mqtt::subscribe(
+                    broker.clone(),
+                    topic.clone(),
+                    Duration::from_secs(*keep_alive),
+                )?);
+
+                task::block_on(async {
+                    while let Some(message) = messages.next().await {
+                        let mqtt::Message { topic, payload } = message?;
+                        let mut record = serde_json::json!({ "topic": topic });
+                        if let Some(twin) = twins.get(&topic) {
+                            record["twin"] = (*twin).into();
+                        }
+                        match &mut offload {
+                            Some((upload, cids)) if payload.len() > *ipfs_threshold => {
+                                upload.send(payload).await?;
+                                let cid = cids.next().await.ok_or("IPFS uploader stopped")??;
+                                record["cid"] = cid.into();
+                            }
+                            _ => {
+                                record["payload"] = match String::from_utf8(payload) {
+                                    Ok(text) => text,
+                                    Err(e) => format!("0x{}", hex::encode(e.into_bytes())),
+                                }
+                                .into();
+                            }
+                        }
+
+                        // Bridge keeps running when single record isn't accepted by node.
+                        let record = record.to_string().into_bytes();
+                        let submit =
+                            datalog::submit(pair.clone(), rpc.clone(), policy, record, None);
+                        match submit.await {
+                            Ok(hash) => println!("{}", hex::encode(hash)),
+                            Err(e) => log::warn!(
+                                target: "robonomics::io",
+                                "Unable to record message of {}: {}",
+                                topic,
+                                e
+                            ),
+                        }
+                    }
+                    Ok::<_, Error>(())
+                })
+            }
         }
     }
 }
 
+/// Read `{ "MQTT_TOPIC": TWIN_ID }` JSON mapping of MQTT topics to digital twins.
+fn read_twin_mapping(file: &std::path::Path) -> Result<std::collections::HashMap<String, u32>> {
+    let file = std::fs::File::open(file)?;
+    serde_json::from_reader(file)
+        .map_err(|e| Error::Other(format!("Invalid twin mapping file: {}", e)))
+}
+
+/// Read `timestamp,value,...` readings from CSV file with header into datalog records.
+fn read_readings(file: &std::path::Path) -> Result<Vec<Vec<u8>>> {
+    let mut reader = csv::Reader::from_path(file)
+        .map_err(|e| Error::Other(format!("Unable to open readings file: {}", e)))?;
+    let mut records = Vec::new();
+    for (line, row) in reader.records().enumerate() {
+        let row = row.map_err(|e| Error::Other(format!("Invalid readings file: {}", e)))?;
+        let timestamp = row.get(0).unwrap_or_default();
+        if row.len() < 2 || timestamp.parse::<u64>().is_err() {
+            return Err(Error::Other(format!(
+                "Reading {} should start with timestamp followed by value",
+                line + 1
+            )));
+        }
+        records.push(row.iter().collect::<Vec<_>>().join(",").into_bytes());
+    }
+    Ok(records)
+}
+
+/// Parse `0x` prefixed Ethereum address.
+fn parse_eth_address(address: &str) -> Result<[u8; 20]> {
+    let bytes = hex::decode(address.trim_start_matches("0x"))
+        .map_err(|e| Error::Other(format!("Invalid Ethereum address {}: {}", address, e)))?;
+    if bytes.len() != 20 {
+        return Err(Error::Other(format!(
+            "Ethereum address {} should be 20 bytes long",
+            address
+        )));
+    }
+    let mut eth_address = [0u8; 20];
+    eth_address.copy_from_slice(&bytes);
+    Ok(eth_address)
+}
+
+/// Recover Ethereum address of `personal_sign` (EIP-191) message signer.
+fn recover_eth_signer(message: &[u8], signature: &[u8]) -> Result<[u8; 20]> {
+    use sp_core::hashing::keccak_256;
+
+    if signature.len() != 65 {
+        return Err("Signature should be 65 bytes long".into());
+    }
+    let mut sig = [0u8; 65];
+    sig.copy_from_slice(signature);
+
+    let mut prefixed = format!("\x19Ethereum Signed Message:\n{}", message.len()).into_bytes();
+    prefixed.extend_from_slice(message);
+    let public = sp_io::crypto::secp256k1_ecdsa_recover(&sig, &keccak_256(&prefixed))
+        .map_err(|_| Error::Other("Unable to recover signer".into()))?;
+
+    // Ethereum address is the tail of uncompressed public key hash.
+    let mut eth_address = [0u8; 20];
+    eth_address.copy_from_slice(&keccak_256(&public)[12..]);
+    Ok(eth_address)
+}
+
+/// Read `{ "ETH_ADDRESS": "SS58" }` JSON mapping of Ethereum addresses to robot accounts.
+fn read_eth_mapping(file: &std::path::Path) -> Result<std::collections::HashMap<[u8; 20], String>> {
+    let file = std::fs::File::open(file)?;
+    let mapping: std::collections::HashMap<String, String> = serde_json::from_reader(file)
+        .map_err(|e| Error::Other(format!("Invalid mapping file: {}", e)))?;
+    mapping
+        .into_iter()
+        .map(|(address, account)| Ok((parse_eth_address(&address)?, account)))
+        .collect()
+}
+
+/// Maximal time in seconds that signed message could be ahead of local clock.
+const MAX_CLOCK_SKEW: u64 = 60;
+
+/// Current unix time in seconds.
+fn unix_time() -> Result<u64> {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .map_err(|e| Error::Other(format!("Invalid system time: {}", e)))
+}
+
+/// Parse `ON:<unix time>` or `OFF:<unix time>` launch command, returns launch parameter
+/// and message time. Messages older than `max_age` seconds are rejected.
+fn parse_eth_command(message: &[u8], now: u64, max_age: u64) -> Result<(bool, u64)> {
+    let message = std::str::from_utf8(message)
+        .map_err(|_| Error::Other("Launch command should be UTF-8 string".into()))?;
+    let mut parts = message.splitn(2, ':');
+    let param = match parts.next() {
+        Some("ON") => true,
+        Some("OFF") => false,
+        _ => {
+            return Err(Error::Other(format!(
+                "Unknown launch command: {}, expected ON or OFF",
+                message
+            )))
+        }
+    };
+    let time: u64 = parts
+        .next()
+        .and_then(|time| time.parse().ok())
+        .ok_or_else(|| Error::Other(format!("No unix time in launch command: {}", message)))?;
+    if time.saturating_add(max_age) < now {
+        return Err(Error::Other(format!(
+            "Launch command is stale: {}",
+            message
+        )));
+    }
+    if time > now.saturating_add(MAX_CLOCK_SKEW) {
+        return Err(Error::Other(format!(
+            "Launch command is from the future: {}",
+            message
+        )));
+    }
+    Ok((param, time))
+}
+
+/// Reject message that isn't newer than the last accepted message of the signer.
+fn check_replay(
+    seen: &std::collections::HashMap<[u8; 20], u64>,
+    signer: &[u8; 20],
+    time: u64,
+) -> Result<()> {
+    if seen.get(signer).map_or(false, |last| time <= *last) {
+        return Err(Error::Other(format!(
+            "Message of 0x{} is replayed, it isn't newer than the last accepted one",
+            hex::encode(signer)
+        )));
+    }
+    Ok(())
+}
+
+/// Read time of the last accepted message of each signer, empty when file is missing.
+fn read_last_seen(file: &std::path::Path) -> Result<std::collections::HashMap<[u8; 20], u64>> {
+    let file = match std::fs::File::open(file) {
+        Ok(file) => file,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Default::default()),
+        Err(e) => return Err(e.into()),
+    };
+    let seen: std::collections::HashMap<String, u64> = serde_json::from_reader(file)
+        .map_err(|e| Error::Other(format!("Invalid last seen file: {}", e)))?;
+    seen.into_iter()
+        .map(|(address, time)| Ok((parse_eth_address(&address)?, time)))
+        .collect()
+}
+
+/// Write time of the last accepted message of each signer.
+///
+/// Notice: File is replaced only by completely written one, so replay protection
+/// state isn't lost on failure.
+fn write_last_seen(
+    file: &std::path::Path,
+    seen: &std::collections::HashMap<[u8; 20], u64>,
+) -> Result<()> {
+    let seen: std::collections::HashMap<String, u64> = seen
+        .iter()
+        .map(|(address, time)| (format!("0x{}", hex::encode(address)), *time))
+        .collect();
+    let tmp = file.with_extension("tmp");
+    serde_json::to_writer(std::fs::File::create(&tmp)?, &seen)
+        .map_err(|e| Error::Other(format!("Unable to write last seen file: {}", e)))?;
+    std::fs::rename(&tmp, file)?;
+    Ok(())
+}
+
 /// I/O operation command.
 #[derive(structopt::StructOpt, Debug)]
 pub enum Operation {
@@ -46,4 +402,169 @@ pub enum Operation {
     Read(super::SourceCmd),
     /// Write information into device.
     Write(super::SinkCmd),
+    /// Toggle GPIO pin by finalized launch requests of the robot.
+    LaunchListener {
+        /// Robonomics node API endpoint.
+        #[structopt(long, value_name = "REMOTE_URI", default_value = "ws://127.0.0.1:9944")]
+        rpc: String,
+        /// Robot account address.
+        #[structopt(long, value_name = "ADDRESS")]
+        account: String,
+        /// Linux GPIO pin number.
+        #[structopt(long, value_name = "PIN")]
+        gpio: u32,
+        /// Minimal interval between pin toggles in milliseconds.
+        #[structopt(long, value_name = "MS", default_value = "500")]
+        debounce: u64,
+        /// Delay before reconnection when node drops, in seconds.
+        #[structopt(long, value_name = "SECS", default_value = "5")]
+        reconnect: u64,
+    },
+    /// Send runtime call on behalf of RWS subscription, signed by subscription device.
+    RwsCall {
+        /// Robonomics node API endpoint.
+        #[structopt(long, value_name = "REMOTE_URI", default_value = "ws://127.0.0.1:9944")]
+        rpc: String,
+        /// Device account seed URI.
+        #[structopt(short, value_name = "SECRET_URI")]
+        suri: String,
+        /// RWS subscription address.
+        #[structopt(long, value_name = "RWS_ADDRESS")]
+        subscription: String,
+        /// SCALE encoded runtime call in hex.
+        #[structopt(long, value_name = "HEX")]
+        call: String,
+    },
+    /// Send timestamped readings from CSV file as batched datalog records.
+    RecordBatch {
+        /// CSV file with `timestamp,value` readings.
+        #[structopt(long, value_name = "PATH", parse(from_os_str))]
+        file: std::path::PathBuf,
+        /// Robonomics node API endpoint.
+        #[structopt(long, value_name = "REMOTE_URI", default_value = "ws://127.0.0.1:9944")]
+        rpc: String,
+        /// Sender account seed URI.
+        #[structopt(short, value_name = "SECRET_URI")]
+        suri: String,
+    },
+    /// Verify Ethereum signed launch command and send launch of mapped robot.
+    /// Notice: Message is `personal_sign` (EIP-191) signed `ON:<unix time>` or `OFF:<unix time>`,
+    /// time of each signer message should grow, so signed commands can't be replayed.
+    VerifyEthSig {
+        /// Signed message in hex.
+        #[structopt(long, value_name = "HEX")]
+        message: String,
+        /// 65 bytes `r, s, v` signature in hex.
+        #[structopt(long, value_name = "HEX")]
+        signature: String,
+        /// Expected signer Ethereum address.
+        #[structopt(long, value_name = "ETH_ADDRESS")]
+        expected: String,
+        /// JSON file mapping Ethereum addresses to robots: `{ "ETH_ADDRESS": "SS58" }`.
+        #[structopt(long, value_name = "PATH", parse(from_os_str))]
+        mapping: std::path::PathBuf,
+        /// JSON file keeping time of the last accepted message of each signer.
+        /// Notice: File is created when missing.
+        #[structopt(long, value_name = "PATH", parse(from_os_str))]
+        last_seen: std::path::PathBuf,
+        /// Maximal age of accepted message in seconds.
+        #[structopt(long, value_name = "SECONDS", default_value = "300")]
+        max_age: u64,
+        /// Robonomics node API endpoint.
+        #[structopt(long, value_name = "REMOTE_URI", default_value = "ws://127.0.0.1:9944")]
+        rpc: String,
+        /// Relayer account seed URI.
+        #[structopt(short, value_name = "SECRET_URI")]
+        suri: String,
+    },
+    /// Submit messages of MQTT topic as datalog records.
+    /// Notice: Record is JSON with `topic`, mapped `twin` and `payload` or IPFS `cid`.
+    MqttBridge {
+        /// MQTT broker URL.
+        #[structopt(long, value_name = "MQTT_URL", default_value = "mqtt://127.0.0.1:1883")]
+        broker: String,
+        /// MQTT topic filter, wildcards are allowed.
+        #[structopt(long, value_name = "TOPIC")]
+        topic: String,
+        /// Robonomics node API endpoint.
+        #[structopt(long, value_name = "REMOTE_URI", default_value = "ws://127.0.0.1:9944")]
+        rpc: String,
+        /// Sender account address.
+        #[structopt(long, value_name = "ADDRESS")]
+        account: String,
+        /// Sender account seed URI.
+        #[structopt(short, value_name = "SECRET_URI")]
+        suri: String,
+        /// JSON file mapping MQTT topics to digital twins: `{ "MQTT_TOPIC": TWIN_ID }`.
+        #[structopt(long, value_name = "PATH", parse(from_os_str))]
+        twin_mapping: Option<std::path::PathBuf>,
+        /// IPFS node endpoint to offload large payloads to.
+        #[structopt(long, value_name = "REMOTE_URI")]
+        ipfs: Option<String>,
+        /// Payloads larger than given bytes are offloaded to IPFS.
+        #[structopt(long, value_name = "BYTES", default_value = "1024")]
+        ipfs_threshold: usize,
+        /// MQTT connection keep alive interval in seconds.
+        #[structopt(long, value_name = "SECS", default_value = "30")]
+        keep_alive: u64,
+    },
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Signer secret key is 0x4646...46.
+    const SIGNER: &str = "0x9d8a62f656a8d1615c1294fd71e9cfb3e4855a4f";
+    const MESSAGE: &[u8] = b"ON:1625097600";
+    const SIGNATURE: &str = "83807d5125a0bf19976a91c7f9b662829f5c16607246092b1a14170ee9e419881be04f9fc086706743e2a55beed82469b91f1901b93d03a717ee000daf49185f1c";
+
+    #[test]
+    fn recover_personal_sign_signer() {
+        let signature = hex::decode(SIGNATURE).unwrap();
+        assert_eq!(
+            recover_eth_signer(MESSAGE, &signature).unwrap(),
+            parse_eth_address(SIGNER).unwrap()
+        );
+    }
+
+    #[test]
+    fn reject_wrong_signer() {
+        let signature = hex::decode(SIGNATURE).unwrap();
+        let signer = recover_eth_signer(b"OFF:1625097600", &signature);
+        assert!(signer.map_or(true, |signer| signer != parse_eth_address(SIGNER).unwrap()));
+        assert!(recover_eth_signer(MESSAGE, &signature[..64]).is_err());
+    }
+
+    #[test]
+    fn parse_fresh_command() {
+        assert_eq!(
+            parse_eth_command(MESSAGE, 1625097600, 30).unwrap(),
+            (true, 1625097600)
+        );
+        assert_eq!(
+            parse_eth_command(b"OFF:1625097600", 1625097630, 30).unwrap(),
+            (false, 1625097600)
+        );
+    }
+
+    #[test]
+    fn reject_stale_command() {
+        assert!(parse_eth_command(MESSAGE, 1625097631, 30).is_err());
+        assert!(parse_eth_command(MESSAGE, 1625097600 - MAX_CLOCK_SKEW - 1, 30).is_err());
+        assert!(parse_eth_command(b"ON", 1625097600, 30).is_err());
+    }
+
+    #[test]
+    fn reject_replayed_command() {
+        let signer = parse_eth_address(SIGNER).unwrap();
+        let mut seen = std::collections::HashMap::new();
+        assert!(check_replay(&seen, &signer, 1625097600).is_ok());
+
+        seen.insert(signer, 1625097600);
+        assert!(check_replay(&seen, &signer, 1625097600).is_err());
+        assert!(check_replay(&seen, &signer, 1625097599).is_err());
+        assert!(check_replay(&seen, &signer, 1625097601).is_ok());
+        assert!(check_replay(&seen, &[0u8; 20], 1625097600).is_ok());
+    }
 }
@@ -21,8 +21,12 @@ pub mod error;
 
 mod io;
 mod sink;
+mod soak;
 mod source;
+mod sudo;
 
 pub use io::IoCmd;
 pub use sink::SinkCmd;
+pub use soak::SoakCmd;
 pub use source::SourceCmd;
+pub use sudo::SudoCmd;
@@ -24,7 +24,7 @@ use async_std::task;
 use futures::prelude::*;
 use robonomics_io::sink::virt;
 use robonomics_io::source::virt::stdin;
-use robonomics_protocol::pubsub::Multiaddr;
+use robonomics_protocol::{pubsub::Multiaddr, subxt::ConnectPolicy};
 use std::time::Duration;
 
 /// Sink device commands.
@@ -96,7 +96,7 @@ pub enum SinkCmd {
 
 impl SinkCmd {
     /// Write data into sink device.
-    pub fn run(&self) -> Result<()> {
+    pub fn run(&self, policy: ConnectPolicy) -> Result<()> {
         match self.clone() {
             SinkCmd::PubSub {
                 topic_name,
@@ -109,7 +109,7 @@ impl SinkCmd {
                 task::block_on(stdin().forward(pubsub))?;
             }
             SinkCmd::Datalog { remote, suri, rws } => {
-                let (submit, hashes) = virt::datalog(remote, suri, rws)?;
+                let (submit, hashes) = virt::datalog(remote, policy, suri, rws)?;
                 task::spawn(stdin().forward(submit));
                 let hex_encoded = hashes.map(|r| r.map(|h| hex::encode(h)));
                 task::block_on(hex_encoded.forward(virt::stdout()))?;
@@ -125,7 +125,7 @@ impl SinkCmd {
                 robot,
                 rws,
             } => {
-                let (submit, hashes) = virt::launch(remote, suri, robot, rws)?;
+                let (submit, hashes) = virt::launch(remote, policy, suri, robot, rws)?;
                 task::spawn(stdin().map(|m| m.map(|s| s == "ON")).forward(submit));
                 let hex_encoded = hashes.map(|r| r.map(|h| hex::encode(h)));
                 task::block_on(hex_encoded.forward(virt::stdout()))?;
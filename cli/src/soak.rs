@@ -0,0 +1,79 @@
+///////////////////////////////////////////////////////////////////////////////
+//
+//  Copyright 2018-2021 Robonomics Network <research@robonomics.network>
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+//
+///////////////////////////////////////////////////////////////////////////////
+//! Robonomics soak test CLI interface.
+
+#![deny(missing_docs)]
+
+use crate::error::{Error, Result};
+use async_std::task;
+use robonomics_protocol::subxt::{soak, ConnectPolicy};
+use sp_core::{sr25519, Pair};
+use std::time::Duration;
+
+/// Flood Robonomics node with datalog records and report inclusion statistics.
+#[derive(structopt::StructOpt, Debug)]
+pub struct SoakCmd {
+    /// Robonomics node API endpoint.
+    #[structopt(long, value_name = "REMOTE_URI", default_value = "ws://127.0.0.1:9944")]
+    pub rpc: String,
+    /// Target count of records sent per second.
+    #[structopt(long, value_name = "COUNT", default_value = "10")]
+    pub tps: u32,
+    /// Test duration in seconds.
+    #[structopt(long, value_name = "SECS", default_value = "60")]
+    pub duration: u64,
+    /// Count of dev accounts sending records.
+    #[structopt(long, value_name = "COUNT", default_value = "10")]
+    pub accounts: usize,
+    /// Secret URI of account funding dev accounts.
+    #[structopt(long, value_name = "SURI", default_value = "//Alice")]
+    pub funder: String,
+}
+
+impl SoakCmd {
+    /// Run soak test and print results as JSON.
+    pub fn run(&self) -> Result<()> {
+        let funder = sr25519::Pair::from_string(self.funder.as_str(), None)
+            .map_err(|e| Error::Other(format!("Invalid secret URI: {:?}", e)))?;
+        let duration = Duration::from_secs(self.duration);
+        let report = task::block_on(soak::run(
+            funder,
+            self.rpc.clone(),
+            ConnectPolicy::default(),
+            self.accounts,
+            self.tps,
+            duration,
+        ))?;
+
+        let millis = |p| report.percentile(p).map(|d| d.as_millis() as u64);
+        let output = serde_json::json!({
+            "submitted": report.submitted,
+            "included": report.latencies.len(),
+            "inclusion_rate": report.inclusion_rate(),
+            "achieved_tps": report.latencies.len() as f64 / duration.as_secs_f64().max(1.0),
+            "latency_ms": {
+                "p50": millis(50.0),
+                "p90": millis(90.0),
+                "p99": millis(99.0),
+                "max": millis(100.0),
+            },
+        });
+        println!("{}", output);
+        Ok(())
+    }
+}
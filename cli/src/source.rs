@@ -22,9 +22,9 @@
 use crate::error::Result;
 use async_std::task;
 use futures::prelude::*;
-use robonomics_io::sink::virt::stdout;
+use robonomics_io::sink::virt::{datalog_batch, stdout};
 use robonomics_io::source::{serial, virt};
-use robonomics_protocol::pubsub::Multiaddr;
+use robonomics_protocol::{pubsub::Multiaddr, subxt::ConnectPolicy};
 use sp_core::crypto::Ss58AddressFormat;
 use std::{convert::TryFrom, time::Duration};
 use structopt::clap::arg_enum;
@@ -49,6 +49,30 @@ pub enum SourceCmd {
         )]
         encoding: Encoding,
     },
+    /// Generic sensor that exposes readings as a device file.
+    Sensor {
+        /// Device file to read from.
+        #[structopt(value_name = "DEVICE")]
+        device: String,
+        /// Reading interval in milliseconds.
+        #[structopt(long, value_name = "MS", default_value = "1000")]
+        interval: u64,
+        /// Submit readings into blockchain as datalog records.
+        #[structopt(long, requires = "suri")]
+        record: bool,
+        /// Readings count in a single datalog record.
+        #[structopt(long, default_value = "1")]
+        batch: usize,
+        /// Robonomics node API endpoint.
+        #[structopt(long, value_name = "REMOTE_URI", default_value = "ws://127.0.0.1:9944")]
+        remote: String,
+        /// Sender account seed URI.
+        #[structopt(short, value_name = "SECRET_URI")]
+        suri: Option<String>,
+        /// RWS subscription address.
+        #[structopt(long, value_name = "RWS_ADDRESS")]
+        rws: Option<String>,
+    },
     /// Subscribe for broadcasing data.
     #[structopt(name = "pubsub")]
     PubSub {
@@ -124,7 +148,7 @@ arg_enum! {
 
 impl SourceCmd {
     /// Read data from source device.
-    pub fn run(&self) -> Result<()> {
+    pub fn run(&self, policy: ConnectPolicy) -> Result<()> {
         match self.clone() {
             SourceCmd::SDS011 {
                 port,
@@ -152,6 +176,27 @@ impl SourceCmd {
                         .forward(stdout()),
                 )?;
             }
+            SourceCmd::Sensor {
+                device,
+                interval,
+                record,
+                batch,
+                remote,
+                suri,
+                rws,
+            } => {
+                let readings = serial::device(device, Duration::from_millis(interval))?;
+
+                match suri {
+                    Some(suri) if record => {
+                        let (submit, hashes) = datalog_batch(remote, policy, suri, rws, batch)?;
+                        task::spawn(readings.forward(submit));
+                        let hex_encoded = hashes.map(|r| r.map(|h| hex::encode(h)));
+                        task::block_on(hex_encoded.forward(stdout()))?;
+                    }
+                    _ => task::block_on(readings.forward(stdout()))?,
+                }
+            }
             SourceCmd::PubSub {
                 topic_name,
                 listen,
@@ -172,7 +217,7 @@ impl SourceCmd {
                 )?;
             }
             SourceCmd::Datalog { remote, address } => {
-                let data = virt::datalog(remote, address)?;
+                let data = virt::datalog(remote, policy, address)?;
                 task::block_on(
                     data.map(|msg| {
                         msg.map(|rec| {
@@ -202,7 +247,7 @@ impl SourceCmd {
             }
             SourceCmd::Launch { remote, network } => {
                 task::block_on(
-                    virt::launch(remote, network)
+                    virt::launch(remote, policy, network)
                         .map(|(sender, robot, param)| {
                             Ok(format!("{} >> {} : {}", sender, robot, param))
                         })
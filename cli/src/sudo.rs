@@ -0,0 +1,59 @@
+///////////////////////////////////////////////////////////////////////////////
+//
+//  Copyright 2018-2021 Robonomics Network <research@robonomics.network>
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+//
+///////////////////////////////////////////////////////////////////////////////
+//! Robonomics sudo call CLI interface.
+
+#![deny(missing_docs)]
+
+use crate::error::{Error, Result};
+use async_std::task;
+use robonomics_protocol::subxt::{sudo, ConnectPolicy};
+use sp_core::{sr25519, Pair};
+use std::path::PathBuf;
+
+/// Wrap encoded runtime call into `sudo.sudo` and submit it signed by sudo key holder.
+#[derive(structopt::StructOpt, Debug)]
+pub struct SudoCmd {
+    /// Hex encoded runtime call, e.g. `system.setCode` call.
+    #[structopt(long, value_name = "HEX")]
+    pub call: String,
+    /// File containing secret URI of the sudo key holder.
+    #[structopt(long, value_name = "PATH", parse(from_os_str))]
+    pub key: PathBuf,
+    /// Robonomics node API endpoint.
+    #[structopt(long, value_name = "REMOTE_URI", default_value = "ws://127.0.0.1:9944")]
+    pub rpc: String,
+}
+
+impl SudoCmd {
+    /// Submit sudo call and print extrinsic hash as hex without `0x` prefix, like io commands.
+    pub fn run(&self) -> Result<()> {
+        let call = hex::decode(self.call.trim_start_matches("0x"))
+            .map_err(|e| Error::Other(format!("Invalid call hex: {}", e)))?;
+        let suri = std::fs::read_to_string(&self.key)?;
+        let pair = sr25519::Pair::from_string(suri.trim(), None)
+            .map_err(|e| Error::Other(format!("Invalid secret URI: {:?}", e)))?;
+        let xt_hash = task::block_on(sudo::call(
+            pair,
+            self.rpc.clone(),
+            ConnectPolicy::default(),
+            call,
+        ))?;
+        println!("{}", hex::encode(xt_hash));
+        Ok(())
+    }
+}
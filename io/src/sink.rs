@@ -17,4 +17,5 @@
 ///////////////////////////////////////////////////////////////////////////////
 //! Collections of I/O devices designed for consume data.
 
+pub mod gpio;
 pub mod virt;
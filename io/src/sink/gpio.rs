@@ -0,0 +1,76 @@
+///////////////////////////////////////////////////////////////////////////////
+//
+//  Copyright 2018-2021 Robonomics Network <research@robonomics.network>
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+//
+///////////////////////////////////////////////////////////////////////////////
+//! Linux GPIO devices collection.
+
+use async_std::task;
+use futures::{channel::mpsc, prelude::*};
+use futures_timer::Delay;
+use std::{path::PathBuf, time::Duration};
+
+use crate::error::{Error, Result};
+
+/// Linux sysfs GPIO interface root.
+const SYSFS_GPIO: &str = "/sys/class/gpio";
+
+/// GPIO output pin driven using Linux sysfs interface.
+///
+/// # Arguments
+/// * `pin` - GPIO pin number, it's exported when not exported yet
+/// * `debounce` - Minimal interval between pin toggles, latest value is applied after it
+///
+/// Returns sink of pin values.
+pub fn sysfs(pin: u32, debounce: Duration) -> Result<impl Sink<bool, Error = Error>> {
+    log::debug!(
+        target: "robonomics::io",
+        "GPIO: pin {} with debounce {:?}", pin, debounce
+    );
+
+    let pin_path = PathBuf::from(SYSFS_GPIO).join(format!("gpio{}", pin));
+    if !pin_path.exists() {
+        std::fs::write(PathBuf::from(SYSFS_GPIO).join("export"), pin.to_string())?;
+    }
+    std::fs::write(pin_path.join("direction"), "out")?;
+
+    let value_path = pin_path.join("value");
+    let (sender, mut receiver) = mpsc::unbounded();
+    task::spawn(async move {
+        let mut current = None;
+        while let Some(mut value) = receiver.next().await {
+            // Rapid toggles are collapsed into the latest one.
+            while let Ok(Some(next)) = receiver.try_next() {
+                value = next;
+            }
+            if current == Some(value) {
+                continue;
+            }
+
+            let level = if value { "1" } else { "0" };
+            if let Err(e) = async_std::fs::write(&value_path, level).await {
+                log::error!(
+                    target: "robonomics::io",
+                    "GPIO: unable to set pin {} to {}: {}", pin, level, e
+                );
+                continue;
+            }
+            current = Some(value);
+            Delay::new(debounce).await;
+        }
+    });
+
+    Ok(sender.sink_err_into())
+}
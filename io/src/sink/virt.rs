@@ -23,7 +23,7 @@ use futures::prelude::*;
 use ipfs_api::{IpfsClient, TryFromUri};
 use robonomics_protocol::{
     pubsub::{self, Multiaddr, PubSub as _},
-    subxt::{datalog, launch},
+    subxt::{datalog, launch, ConnectPolicy},
 };
 use sp_core::{crypto::Pair, sr25519};
 use std::io::Cursor;
@@ -77,6 +77,7 @@ pub fn pubsub<T: Into<Vec<u8>> + Send + 'static>(
 /// Returns hash of sended datalog extrinsic.
 pub fn datalog<T: Into<Vec<u8>>>(
     remote: String,
+    policy: ConnectPolicy,
     suri: String,
     rws: Option<String>,
 ) -> Result<(
@@ -87,12 +88,72 @@ pub fn datalog<T: Into<Vec<u8>>>(
 
     let (sender, receiver) = mpsc::unbounded();
     let hashes = receiver.then(move |msg: T| {
-        datalog::submit(pair.clone(), remote.clone(), msg.into(), rws.clone())
-            .map(|r| r.map_err(Into::into))
+        datalog::submit(
+            pair.clone(),
+            remote.clone(),
+            policy,
+            msg.into(),
+            rws.clone(),
+        )
+        .map(|r| r.map_err(Into::into))
     });
     Ok((sender.sink_err_into(), hashes))
 }
 
+/// Maximal delay between datalog submission attempts.
+const MAX_BACKOFF: Duration = Duration::from_secs(64);
+
+/// Submit signed data records into blockchain, `batch` messages are joined into single record.
+///
+/// Failed submissions (e.g. when transaction pool is full) are retried with exponential backoff.
+///
+/// Returns hash of sended datalog extrinsic.
+pub fn datalog_batch(
+    remote: String,
+    policy: ConnectPolicy,
+    suri: String,
+    rws: Option<String>,
+    batch: usize,
+) -> Result<(
+    impl Sink<String, Error = Error>,
+    impl Stream<Item = Result<[u8; 32]>>,
+)> {
+    let pair = sr25519::Pair::from_string(suri.as_str(), None)?;
+
+    let (sender, receiver) = mpsc::unbounded();
+    let hashes = receiver
+        .chunks(batch.max(1))
+        .then(move |msgs: Vec<String>| {
+            let record = msgs.join("\n").into_bytes();
+            let (pair, remote, rws) = (pair.clone(), remote.clone(), rws.clone());
+            async move {
+                let mut backoff = Duration::from_secs(1);
+                loop {
+                    let submit = datalog::submit(
+                        pair.clone(),
+                        remote.clone(),
+                        policy,
+                        record.clone(),
+                        rws.clone(),
+                    );
+                    match submit.await {
+                        Ok(hash) => return Ok(hash),
+                        Err(e) if backoff <= MAX_BACKOFF => {
+                            log::warn!(
+                                target: "robonomics::io",
+                                "Datalog submission failed: {}, retry in {:?}", e, backoff
+                            );
+                            futures_timer::Delay::new(backoff).await;
+                            backoff *= 2;
+                        }
+                        Err(e) => return Err(e.into()),
+                    }
+                }
+            }
+        });
+    Ok((sender.sink_err_into(), hashes))
+}
+
 /// Upload some data into IPFS network.
 ///
 /// Returns IPFS hash of consumed data objects.
@@ -123,6 +184,7 @@ where
 /// Returns hash of sended launch extrinsic.
 pub fn launch(
     remote: String,
+    policy: ConnectPolicy,
     suri: String,
     robot: String,
     rws: Option<String>,
@@ -137,6 +199,7 @@ pub fn launch(
         launch::submit(
             pair.clone(),
             remote.clone(),
+            policy,
             robot.clone(),
             signal,
             rws.clone(),
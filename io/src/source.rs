@@ -17,5 +17,6 @@
 ///////////////////////////////////////////////////////////////////////////////
 //! Collection of I/O devices designed to provide some data.
 
+pub mod mqtt;
 pub mod serial;
 pub mod virt;
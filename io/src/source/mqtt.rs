@@ -0,0 +1,245 @@
+///////////////////////////////////////////////////////////////////////////////
+//
+//  Copyright 2018-2021 Robonomics Network <research@robonomics.network>
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+//
+///////////////////////////////////////////////////////////////////////////////
+//! MQTT topic subscriber.
+
+use async_std::prelude::*;
+use futures::channel::mpsc;
+use std::io::{ErrorKind, Read, Write};
+use std::net::TcpStream;
+use std::time::{Duration, Instant};
+
+use crate::error::Result;
+
+/// Default MQTT broker port.
+const MQTT_PORT: u16 = 1883;
+
+/// Maximal delay between broker reconnection attempts.
+const MAX_RECONNECT_BACKOFF: Duration = Duration::from_secs(64);
+
+/// Message received from MQTT topic.
+#[derive(Clone, Debug)]
+pub struct Message {
+    /// Topic name the message was published to.
+    pub topic: String,
+    /// Message payload.
+    pub payload: Vec<u8>,
+}
+
+/// Encode MQTT packet with fixed header and remaining length.
+fn packet(header: u8, body: &[u8]) -> Vec<u8> {
+    let mut packet = vec![header];
+    let mut len = body.len();
+    loop {
+        let mut byte = (len % 128) as u8;
+        len /= 128;
+        if len > 0 {
+            byte |= 0x80;
+        }
+        packet.push(byte);
+        if len == 0 {
+            break;
+        }
+    }
+    packet.extend_from_slice(body);
+    packet
+}
+
+/// Encode MQTT length prefixed string.
+fn string(s: &str) -> Vec<u8> {
+    let mut bytes = (s.len() as u16).to_be_bytes().to_vec();
+    bytes.extend_from_slice(s.as_bytes());
+    bytes
+}
+
+/// Read MQTT packet type byte and body.
+fn read_packet(stream: &mut TcpStream) -> std::io::Result<(u8, Vec<u8>)> {
+    let mut header = [0u8; 1];
+    stream.read_exact(&mut header)?;
+    let mut len = 0usize;
+    for shift in 0..4 {
+        let mut byte = [0u8; 1];
+        stream.read_exact(&mut byte)?;
+        len |= ((byte[0] & 0x7f) as usize) << (7 * shift);
+        if byte[0] & 0x80 == 0 {
+            let mut body = vec![0u8; len];
+            stream.read_exact(&mut body)?;
+            return Ok((header[0], body));
+        }
+    }
+    Err(std::io::Error::new(
+        ErrorKind::InvalidData,
+        "Malformed remaining length",
+    ))
+}
+
+/// Connect to `mqtt://host[:port]` broker and subscribe for topic filter with QoS 0.
+fn connect(broker: &str, topic: &str, keep_alive: Duration) -> std::io::Result<TcpStream> {
+    let invalid = |e: &str| std::io::Error::new(ErrorKind::InvalidInput, e.to_string());
+    let host = broker
+        .strip_prefix("mqtt://")
+        .ok_or_else(|| invalid("Only mqtt:// URLs are supported"))?
+        .trim_end_matches('/');
+    let address = if host.contains(':') {
+        host.to_string()
+    } else {
+        format!("{}:{}", host, MQTT_PORT)
+    };
+    let mut stream = TcpStream::connect(address)?;
+    stream.set_read_timeout(Some(keep_alive))?;
+
+    // Protocol level 4 (MQTT 3.1.1) with clean session.
+    let mut connect = string("MQTT");
+    connect.extend_from_slice(&[4, 0x02]);
+    connect.extend_from_slice(&(keep_alive.as_secs() as u16).to_be_bytes());
+    connect.extend_from_slice(&string(&format!("robonomics-{}", std::process::id())));
+    stream.write_all(&packet(0x10, &connect))?;
+    match read_packet(&mut stream)? {
+        (0x20, body) if body.get(1) == Some(&0) => (),
+        (0x20, body) => {
+            return Err(invalid(&format!(
+                "Connection refused with code {:?}",
+                body.get(1)
+            )))
+        }
+        _ => return Err(invalid("Unexpected MQTT broker response")),
+    }
+
+    let mut subscribe = 1u16.to_be_bytes().to_vec();
+    subscribe.extend_from_slice(&string(topic));
+    subscribe.push(0);
+    stream.write_all(&packet(0x82, &subscribe))?;
+    match read_packet(&mut stream)? {
+        (0x90, body) if body.get(2).map_or(false, |code| *code < 0x80) => Ok(stream),
+        _ => Err(invalid("Subscription refused")),
+    }
+}
+
+/// Receive messages from connected broker until connection fails.
+///
+/// Notice: PINGREQ is sent when nothing was sent to broker during keep alive interval.
+fn receive(
+    stream: &mut TcpStream,
+    sender: &mpsc::UnboundedSender<Result<Message>>,
+    keep_alive: Duration,
+) -> std::io::Result<()> {
+    let mut last_sent = Instant::now();
+    loop {
+        let ping_in = match keep_alive.checked_sub(last_sent.elapsed()) {
+            Some(timeout) if timeout > Duration::from_secs(0) => timeout,
+            _ => {
+                stream.write_all(&packet(0xc0, &[]))?;
+                last_sent = Instant::now();
+                continue;
+            }
+        };
+
+        // Wait for the next packet without consuming it, until ping is due.
+        stream.set_read_timeout(Some(ping_in))?;
+        match stream.peek(&mut [0u8; 1]) {
+            Ok(0) => {
+                return Err(std::io::Error::new(
+                    ErrorKind::UnexpectedEof,
+                    "Connection closed by broker",
+                ))
+            }
+            Ok(_) => (),
+            Err(e) if e.kind() == ErrorKind::WouldBlock || e.kind() == ErrorKind::TimedOut => {
+                continue
+            }
+            Err(e) => return Err(e),
+        }
+
+        // Packet is partially consumed on timeout, so connection can't be used anymore.
+        stream.set_read_timeout(Some(keep_alive))?;
+        let (header, body) = read_packet(stream).map_err(|e| match e.kind() {
+            ErrorKind::WouldBlock | ErrorKind::TimedOut => {
+                std::io::Error::new(ErrorKind::TimedOut, "Incomplete packet received")
+            }
+            _ => e,
+        })?;
+        if header >> 4 != 3 {
+            continue;
+        }
+
+        let invalid = || std::io::Error::new(ErrorKind::InvalidData, "Malformed PUBLISH");
+        let topic_len = u16::from_be_bytes([
+            *body.get(0).ok_or_else(invalid)?,
+            *body.get(1).ok_or_else(invalid)?,
+        ]) as usize;
+        let topic = body.get(2..2 + topic_len).ok_or_else(invalid)?;
+        let topic = String::from_utf8_lossy(topic).into_owned();
+        let mut offset = 2 + topic_len;
+        // Broker could send QoS 1 message despite of subscription, it should be acknowledged.
+        if (header >> 1) & 0x03 > 0 {
+            let packet_id = body.get(offset..offset + 2).ok_or_else(invalid)?;
+            stream.write_all(&packet(0x40, packet_id))?;
+            last_sent = Instant::now();
+            offset += 2;
+        }
+        let payload = body.get(offset..).ok_or_else(invalid)?.to_vec();
+
+        if sender
+            .unbounded_send(Ok(Message { topic, payload }))
+            .is_err()
+        {
+            return Ok(());
+        }
+    }
+}
+
+/// MQTT topic subscriber, e.g. `mqtt://127.0.0.1:1883` broker.
+///
+/// # Arguments
+/// * `broker` - MQTT broker URL
+/// * `topic` - Topic filter, wildcards are allowed
+/// * `keep_alive` - Interval of connection checks
+///
+/// Returns stream of received messages, broker connection is retried with exponential backoff.
+pub fn subscribe(
+    broker: String,
+    topic: String,
+    keep_alive: Duration,
+) -> Result<impl Stream<Item = Result<Message>>> {
+    log::debug!(
+        target: "robonomics::io",
+        "MQTT: broker {} with topic {}", broker, topic
+    );
+
+    let (sender, receiver) = mpsc::unbounded();
+    // Broker connection is blocking, so it lives on dedicated thread.
+    std::thread::spawn(move || {
+        let mut backoff = Duration::from_secs(1);
+        while !sender.is_closed() {
+            let result = connect(&broker, &topic, keep_alive).and_then(|mut stream| {
+                log::info!(target: "robonomics::io", "Subscribed to MQTT topic {}", topic);
+                backoff = Duration::from_secs(1);
+                receive(&mut stream, &sender, keep_alive)
+            });
+            if let Err(e) = result {
+                log::warn!(
+                    target: "robonomics::io",
+                    "MQTT broker {} failed: {}, reconnect in {:?}", broker, e, backoff
+                );
+                std::thread::sleep(backoff);
+                backoff = (backoff * 2).min(MAX_RECONNECT_BACKOFF);
+            }
+        }
+    });
+
+    Ok(receiver)
+}
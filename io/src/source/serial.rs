@@ -34,7 +34,7 @@ use crate::error::Result;
 /// Returns stream of Nova SDS011 sensor messages.
 pub fn sds011(port: String, period: u8) -> Result<impl Stream<Item = Result<sds011::Message>>> {
     log::debug!(
-        target: "robonomics-io",
+        target: "robonomics::io",
         "SDS011: port {} with period {} min", port, period
     );
     let mut device = sds011::SDS011::new(port.as_str())?;
@@ -51,3 +51,32 @@ pub fn sds011(port: String, period: u8) -> Result<impl Stream<Item = Result<sds0
 
     Ok(receiver.map(|v| v.map_err(Into::into)))
 }
+
+/// Generic device file sensor, e.g. `/sys/bus/iio/devices/iio:device0/in_temp_input`.
+///
+/// # Arguments
+/// * `path` - Device file path, whole file content is a single reading
+/// * `interval` - Reading interval
+///
+/// Returns stream of device readings.
+pub fn device(path: String, interval: Duration) -> Result<impl Stream<Item = Result<String>>> {
+    log::debug!(
+        target: "robonomics::io",
+        "Device: file {} with interval {:?}", path, interval
+    );
+
+    let (sender, receiver) = mpsc::unbounded();
+    task::spawn(async move {
+        loop {
+            let reading = async_std::fs::read_to_string(&path)
+                .await
+                .map(|s| s.trim().to_string());
+            if sender.unbounded_send(reading).is_err() {
+                break;
+            }
+            Delay::new(interval).await;
+        }
+    });
+
+    Ok(receiver.map(|v| v.map_err(Into::into)))
+}
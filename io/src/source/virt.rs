@@ -19,9 +19,10 @@
 
 use async_std::{io, task};
 use futures::{channel::mpsc, prelude::*};
+use futures_timer::Delay;
 use ipfs_api::{IpfsClient, TryFromUri};
 use robonomics_protocol::pubsub::{self, Multiaddr, PubSub as PubSubT};
-use robonomics_protocol::subxt::{datalog, AccountId};
+use robonomics_protocol::subxt::{datalog, launch as launch_events, AccountId, ConnectPolicy};
 use sp_core::crypto::{Ss58AddressFormat, Ss58Codec};
 use std::time::Duration;
 
@@ -65,6 +66,7 @@ pub fn pubsub(
 /// Returns datalog data objects.
 pub fn datalog(
     remote: String,
+    policy: ConnectPolicy,
     address: String,
 ) -> Result<impl Stream<Item = Result<Vec<(u64, Vec<u8>)>>>> {
     let robot_account =
@@ -75,7 +77,7 @@ pub fn datalog(
         sender.send(robot_account).await.unwrap();
     });
     let data = receiver.then(move |robot_account: AccountId| {
-        datalog::fetch(robot_account.clone(), remote.clone()).map(|r| r.map_err(Into::into))
+        datalog::fetch(robot_account.clone(), remote.clone(), policy).map(|r| r.map_err(Into::into))
     });
     Ok(data)
 }
@@ -106,12 +108,14 @@ pub fn ipfs(
 /// Returns launch parameter, event sender account.
 pub fn launch(
     remote: String,
+    policy: ConnectPolicy,
     format: Ss58AddressFormat,
 ) -> impl Stream<Item = (String, String, bool)> {
     let (mut sender, receiver) = mpsc::unbounded();
 
     task::spawn(robonomics_protocol::subxt::launch::listen(
         remote,
+        policy,
         move |event| {
             let _ = sender.send((
                 event.sender.to_ss58check_with_version(format),
@@ -124,6 +128,52 @@ pub fn launch(
     receiver
 }
 
+/// Listen for finalized launch requests of the robot, resubscribe when remote node drops.
+///
+/// # Arguments
+/// * `remote` - Robonomics node API endpoint
+/// * `policy` - Robonomics node API connection policy
+/// * `robot` - Robot account address
+/// * `reconnect` - Delay before resubscription
+///
+/// Returns stream of launch parameters.
+pub fn robot_launch(
+    remote: String,
+    policy: ConnectPolicy,
+    robot: String,
+    reconnect: Duration,
+) -> Result<impl Stream<Item = bool>> {
+    let robot_account =
+        AccountId::from_ss58check(robot.as_str()).map_err(|_| Error::Ss58CodecError)?;
+
+    let (sender, receiver) = mpsc::unbounded();
+    task::spawn(async move {
+        loop {
+            let events = sender.clone();
+            let robot = robot_account.clone();
+            let result = launch_events::listen_finalized(remote.clone(), policy, move |event| {
+                if event.robot == robot {
+                    let _ = events.unbounded_send(event.param);
+                }
+            })
+            .await;
+
+            if sender.is_closed() {
+                break;
+            }
+
+            log::warn!(
+                target: "robonomics::io",
+                "Launch subscription to {} dropped ({:?}), reconnect in {:?}",
+                remote, result, reconnect
+            );
+            Delay::new(reconnect).await;
+        }
+    });
+
+    Ok(receiver)
+}
+
 #[cfg(feature = "ros")]
 /// Subscribe for messages from ROS topic.
 pub fn ros(
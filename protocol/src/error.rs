@@ -50,6 +50,16 @@ pub enum Error {
     Ss58CodecError,
     /// Unable to get metadata.
     MetadataError,
+    /// RWS subscription isn't registered.
+    #[display(fmt = "RWS subscription {} isn't registered", _0)]
+    NoSubscription(#[from(ignore)] String),
+    /// Account isn't a device of RWS subscription.
+    #[display(fmt = "Account {} isn't authorized on RWS subscription {}", _0, _1)]
+    NotAuthorized(String, String),
+    /// Account isn't the sudo key holder.
+    #[display(fmt = "Account {} isn't the sudo key holder {}", _0, _1)]
+    #[from(ignore)]
+    NotSudo(String, String),
     /// Other error.
     Other(String),
 }
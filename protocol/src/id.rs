@@ -24,7 +24,7 @@ use libp2p::PeerId;
 pub fn random() -> Keypair {
     let key = Keypair::generate_ed25519();
     let peer_id = PeerId::from(key.public());
-    log::info!(target: "robonomics-identity",
+    log::info!(target: "robonomics::protocol",
                "Generated random peer id: {}", peer_id.to_base58());
     key
 }
@@ -98,7 +98,7 @@ async fn connect<T: PubSub>(pubsub: Arc<T>, msg: super::Message) {
         }
         Err(e) => {
             log::error!(
-                target: "robonomics-pubsub",
+                target: "robonomics::pubsub",
                 "Unable to decode discovery message from {}: {}",
                 msg.from.to_base58(), e
             );
@@ -109,7 +109,7 @@ impl PubSubWorker {
     fn listen(&mut self, address: Multiaddr) -> Result<ListenerId> {
         let listener = Swarm::listen_on(&mut self.swarm, address.clone())?;
         log::debug!(
-            target: "robonomics-pubsub",
+            target: "robonomics::pubsub",
             "Listener for address {} created: {:?}", address, listener
         );
         Ok(listener)
@@ -117,12 +117,12 @@ impl PubSubWorker {
 
     fn listeners(&self) -> Vec<Multiaddr> {
         let listeners = Swarm::listeners(&self.swarm).cloned().collect();
-        log::debug!(target: "robonomics-pubsub", "Listeners: {:?}", listeners);
+        log::debug!(target: "robonomics::pubsub", "Listeners: {:?}", listeners);
         listeners
     }
 
     fn connect(&mut self, address: Multiaddr) -> bool {
-        log::debug!(target: "robonomics-pubsub", "Connecting to {}", address);
+        log::debug!(target: "robonomics::pubsub", "Connecting to {}", address);
 
         Swarm::dial_addr(&mut self.swarm, address).is_ok()
     }
@@ -135,10 +135,10 @@ impl PubSubWorker {
         let topic = Topic::new(topic_name.clone());
         let subscribed = self.swarm.deref_mut().subscribe(&topic);
         if subscribed.is_ok() {
-            log::debug!(target: "robonomics-pubsub", "Subscribed to {}", topic_name);
+            log::debug!(target: "robonomics::pubsub", "Subscribed to {}", topic_name);
             self.inbox.insert(topic.hash(), inbox);
         } else {
-            log::warn!(target: "robonomics-pubsub",
+            log::warn!(target: "robonomics::pubsub",
                        "Subscription error {:?}", subscribed);
         }
         subscribed.is_ok()
@@ -148,17 +148,17 @@ impl PubSubWorker {
         let topic = Topic::new(topic_name.clone());
         let unsubscribed = self.swarm.deref_mut().unsubscribe(&topic);
         if unsubscribed.is_ok() {
-            log::debug!(target: "robonomics-pubsub", "Unsubscribed from {}", topic_name);
+            log::debug!(target: "robonomics::pubsub", "Unsubscribed from {}", topic_name);
             self.inbox.remove(&topic.hash());
         } else {
-            log::warn!(target: "robonomics-pubsub",
+            log::warn!(target: "robonomics::pubsub",
                        "Unsubscribe error {:?}", unsubscribed);
         }
         unsubscribed.is_ok()
     }
 
     fn publish(&mut self, topic_name: String, message: Vec<u8>) {
-        log::debug!(target: "robonomics-pubsub", "Publish to {}", topic_name);
+        log::debug!(target: "robonomics::pubsub", "Publish to {}", topic_name);
 
         let topic = Topic::new(topic_name);
         let _ = self.swarm.deref_mut().publish(topic, message);
@@ -178,7 +178,7 @@ impl Future for PubSubWorker {
                         message,
                     } => {
                         log::debug!(
-                            target: "robonomics-pubsub",
+                            target: "robonomics::pubsub",
                             "Received message with id: {} from peer: {}", id, peer_id.to_base58()
                         );
 
@@ -192,7 +192,7 @@ impl Future for PubSubWorker {
                             }
                         } else {
                             log::warn!(
-                                target: "robonomics-pubsub",
+                                target: "robonomics::pubsub",
                                 "Topic {} have no associated inbox!", message.topic
                             );
                         }
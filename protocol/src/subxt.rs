@@ -17,6 +17,8 @@
 ///////////////////////////////////////////////////////////////////////////////
 //! SubXt compatible Robonomics Network types.
 
+use futures::future::{self, Either};
+use futures_timer::Delay;
 use sp_runtime::{
     generic::Header,
     traits::{BlakeTwo256, IdentifyAccount, Verify},
@@ -35,10 +37,71 @@ pub mod launch;
 pub mod pallet_datalog;
 pub mod pallet_launch;
 pub mod pallet_rws;
+pub mod pallet_sudo;
+pub mod pallet_utility;
+pub mod rws;
+pub mod soak;
+pub mod sudo;
 
 use pallet_datalog::DatalogEventTypeRegistry;
 use pallet_launch::LaunchEventTypeRegistry;
 use pallet_rws::RWSEventTypeRegistry;
+use pallet_sudo::SudoEventTypeRegistry;
+use pallet_utility::UtilityEventTypeRegistry;
+
+use crate::error::{Error, Result};
+use std::future::Future;
+use std::time::Duration;
+
+/// Maximal delay between connection attempts.
+const MAX_CONNECT_BACKOFF: Duration = Duration::from_secs(64);
+
+/// Remote node connection policy, single attempt without timeout by default.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ConnectPolicy {
+    /// Number of reconnection attempts when remote node isn't available.
+    pub retries: usize,
+    /// Single connection attempt timeout.
+    pub timeout: Option<Duration>,
+}
+
+impl ConnectPolicy {
+    /// Connect to remote node, failed attempts are retried with exponential backoff.
+    pub async fn connect<F, Fut, T>(&self, build: F) -> Result<T>
+    where
+        F: Fn() -> Fut,
+        Fut: Future<Output = std::result::Result<T, substrate_subxt::Error>>,
+    {
+        let mut backoff = Duration::from_secs(1);
+        let mut attempt = 0;
+        loop {
+            let result = match self.timeout {
+                Some(timeout) => {
+                    let delay = Delay::new(timeout);
+                    match future::select(Box::pin(build()), delay).await {
+                        Either::Left((result, _)) => result.map_err(Into::into),
+                        Either::Right(_) => Err(Error::Other("Connection timeout".into())),
+                    }
+                }
+                None => build().await.map_err(Into::into),
+            };
+
+            match result {
+                Err(e) if attempt < self.retries => {
+                    attempt += 1;
+                    log::warn!(
+                        target: "robonomics::protocol",
+                        "Connection failed: {}, retry {}/{} in {:?}",
+                        e, attempt, self.retries, backoff
+                    );
+                    Delay::new(backoff).await;
+                    backoff = (backoff * 2).min(MAX_CONNECT_BACKOFF);
+                }
+                result => return result,
+            }
+        }
+    }
+}
 
 /// Robonomics Network family substrate runtimes.
 #[derive(Debug, Clone, Eq, PartialEq)]
@@ -57,6 +120,8 @@ impl Runtime for Robonomics {
         event_type_registry.with_datalog();
         event_type_registry.with_launch();
         event_type_registry.with_rws();
+        event_type_registry.with_sudo();
+        event_type_registry.with_utility();
         register_default_type_sizes(event_type_registry);
     }
 }
@@ -86,3 +151,7 @@ impl pallet_launch::Launch for Robonomics {
 }
 
 impl pallet_rws::RWS for Robonomics {}
+
+impl pallet_sudo::Sudo for Robonomics {}
+
+impl pallet_utility::Utility for Robonomics {}
@@ -17,17 +17,40 @@
 ///////////////////////////////////////////////////////////////////////////////
 //! Robonomics data blockchainization.
 
-use super::{pallet_datalog::*, pallet_rws::*, AccountId, Robonomics};
+use super::{
+    pallet_datalog::*, pallet_rws::*, pallet_utility::*, AccountId, ConnectPolicy, Robonomics,
+};
 use crate::error::{Error, Result};
 
+use codec::Decode;
+use core::marker::PhantomData;
 use futures::future::join_all;
 use sp_core::crypto::{Pair, Ss58Codec};
-use substrate_subxt::PairSigner;
+use substrate_subxt::{
+    system::{AccountStoreExt, ExtrinsicSuccessEvent},
+    PairSigner, Signer,
+};
+
+/// Normal dispatch class weights, the part of `frame_system::limits::WeightsPerClass`.
+#[derive(Decode)]
+struct WeightsPerClass {
+    _base_extrinsic: u64,
+    max_extrinsic: Option<u64>,
+}
+
+/// Runtime block weights, the part of `frame_system::limits::BlockWeights`.
+#[derive(Decode)]
+struct BlockWeights {
+    _base_block: u64,
+    max_block: u64,
+    normal: WeightsPerClass,
+}
 
 /// Sign datalog record and send using remote Robonomics node.
 pub async fn submit<T: Pair>(
     signer: T,
     remote: String,
+    policy: ConnectPolicy,
     data_record: Vec<u8>,
     rws: Option<String>,
 ) -> Result<[u8; 32]>
@@ -37,10 +60,13 @@ where
     <T as Pair>::Signature: codec::Codec,
 {
     let subxt_signer = PairSigner::new(signer);
-    let client = substrate_subxt::ClientBuilder::<Robonomics>::new()
-        .skip_type_sizes_check()
-        .set_url(remote.as_str())
-        .build()
+    let client = policy
+        .connect(|| {
+            substrate_subxt::ClientBuilder::<Robonomics>::new()
+                .skip_type_sizes_check()
+                .set_url(remote.as_str())
+                .build()
+        })
         .await?;
 
     let xt_hash = if let Some(subscription) = rws {
@@ -57,18 +83,130 @@ where
     };
 
     log::debug!(
-        target: "robonomics-datalog",
+        target: "robonomics::protocol",
         "Data record submited in extrinsic with hash {}", xt_hash
     );
     Ok(xt_hash.into())
 }
 
+/// Sign datalog records and send them in utility batches using remote Robonomics node.
+///
+/// Records are split into several batches when weight of single batch exceeds
+/// maximal extrinsic weight of the runtime, batch weight is measured by the first
+/// record sent in its own batch. Records are sent one by one when runtime has no
+/// utility pallet.
+///
+/// Returns hashes of sended extrinsics.
+pub async fn submit_batch<T: Pair>(
+    signer: T,
+    remote: String,
+    policy: ConnectPolicy,
+    records: Vec<Vec<u8>>,
+) -> Result<Vec<[u8; 32]>>
+where
+    sp_runtime::MultiSigner: From<<T as Pair>::Public>,
+    sp_runtime::MultiSignature: From<<T as Pair>::Signature>,
+    <T as Pair>::Signature: codec::Codec,
+{
+    let mut subxt_signer = PairSigner::new(signer);
+    let client = policy
+        .connect(|| {
+            substrate_subxt::ClientBuilder::<Robonomics>::new()
+                .skip_type_sizes_check()
+                .set_url(remote.as_str())
+                .build()
+        })
+        .await?;
+
+    // Records are sent without waiting for inclusion, so nonce is tracked locally.
+    let nonce = client.account(subxt_signer.account_id(), None).await?.nonce;
+    subxt_signer.set_nonce(nonce);
+
+    let mut hashes = Vec::new();
+    if !has_utility(&client) {
+        for record in records {
+            let xt_hash = client.submit(RecordCall { record }, &subxt_signer).await?;
+            subxt_signer.increment_nonce();
+            hashes.push(xt_hash.into());
+        }
+        return Ok(hashes);
+    }
+
+    let (first, others) = match records.split_first() {
+        Some(records) => records,
+        None => return Ok(hashes),
+    };
+    let result = client
+        .watch(
+            BatchCall {
+                calls: vec![client.encode(RecordCall {
+                    record: first.clone(),
+                })?],
+                _runtime: PhantomData,
+            },
+            &subxt_signer,
+        )
+        .await?;
+    subxt_signer.increment_nonce();
+    hashes.push(result.extrinsic.into());
+
+    // Weight of single record batch includes batch overhead, so it bounds weight per record.
+    let record_weight = result
+        .find_event::<ExtrinsicSuccessEvent<Robonomics>>()
+        .map_err(substrate_subxt::Error::from)?
+        .ok_or_else(|| Error::Other("Batch of data records isn't dispatched".into()))?
+        .info
+        .weight;
+    let weights: BlockWeights = client
+        .metadata()
+        .module("System")?
+        .constant("BlockWeights")?
+        .value()?;
+    let max_weight = weights.normal.max_extrinsic.unwrap_or(weights.max_block);
+    let batch_size = (max_weight / record_weight.max(1)).max(1) as usize;
+
+    for chunk in others.chunks(batch_size) {
+        let calls = chunk
+            .iter()
+            .map(|record| {
+                client.encode(RecordCall {
+                    record: record.clone(),
+                })
+            })
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+        let xt_hash = client
+            .submit(
+                BatchCall {
+                    calls,
+                    _runtime: PhantomData,
+                },
+                &subxt_signer,
+            )
+            .await?;
+        subxt_signer.increment_nonce();
+
+        log::debug!(
+            target: "robonomics::protocol",
+            "Batch of {} data records submited in extrinsic with hash {}", chunk.len(), xt_hash
+        );
+        hashes.push(xt_hash.into());
+    }
+    Ok(hashes)
+}
+
 /// Read datalog records from remote Robonomics node.
-pub async fn fetch(robot_account: AccountId, remote: String) -> Result<Vec<(u64, Vec<u8>)>> {
-    let client = substrate_subxt::ClientBuilder::<Robonomics>::new()
-        .skip_type_sizes_check()
-        .set_url(remote.as_str())
-        .build()
+pub async fn fetch(
+    robot_account: AccountId,
+    remote: String,
+    policy: ConnectPolicy,
+) -> Result<Vec<(u64, Vec<u8>)>> {
+    let client = policy
+        .connect(|| {
+            substrate_subxt::ClientBuilder::<Robonomics>::new()
+                .skip_type_sizes_check()
+                .set_url(remote.as_str())
+                .build()
+        })
         .await?;
 
     let metadata = client.metadata().module("Datalog")?;
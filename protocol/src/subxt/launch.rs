@@ -17,7 +17,7 @@
 ///////////////////////////////////////////////////////////////////////////////
 //! Launch CPS using Robonomics network.
 
-use super::{pallet_launch::*, pallet_rws::*, AccountId, Robonomics};
+use super::{pallet_launch::*, pallet_rws::*, AccountId, ConnectPolicy, Robonomics};
 use crate::error::{Error, Result};
 
 use codec::Decode;
@@ -28,6 +28,7 @@ use substrate_subxt::{EventSubscription, PairSigner};
 pub async fn submit<T: Pair>(
     signer: T,
     remote: String,
+    policy: ConnectPolicy,
     robot: String,
     param: bool,
     rws: Option<String>,
@@ -40,9 +41,12 @@ where
     let subxt_signer = PairSigner::new(signer);
     let robot_account =
         AccountId::from_ss58check(robot.as_str()).map_err(|_| Error::Ss58CodecError)?;
-    let client = substrate_subxt::ClientBuilder::<Robonomics>::new()
-        .set_url(remote.as_str())
-        .build()
+    let client = policy
+        .connect(|| {
+            substrate_subxt::ClientBuilder::<Robonomics>::new()
+                .set_url(remote.as_str())
+                .build()
+        })
         .await?;
 
     let xt_hash = if let Some(subscription) = rws {
@@ -60,7 +64,7 @@ where
     };
 
     log::debug!(
-        target: "robonomics-launch",
+        target: "robonomics::protocol",
         "Launch request submited in extrinsic with hash {}", xt_hash
     );
     Ok(xt_hash.into())
@@ -69,21 +73,47 @@ where
 /// Listen for incoming launch requests.
 pub async fn listen(
     remote: String,
+    policy: ConnectPolicy,
+    callback: impl FnMut(NewLaunchEvent<Robonomics>),
+) -> Result<()> {
+    listen_events(remote, policy, false, callback).await
+}
+
+/// Listen for launch requests included into finalized blocks.
+pub async fn listen_finalized(
+    remote: String,
+    policy: ConnectPolicy,
+    callback: impl FnMut(NewLaunchEvent<Robonomics>),
+) -> Result<()> {
+    listen_events(remote, policy, true, callback).await
+}
+
+async fn listen_events(
+    remote: String,
+    policy: ConnectPolicy,
+    finalized: bool,
     mut callback: impl FnMut(NewLaunchEvent<Robonomics>),
 ) -> Result<()> {
-    let client = substrate_subxt::ClientBuilder::<Robonomics>::new()
-        .set_url(remote.as_str())
-        .build()
+    let client = policy
+        .connect(|| {
+            substrate_subxt::ClientBuilder::<Robonomics>::new()
+                .set_url(remote.as_str())
+                .build()
+        })
         .await?;
 
-    let sub = client.subscribe_events().await?;
+    let sub = if finalized {
+        client.subscribe_finalized_events().await?
+    } else {
+        client.subscribe_events().await?
+    };
     let mut sub = EventSubscription::<Robonomics>::new(sub, client.events_decoder());
     sub.filter_event::<NewLaunchEvent<_>>();
     while let Some(Ok(raw)) = sub.next().await {
         if let Ok(event) = NewLaunchEvent::<Robonomics>::decode(&mut &raw.data[..]) {
             callback(event)
         } else {
-            log::warn!("Unable decode launch event: {:?}", raw);
+            log::warn!(target: "robonomics::protocol", "Unable decode launch event: {:?}", raw);
         }
     }
 
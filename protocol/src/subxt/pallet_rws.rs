@@ -21,7 +21,7 @@ use codec::{Decode, Encode};
 use sp_runtime::{DispatchResult, Perbill};
 use std::fmt::Debug;
 use substrate_subxt::{system::System, Encoded};
-use substrate_subxt_proc_macro::{module, Call, Event};
+use substrate_subxt_proc_macro::{module, Call, Event, Store};
 
 /// The subset of the `pallet_robonomics_rws::Config` that a client must implement.
 #[module]
@@ -34,6 +34,13 @@ pub struct CallCall<'a, T: RWS> {
     pub call: &'a Encoded,
 }
 
+/// Get devices of RWS subscription.
+#[derive(Clone, Debug, Eq, PartialEq, Store, Encode)]
+pub struct SubscriptionStore<'a, T: RWS> {
+    #[store(returns = Vec<T::AccountId>)]
+    pub subscription: &'a T::AccountId,
+}
+
 /// Updated bandwidth for an account.
 #[derive(Clone, Debug, Eq, PartialEq, Event, Decode)]
 pub struct BandwidthEvent<T: RWS> {
@@ -0,0 +1,49 @@
+///////////////////////////////////////////////////////////////////////////////
+//
+//  Copyright 2018-2021 Robonomics Network <research@robonomics.network>
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+//
+///////////////////////////////////////////////////////////////////////////////
+//! SubXt compatible sudo pallet.
+
+use codec::{Decode, Encode};
+use sp_runtime::DispatchResult;
+use std::fmt::Debug;
+use substrate_subxt::{system::System, Encoded};
+use substrate_subxt_proc_macro::{module, Call, Event, Store};
+
+/// The subset of the `pallet_sudo::Config` that a client must implement.
+#[module]
+pub trait Sudo: System {}
+
+/// Dispatch encoded call with root origin.
+#[derive(Clone, Debug, Eq, PartialEq, Call, Encode)]
+pub struct SudoCall<'a, T: Sudo> {
+    pub call: &'a Encoded,
+    pub _runtime: core::marker::PhantomData<T>,
+}
+
+/// Get sudo key holder account.
+#[derive(Clone, Debug, Eq, PartialEq, Store, Encode)]
+pub struct KeyStore<T: Sudo> {
+    #[store(returns = T::AccountId)]
+    pub _runtime: core::marker::PhantomData<T>,
+}
+
+/// Sudo call dispatched.
+#[derive(Clone, Debug, Eq, PartialEq, Event, Decode)]
+pub struct SudidEvent<T: Sudo> {
+    pub result: DispatchResult,
+    pub _runtime: core::marker::PhantomData<T>,
+}
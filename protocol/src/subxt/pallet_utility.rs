@@ -0,0 +1,42 @@
+///////////////////////////////////////////////////////////////////////////////
+//
+//  Copyright 2018-2021 Robonomics Network <research@robonomics.network>
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+//
+///////////////////////////////////////////////////////////////////////////////
+//! SubXt compatible utility pallet.
+
+use codec::Encode;
+use core::marker::PhantomData;
+use std::fmt::Debug;
+use substrate_subxt::{system::System, Client, Encoded, Runtime};
+use substrate_subxt_proc_macro::{module, Call};
+
+/// The subset of the `pallet_utility::Config` that a client must implement.
+#[module]
+pub trait Utility: System {}
+
+/// Dispatch batch of encoded calls.
+#[derive(Clone, Debug, Eq, PartialEq, Call, Encode)]
+pub struct BatchCall<T: Utility> {
+    /// Encoded runtime calls.
+    pub calls: Vec<Encoded>,
+    /// Runtime marker.
+    pub _runtime: PhantomData<T>,
+}
+
+/// Check that runtime of connected node has utility pallet, e.g. local runtime hasn't.
+pub fn has_utility<T: Runtime>(client: &Client<T>) -> bool {
+    client.metadata().module_with_calls("Utility").is_ok()
+}
@@ -0,0 +1,74 @@
+///////////////////////////////////////////////////////////////////////////////
+//
+//  Copyright 2018-2021 Robonomics Network <research@robonomics.network>
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+//
+///////////////////////////////////////////////////////////////////////////////
+//! Robonomics Web Services calls.
+
+use super::{pallet_rws::*, AccountId, ConnectPolicy, Robonomics};
+use crate::error::{Error, Result};
+
+use sp_core::crypto::{Pair, Ss58Codec};
+use substrate_subxt::{Encoded, PairSigner, Signer};
+
+/// Sign encoded runtime call, wrap it into RWS call and send using remote Robonomics node.
+///
+/// Signer account should be a device of given subscription.
+pub async fn call<T: Pair>(
+    signer: T,
+    remote: String,
+    policy: ConnectPolicy,
+    subscription: String,
+    call: Vec<u8>,
+) -> Result<[u8; 32]>
+where
+    sp_runtime::MultiSigner: From<<T as Pair>::Public>,
+    sp_runtime::MultiSignature: From<<T as Pair>::Signature>,
+    <T as Pair>::Signature: codec::Codec,
+{
+    let subxt_signer = PairSigner::new(signer);
+    let subscription_account =
+        AccountId::from_ss58check(subscription.as_str()).map_err(|_| Error::Ss58CodecError)?;
+    let client = policy
+        .connect(|| {
+            substrate_subxt::ClientBuilder::<Robonomics>::new()
+                .skip_type_sizes_check()
+                .set_url(remote.as_str())
+                .build()
+        })
+        .await?;
+
+    let store = SubscriptionStore {
+        subscription: &subscription_account,
+    };
+    let devices = client
+        .fetch(&store, None)
+        .await?
+        .ok_or_else(|| Error::NoSubscription(subscription.clone()))?;
+    let device = subxt_signer.account_id();
+    if !devices.contains(device) {
+        return Err(Error::NotAuthorized(device.to_ss58check(), subscription));
+    }
+
+    let xt_hash = client
+        .call(&subxt_signer, &subscription_account, &Encoded(call))
+        .await?;
+
+    log::debug!(
+        target: "robonomics::protocol",
+        "RWS call submited in extrinsic with hash {}", xt_hash
+    );
+    Ok(xt_hash.into())
+}
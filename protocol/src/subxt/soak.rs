@@ -0,0 +1,255 @@
+///////////////////////////////////////////////////////////////////////////////
+//
+//  Copyright 2018-2021 Robonomics Network <research@robonomics.network>
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+//
+///////////////////////////////////////////////////////////////////////////////
+//! Robonomics node load testing by datalog records.
+
+use super::{pallet_datalog::*, pallet_utility::*, ConnectPolicy, Robonomics};
+use crate::error::{Error, Result};
+
+use core::marker::PhantomData;
+use futures::{future, prelude::*, select, stream::FuturesUnordered};
+use futures_timer::Delay;
+use sp_core::{crypto::Pair, sr25519};
+use std::time::{Duration, Instant};
+use substrate_subxt::{balances::TransferCall, PairSigner, Signer};
+
+/// Balance transferred to each soak account before test, 1000 XRT.
+const SOAK_ENDOWMENT: u128 = 1_000_000_000_000;
+
+/// Maximal time to wait for inclusion of records sent at the end of test.
+const INCLUSION_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// Soak test results.
+#[derive(Debug, Default)]
+pub struct SoakReport {
+    /// Count of submitted records.
+    pub submitted: usize,
+    /// Inclusion latencies of records included into blocks.
+    pub latencies: Vec<Duration>,
+}
+
+impl SoakReport {
+    /// Part of submitted records included into blocks.
+    pub fn inclusion_rate(&self) -> f64 {
+        if self.submitted == 0 {
+            return 0.0;
+        }
+        self.latencies.len() as f64 / self.submitted as f64
+    }
+
+    /// Inclusion latency percentile, `p` is in range `0..=100`.
+    pub fn percentile(&self, p: f64) -> Option<Duration> {
+        let mut latencies = self.latencies.clone();
+        latencies.sort();
+        let last = latencies.len().checked_sub(1)?;
+        let index = ((last as f64) * p / 100.0).round() as usize;
+        latencies.get(index.min(last)).cloned()
+    }
+}
+
+/// Dev account used as soak test sender.
+fn soak_account(index: usize) -> sr25519::Pair {
+    sr25519::Pair::from_string(&format!("//Soak//{}", index), None)
+        .expect("static derivation path; qed")
+}
+
+/// Fund `accounts` dev accounts by `funder` and send datalog records by them
+/// with target `tps` rate during `duration`.
+///
+/// Notice: inclusion latency is time between record submission and block import.
+pub async fn run(
+    funder: sr25519::Pair,
+    remote: String,
+    policy: ConnectPolicy,
+    accounts: usize,
+    tps: u32,
+    duration: Duration,
+) -> Result<SoakReport> {
+    if accounts == 0 || tps == 0 {
+        return Err(Error::Other("Accounts and TPS should be positive".into()));
+    }
+
+    let client = policy
+        .connect(|| {
+            substrate_subxt::ClientBuilder::<Robonomics>::new()
+                .skip_type_sizes_check()
+                .set_url(remote.as_str())
+                .build()
+        })
+        .await?;
+
+    let mut signers: Vec<PairSigner<Robonomics, sr25519::Pair>> = (0..accounts)
+        .map(|i| PairSigner::new(soak_account(i)))
+        .collect();
+
+    let mut funder = PairSigner::new(funder);
+    if has_utility(&client) {
+        // All accounts are funded by single batch to spend one block only.
+        let transfers = signers
+            .iter()
+            .map(|signer| {
+                client.encode(TransferCall {
+                    to: signer.account_id(),
+                    amount: SOAK_ENDOWMENT,
+                })
+            })
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+        client
+            .watch(
+                BatchCall {
+                    calls: transfers,
+                    _runtime: PhantomData,
+                },
+                &funder,
+            )
+            .await?;
+    } else {
+        // Transfers are sent one by one, inclusion of the last one means all are included.
+        let nonce = client.account(funder.account_id(), None).await?.nonce;
+        funder.set_nonce(nonce);
+        let (last, others) = signers.split_last().expect("accounts are positive; qed");
+        for signer in others {
+            client
+                .submit(
+                    TransferCall {
+                        to: signer.account_id(),
+                        amount: SOAK_ENDOWMENT,
+                    },
+                    &funder,
+                )
+                .await?;
+            funder.increment_nonce();
+        }
+        client
+            .watch(
+                TransferCall {
+                    to: last.account_id(),
+                    amount: SOAK_ENDOWMENT,
+                },
+                &funder,
+            )
+            .await?;
+    }
+    log::info!(
+        target: "robonomics::protocol",
+        "Funded {} soak accounts, sending {} records per second during {:?}",
+        accounts, tps, duration
+    );
+
+    // Records are sent without waiting for inclusion, so nonce is tracked locally.
+    for signer in signers.iter_mut() {
+        let nonce = client.account(signer.account_id(), None).await?.nonce;
+        signer.set_nonce(nonce);
+    }
+
+    let mut report = SoakReport::default();
+    let mut pending = FuturesUnordered::new();
+    let period = Duration::from_secs(1) / tps;
+    let started = Instant::now();
+    let mut next = started;
+    while next < started + duration {
+        let signer = &mut signers[report.submitted % accounts];
+        let record = format!(
+            "soak {} {}",
+            report.submitted,
+            started.elapsed().as_millis()
+        );
+        let xt = client
+            .create_signed(
+                RecordCall {
+                    record: record.into_bytes(),
+                },
+                &*signer,
+            )
+            .await?;
+        signer.increment_nonce();
+        report.submitted += 1;
+
+        let submitted = Instant::now();
+        let watch = client.submit_and_watch_extrinsic(xt);
+        pending.push(async move { watch.await.map(|_| submitted.elapsed()) });
+
+        next += period;
+        let mut tick = Delay::new(next.saturating_duration_since(Instant::now())).fuse();
+        loop {
+            select! {
+                _ = tick => break,
+                result = pending.select_next_some() => record_result(&mut report, result),
+            }
+        }
+    }
+
+    let drain = pending.for_each(|result| {
+        record_result(&mut report, result);
+        future::ready(())
+    });
+    future::select(drain, Delay::new(INCLUSION_TIMEOUT)).await;
+
+    Ok(report)
+}
+
+/// Account single record inclusion result.
+fn record_result<E: std::fmt::Display>(
+    report: &mut SoakReport,
+    result: std::result::Result<Duration, E>,
+) {
+    match result {
+        Ok(latency) => report.latencies.push(latency),
+        Err(e) => log::debug!(target: "robonomics::protocol", "Record isn't included: {}", e),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn report(latencies: &[u64]) -> SoakReport {
+        SoakReport {
+            submitted: 10,
+            latencies: latencies
+                .iter()
+                .cloned()
+                .map(Duration::from_millis)
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn percentile_of_unsorted_latencies() {
+        let report = report(&[30, 10, 50, 20, 40]);
+        assert_eq!(report.percentile(0.0), Some(Duration::from_millis(10)));
+        assert_eq!(report.percentile(50.0), Some(Duration::from_millis(30)));
+        assert_eq!(report.percentile(90.0), Some(Duration::from_millis(50)));
+        assert_eq!(report.percentile(100.0), Some(Duration::from_millis(50)));
+        assert_eq!(report.percentile(150.0), Some(Duration::from_millis(50)));
+    }
+
+    #[test]
+    fn percentile_of_single_and_no_latencies() {
+        assert_eq!(report(&[]).percentile(50.0), None);
+        assert_eq!(
+            report(&[7]).percentile(99.0),
+            Some(Duration::from_millis(7))
+        );
+    }
+
+    #[test]
+    fn inclusion_rate() {
+        assert_eq!(report(&[1, 2, 3, 4, 5]).inclusion_rate(), 0.5);
+        assert_eq!(SoakReport::default().inclusion_rate(), 0.0);
+    }
+}
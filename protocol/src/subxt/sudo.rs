@@ -0,0 +1,83 @@
+///////////////////////////////////////////////////////////////////////////////
+//
+//  Copyright 2018-2021 Robonomics Network <research@robonomics.network>
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+//
+///////////////////////////////////////////////////////////////////////////////
+//! Robonomics sudo calls.
+
+use super::{pallet_sudo::*, ConnectPolicy, Robonomics};
+use crate::error::{Error, Result};
+
+use core::marker::PhantomData;
+use sp_core::crypto::{Pair, Ss58Codec};
+use substrate_subxt::{Encoded, PairSigner, Signer};
+
+/// Sign encoded runtime call, wrap it into sudo call and send using remote Robonomics node.
+///
+/// Signer account should be the on-chain sudo key holder.
+pub async fn call<T: Pair>(
+    signer: T,
+    remote: String,
+    policy: ConnectPolicy,
+    call: Vec<u8>,
+) -> Result<[u8; 32]>
+where
+    sp_runtime::MultiSigner: From<<T as Pair>::Public>,
+    sp_runtime::MultiSignature: From<<T as Pair>::Signature>,
+    <T as Pair>::Signature: codec::Codec,
+{
+    let subxt_signer = PairSigner::new(signer);
+    let client = policy
+        .connect(|| {
+            substrate_subxt::ClientBuilder::<Robonomics>::new()
+                .skip_type_sizes_check()
+                .set_url(remote.as_str())
+                .build()
+        })
+        .await?;
+
+    let sudo_key = client
+        .fetch(
+            &KeyStore {
+                _runtime: PhantomData,
+            },
+            None,
+        )
+        .await?
+        .ok_or("Sudo key isn't set on chain")?;
+    let account = subxt_signer.account_id();
+    if account != &sudo_key {
+        return Err(Error::NotSudo(
+            account.to_ss58check(),
+            sudo_key.to_ss58check(),
+        ));
+    }
+
+    let xt_hash = client
+        .submit(
+            SudoCall {
+                call: &Encoded(call),
+                _runtime: PhantomData,
+            },
+            &subxt_signer,
+        )
+        .await?;
+
+    log::debug!(
+        target: "robonomics::protocol",
+        "Sudo call submited in extrinsic with hash {}", xt_hash
+    );
+    Ok(xt_hash.into())
+}
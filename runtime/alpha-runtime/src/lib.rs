@@ -34,6 +34,13 @@ pub fn wasm_binary_unwrap() -> &'static [u8] {
     )
 }
 
+#[cfg(feature = "std")]
+/// Wasm binary, error is returned when built with `BUILD_DUMMY_WASM_BINARY`.
+pub fn wasm_binary() -> Result<&'static [u8], &'static str> {
+    WASM_BINARY
+        .ok_or("Runtime wasm not built; enable std build without `BUILD_DUMMY_WASM_BINARY` flag")
+}
+
 pub mod constants;
 
 use frame_support::{
@@ -72,7 +79,7 @@ pub const VERSION: RuntimeVersion = RuntimeVersion {
     spec_name: create_runtime_str!("robonomics-alpha"),
     impl_name: create_runtime_str!("robonomics-airalab"),
     authoring_version: 12,
-    spec_version: 12,
+    spec_version: 13,
     impl_version: 0,
     apis: RUNTIME_API_VERSIONS,
     transaction_version: 1,
@@ -177,6 +184,24 @@ impl pallet_utility::Config for Runtime {
     type WeightInfo = ();
 }
 
+parameter_types! {
+    // One storage item; key size is 32; value is size 4+4+16+32 bytes = 56 bytes.
+    pub const DepositBase: Balance = deposit(1, 88);
+    // Additional storage item size of 32 bytes.
+    pub const DepositFactor: Balance = deposit(0, 32);
+    pub const MaxSignatories: u16 = 100;
+}
+
+impl pallet_multisig::Config for Runtime {
+    type Event = Event;
+    type Call = Call;
+    type Currency = Balances;
+    type DepositBase = DepositBase;
+    type DepositFactor = DepositFactor;
+    type MaxSignatories = MaxSignatories;
+    type WeightInfo = ();
+}
+
 parameter_types! {
     pub const MinimumPeriod: Moment = MILLISECS_PER_BLOCK / 2;
 }
@@ -495,6 +520,7 @@ construct_runtime! {
         // Basic stuff.
         System: frame_system::{Pallet, Call, Config, Storage, Event<T>},
         Utility: pallet_utility::{Pallet, Call, Storage, Event},
+        Multisig: pallet_multisig::{Pallet, Call, Storage, Event<T>},
         Timestamp: pallet_timestamp::{Pallet, Call, Storage, Inherent},
         Identity: pallet_identity::{Pallet, Call, Storage, Event<T>},
         Sudo: pallet_sudo::{Pallet, Call, Storage, Event<T>, Config<T>},